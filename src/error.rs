@@ -11,6 +11,9 @@ error_chain! {
 
         /// The value has already been defined.
         AlreadyDefined { }
+
+        /// The operation is not possible in the current state.
+        InvalidState { }
     }
 
     foreign_links {
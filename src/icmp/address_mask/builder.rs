@@ -0,0 +1,146 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::address_mask::Packet;
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Address Mask Request/Reply packet builder.
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    kind: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(12)?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            kind: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.kind {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it a request.
+    pub fn request(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::AddressMaskRequest.into();
+
+        Ok(self)
+    }
+
+    /// Make it a reply.
+    pub fn reply(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::AddressMaskReply.into();
+
+        Ok(self)
+    }
+
+    /// Packet identifier.
+    pub fn identifier(mut self, value: u16) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn sequence(mut self, value: u16) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Address mask.
+    pub fn mask(mut self, value: Ipv4Addr) -> Result<Self> {
+        self.buffer.data_mut()[8..12].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn simple() {
+        let mut packet = icmp::Builder::default()
+            .address_mask()
+            .unwrap()
+            .reply()
+            .unwrap()
+            .identifier(42)
+            .unwrap()
+            .sequence(1)
+            .unwrap()
+            .mask("255.255.255.0".parse().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 12);
+
+        let icmp = icmp::Packet::new(&packet[..]).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::AddressMaskReply);
+
+        let mask = icmp.address_mask().unwrap();
+        assert!(mask.is_reply());
+        assert_eq!(mask.identifier(), 42);
+        assert_eq!(mask.sequence(), 1);
+        assert_eq!(mask.mask(), "255.255.255.0".parse::<Ipv4Addr>().unwrap());
+
+        let mut icmp = icmp::Packet::new(&mut packet[..]).unwrap();
+        icmp.address_mask_mut().unwrap().checked().make_request().unwrap().set_mask(Ipv4Addr::UNSPECIFIED).unwrap();
+        assert_eq!(icmp.kind(), icmp::Kind::AddressMaskRequest);
+        assert!(icmp.is_valid());
+    }
+}
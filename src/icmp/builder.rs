@@ -5,7 +5,9 @@ use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::error::*;
 use crate::icmp::checksum;
-use crate::icmp::{echo, information, timestamp};
+use crate::icmp::{address_mask, echo, extended_echo, information, parameter_problem, previous, redirect_message, router_advertisement, router_solicitation, timestamp, traceroute};
+use crate::ip;
+use crate::packet::Packet;
 
 /// ICMP packet builder.
 #[derive(Debug)]
@@ -38,6 +40,14 @@ impl Default for Builder<buffer::Dynamic> {
 }
 
 impl<B: Buffer> Builder<B> {
+    /// Create an Address Mask Request/Reply packet.
+    pub fn address_mask(self) -> Result<address_mask::Builder<B>> {
+        let mut address_mask = address_mask::Builder::with(self.buffer)?;
+        address_mask.finalizer().extend(self.finalizer);
+
+        Ok(address_mask)
+    }
+
     /// Build an Echo Request/Reply packet.
     pub fn echo(self) -> Result<echo::Builder<B>> {
         let mut echo = echo::Builder::with(self.buffer)?;
@@ -46,6 +56,14 @@ impl<B: Buffer> Builder<B> {
         Ok(echo)
     }
 
+    /// Create an Extended Echo Request/Reply packet.
+    pub fn extended_echo(self) -> Result<extended_echo::Builder<B>> {
+        let mut extended_echo = extended_echo::Builder::with(self.buffer)?;
+        extended_echo.finalizer().extend(self.finalizer);
+
+        Ok(extended_echo)
+    }
+
     /// Create an Information Request/Reply packet.
     pub fn information(self) -> Result<information::Builder<B>> {
         let mut information = information::Builder::with(self.buffer)?;
@@ -54,6 +72,46 @@ impl<B: Buffer> Builder<B> {
         Ok(information)
     }
 
+    /// Create a Parameter Problem packet.
+    pub fn parameter_problem(self) -> Result<parameter_problem::Builder<B>> {
+        let mut parameter_problem = parameter_problem::Builder::with(self.buffer)?;
+        parameter_problem.finalizer().extend(self.finalizer);
+
+        Ok(parameter_problem)
+    }
+
+    /// Create a Source Quench, Destination Unreachable or Time Exceeded packet.
+    pub fn previous(self) -> Result<previous::Builder<B>> {
+        let mut previous = previous::Builder::with(self.buffer)?;
+        previous.finalizer().extend(self.finalizer);
+
+        Ok(previous)
+    }
+
+    /// Create a Redirect Message packet.
+    pub fn redirect_message(self) -> Result<redirect_message::Builder<B>> {
+        let mut redirect_message = redirect_message::Builder::with(self.buffer)?;
+        redirect_message.finalizer().extend(self.finalizer);
+
+        Ok(redirect_message)
+    }
+
+    /// Create a Router Advertisement packet.
+    pub fn router_advertisement(self) -> Result<router_advertisement::Builder<B>> {
+        let mut router_advertisement = router_advertisement::Builder::with(self.buffer)?;
+        router_advertisement.finalizer().extend(self.finalizer);
+
+        Ok(router_advertisement)
+    }
+
+    /// Create a Router Solicitation packet.
+    pub fn router_solicitation(self) -> Result<router_solicitation::Builder<B>> {
+        let mut router_solicitation = router_solicitation::Builder::with(self.buffer)?;
+        router_solicitation.finalizer().extend(self.finalizer);
+
+        Ok(router_solicitation)
+    }
+
     /// Create a Timestamp Request/Reply packet.
     pub fn timestamp(self) -> Result<timestamp::Builder<B>> {
         let mut timestamp = timestamp::Builder::with(self.buffer)?;
@@ -61,6 +119,14 @@ impl<B: Buffer> Builder<B> {
 
         Ok(timestamp)
     }
+
+    /// Create a Traceroute packet.
+    pub fn traceroute(self) -> Result<traceroute::Builder<B>> {
+        let mut traceroute = traceroute::Builder::with(self.buffer)?;
+        traceroute.finalizer().extend(self.finalizer);
+
+        Ok(traceroute)
+    }
 }
 
 pub(in crate::icmp) fn prepare<B: Buffer>(finalizer: &mut Finalization, buffer: &B) {
@@ -75,6 +141,18 @@ pub(in crate::icmp) fn prepare<B: Buffer>(finalizer: &mut Finalization, buffer:
     });
 }
 
+pub(in crate::icmp) fn quote<B: Buffer, I: AsRef<[u8]>>(buffer: &mut B, packet: &ip::v4::Packet<I>, length: usize) -> Result<()> {
+    let (header, payload) = packet.split();
+    let payload = &payload[..payload.len().min(length)];
+
+    for byte in header.iter().chain(payload) {
+        buffer.more(1)?;
+        *buffer.data_mut().last_mut().unwrap() = *byte;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use crate::builder::Builder;
@@ -88,6 +88,67 @@ pub enum ParameterProblem {
     Unknown(u8),
 }
 
+/// Codes for Time Exceeded packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimeExceeded {
+    ///
+    TtlExpiredInTransit,
+
+    ///
+    FragmentReassemblyTimeExceeded,
+
+    ///
+    Unknown(u8),
+}
+
+/// Codes for Router Advertisement packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum RouterAdvertisement {
+    ///
+    NormalRouterAdvertisement,
+
+    ///
+    DoesNotRouteCommonTraffic,
+
+    ///
+    Unknown(u8),
+}
+
+/// Codes for Extended Echo Reply packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ExtendedEchoReply {
+    ///
+    NoError,
+
+    ///
+    MalformedQuery,
+
+    ///
+    NoSuchInterface,
+
+    ///
+    NoSuchTableEntry,
+
+    ///
+    MultipleInterfacesSatisfyQuery,
+
+    ///
+    Unknown(u8),
+}
+
+/// Codes for Traceroute packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TraceRoute {
+    ///
+    OutboundPacketForwarded,
+
+    ///
+    NoRoute,
+
+    ///
+    Unknown(u8),
+}
+
 impl From<u8> for DestinationUnreachable {
     fn from(value: u8) -> Self {
         use self::DestinationUnreachable::*;
@@ -193,3 +254,105 @@ impl Into<u8> for ParameterProblem {
         }
     }
 }
+
+impl From<u8> for TimeExceeded {
+    fn from(value: u8) -> Self {
+        use self::TimeExceeded::*;
+
+        match value {
+            0 => TtlExpiredInTransit,
+            1 => FragmentReassemblyTimeExceeded,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for TimeExceeded {
+    fn into(self) -> u8 {
+        use self::TimeExceeded::*;
+
+        match self {
+            TtlExpiredInTransit => 0,
+            FragmentReassemblyTimeExceeded => 1,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for RouterAdvertisement {
+    fn from(value: u8) -> Self {
+        use self::RouterAdvertisement::*;
+
+        match value {
+            0 => NormalRouterAdvertisement,
+            16 => DoesNotRouteCommonTraffic,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for RouterAdvertisement {
+    fn into(self) -> u8 {
+        use self::RouterAdvertisement::*;
+
+        match self {
+            NormalRouterAdvertisement => 0,
+            DoesNotRouteCommonTraffic => 16,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for ExtendedEchoReply {
+    fn from(value: u8) -> Self {
+        use self::ExtendedEchoReply::*;
+
+        match value {
+            0 => NoError,
+            1 => MalformedQuery,
+            2 => NoSuchInterface,
+            3 => NoSuchTableEntry,
+            4 => MultipleInterfacesSatisfyQuery,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for ExtendedEchoReply {
+    fn into(self) -> u8 {
+        use self::ExtendedEchoReply::*;
+
+        match self {
+            NoError => 0,
+            MalformedQuery => 1,
+            NoSuchInterface => 2,
+            NoSuchTableEntry => 3,
+            MultipleInterfacesSatisfyQuery => 4,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for TraceRoute {
+    fn from(value: u8) -> Self {
+        use self::TraceRoute::*;
+
+        match value {
+            0 => OutboundPacketForwarded,
+            1 => NoRoute,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for TraceRoute {
+    fn into(self) -> u8 {
+        use self::TraceRoute::*;
+
+        match self {
+            OutboundPacketForwarded => 0,
+            NoRoute => 1,
+            Unknown(v) => v,
+        }
+    }
+}
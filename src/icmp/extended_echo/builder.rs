@@ -0,0 +1,270 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::extended_echo::{Packet, State};
+use crate::icmp::extension::{self, object::Identification};
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Extended Echo Request/Reply packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    kind: bool,
+    interface: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            kind: false,
+            interface: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.kind {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it a request.
+    pub fn request(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::ExtendedEchoRequest.into();
+        self.buffer.data_mut()[1] = 0;
+
+        Ok(self)
+    }
+
+    /// Make it a reply.
+    pub fn reply(mut self, code: code::ExtendedEchoReply) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::ExtendedEchoReply.into();
+        self.buffer.data_mut()[1] = code.into();
+
+        Ok(self)
+    }
+
+    /// Packet identifier.
+    pub fn identifier(mut self, value: u16) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn sequence(mut self, value: u8) -> Result<Self> {
+        self.buffer.data_mut()[6] = value;
+
+        Ok(self)
+    }
+
+    /// Whether the probed interface resides on the proxy node, for requests.
+    pub fn local(self, value: bool) -> Result<Self> {
+        self.flag(0b1, value)
+    }
+
+    /// State of the neighbor table entry, for replies.
+    pub fn state(mut self, value: State) -> Result<Self> {
+        let value: u8 = value.into();
+
+        if value > 0b111 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let flags = &mut self.buffer.data_mut()[7];
+        *flags = (*flags & 0b0001_1111) | (value << 5);
+
+        Ok(self)
+    }
+
+    /// Whether the probed interface is active, for replies.
+    pub fn active(self, value: bool) -> Result<Self> {
+        self.flag(0b100, value)
+    }
+
+    /// Whether the probed interface runs IPv4, for replies.
+    pub fn ipv4(self, value: bool) -> Result<Self> {
+        self.flag(0b10, value)
+    }
+
+    /// Whether the probed interface runs IPv6, for replies.
+    pub fn ipv6(self, value: bool) -> Result<Self> {
+        self.flag(0b1, value)
+    }
+
+    /// Interface to probe, for requests.
+    pub fn interface(mut self, value: Identification) -> Result<Self> {
+        if self.interface {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.interface = true;
+
+        let offset = extension::builder::structure(&mut self.buffer)?;
+        extension::builder::identification(&mut self.buffer, offset, value)?;
+
+        Ok(self)
+    }
+
+    fn flag(mut self, flag: u8, value: bool) -> Result<Self> {
+        if value {
+            self.buffer.data_mut()[7] |= flag;
+        } else {
+            self.buffer.data_mut()[7] &= !flag;
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use crate::icmp::extended_echo::State;
+    use crate::icmp::extension::object::Identification;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn request() {
+        let packet = ip::v4::Builder::default()
+            .source("192.0.2.1".parse().unwrap())
+            .unwrap()
+            .destination("192.0.2.2".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .extended_echo()
+            .unwrap()
+            .request()
+            .unwrap()
+            .identifier(42)
+            .unwrap()
+            .sequence(7)
+            .unwrap()
+            .local(true)
+            .unwrap()
+            .interface(Identification::Name("eth0.1"))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 20 + 8 + 4 + 4 + 8);
+
+        let icmp = icmp::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::ExtendedEchoRequest);
+
+        let echo = icmp.extended_echo().unwrap();
+        assert!(echo.is_request());
+        assert!(echo.is_local());
+        assert_eq!(echo.identifier(), 42);
+        assert_eq!(echo.sequence(), 7);
+
+        let extension = echo.extension().unwrap();
+        assert!(extension.is_valid());
+
+        let object = extension.objects().next().unwrap().unwrap();
+        assert_eq!(object.identification().unwrap(), Identification::Name("eth0.1"));
+    }
+
+    #[test]
+    fn reply() {
+        let packet = icmp::Builder::default()
+            .extended_echo()
+            .unwrap()
+            .reply(icmp::code::ExtendedEchoReply::NoError)
+            .unwrap()
+            .identifier(42)
+            .unwrap()
+            .sequence(7)
+            .unwrap()
+            .state(State::Reachable)
+            .unwrap()
+            .active(true)
+            .unwrap()
+            .ipv4(true)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet[7], 0b0100_0110);
+
+        let icmp = icmp::Packet::new(&packet[..]).unwrap();
+        assert!(icmp.is_valid());
+
+        let echo = icmp.extended_echo().unwrap();
+        assert!(echo.is_reply());
+        assert!(!echo.is_local());
+        assert_eq!(echo.state(), State::Reachable);
+        assert!(echo.is_active());
+        assert!(echo.is_ipv4());
+        assert!(!echo.is_ipv6());
+        assert!(echo.extension().is_err());
+    }
+
+    #[test]
+    fn identification() {
+        for value in &[
+            Identification::Index(3),
+            Identification::Address("192.0.2.2".parse().unwrap()),
+            Identification::Address("2001:db8::2".parse().unwrap()),
+        ] {
+            let packet = icmp::Builder::default().extended_echo().unwrap().request().unwrap().interface(*value).unwrap().build().unwrap();
+
+            let icmp = icmp::Packet::new(&packet[..]).unwrap();
+            let echo = icmp.extended_echo().unwrap();
+            let extension = echo.extension().unwrap();
+            assert!(extension.is_valid());
+            assert_eq!(extension.objects().next().unwrap().unwrap().identification().unwrap(), *value);
+        }
+
+        assert!(icmp::Builder::default().extended_echo().unwrap().state(State::Unknown(8)).is_err());
+    }
+}
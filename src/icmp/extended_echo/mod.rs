@@ -0,0 +1,5 @@
+mod packet;
+pub use self::packet::{Packet, State};
+
+mod builder;
+pub use self::builder::Builder;
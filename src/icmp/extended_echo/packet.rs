@@ -0,0 +1,274 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmp::extension::Extension;
+use crate::icmp::packet::Checked;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Extended Echo Request/Reply packet parser, as defined in RFC 8335.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+/// State of the probed interface's neighbor table entry.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum State {
+    ///
+    Reserved,
+
+    ///
+    Incomplete,
+
+    ///
+    Reachable,
+
+    ///
+    Stale,
+
+    ///
+    Delay,
+
+    ///
+    Probe,
+
+    ///
+    Failed,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::extended_echo::Packet")
+            .field("request", &self.is_request())
+            .field("identifier", &self.identifier())
+            .field("sequence", &self.sequence())
+            .field("flags", &self.buffer.as_ref()[7])
+            .field("extension", &self.extension().ok())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create an Extended Echo Request/Reply packet without checking the
+    /// buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse an Extended Echo Request/Reply packet, checking the buffer
+    /// contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        match Kind::from(packet.buffer.as_ref()[0]) {
+            Kind::ExtendedEchoRequest | Kind::ExtendedEchoReply => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_ref()[..8 + payload].split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_mut()[..8 + payload].split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Check if it's a Request packet.
+    pub fn is_request(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::ExtendedEchoRequest
+    }
+
+    /// Check if it's a Reply packet.
+    pub fn is_reply(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::ExtendedEchoReply
+    }
+
+    /// Packet identifier.
+    pub fn identifier(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Packet sequence.
+    pub fn sequence(&self) -> u8 {
+        self.buffer.as_ref()[6]
+    }
+
+    /// Whether the probed interface resides on the proxy node, for requests.
+    pub fn is_local(&self) -> bool {
+        self.is_request() && self.buffer.as_ref()[7] & 0b1 != 0
+    }
+
+    /// State of the neighbor table entry, for replies.
+    pub fn state(&self) -> State {
+        (self.buffer.as_ref()[7] >> 5).into()
+    }
+
+    /// Whether the probed interface is active, for replies.
+    pub fn is_active(&self) -> bool {
+        self.is_reply() && self.buffer.as_ref()[7] & 0b100 != 0
+    }
+
+    /// Whether the probed interface runs IPv4, for replies.
+    pub fn is_ipv4(&self) -> bool {
+        self.is_reply() && self.buffer.as_ref()[7] & 0b10 != 0
+    }
+
+    /// Whether the probed interface runs IPv6, for replies.
+    pub fn is_ipv6(&self) -> bool {
+        self.is_reply() && self.buffer.as_ref()[7] & 0b1 != 0
+    }
+
+    /// Extension structure identifying the probed interface, for requests.
+    pub fn extension(&self) -> Result<Extension<&[u8]>> {
+        Extension::new(self.payload())
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Packet identifier.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn set_sequence(&mut self, value: u8) -> Result<&mut Self> {
+        self.buffer.as_mut()[6] = value;
+
+        Ok(self)
+    }
+
+    /// Create a checksumed setter.
+    pub fn checked(&mut self) -> Checked<Self> {
+        Checked { packet: self }
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]> + 'a> Checked<'a, Packet<B>> {
+    /// Packet identifier.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_identifier(value)?;
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn set_sequence(&mut self, value: u8) -> Result<&mut Self> {
+        self.packet.set_sequence(value)?;
+        Ok(self)
+    }
+}
+
+impl From<u8> for State {
+    fn from(value: u8) -> Self {
+        use self::State::*;
+
+        match value {
+            0 => Reserved,
+            1 => Incomplete,
+            2 => Reachable,
+            3 => Stale,
+            4 => Delay,
+            5 => Probe,
+            6 => Failed,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for State {
+    fn into(self) -> u8 {
+        use self::State::*;
+
+        match self {
+            Reserved => 0,
+            Incomplete => 1,
+            Reachable => 2,
+            Stale => 3,
+            Delay => 4,
+            Probe => 5,
+            Failed => 6,
+            Unknown(v) => v,
+        }
+    }
+}
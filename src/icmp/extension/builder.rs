@@ -0,0 +1,178 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+use std::net::IpAddr;
+
+use crate::buffer::Buffer;
+use crate::error::*;
+use crate::icmp::checksum;
+use crate::icmp::extension::interface::{ADDRESS, INDEX, MTU, NAME};
+use crate::icmp::extension::object::{Class, Identification, Label};
+use crate::icmp::extension::Role;
+
+/// Minimum length of the quoted packet when extensions are present.
+const QUOTE: usize = 128;
+
+/// Pad the quoted packet, set its length and append an empty extension
+/// structure, returning its offset.
+pub fn start<B: Buffer>(buffer: &mut B) -> Result<usize> {
+    let quoted = buffer.length() - 8;
+    let padded = (quoted.max(QUOTE) + 3) / 4 * 4;
+
+    if padded / 4 > 255 {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    buffer.more(padded - quoted)?;
+    buffer.data_mut()[5] = (padded / 4) as u8;
+
+    for byte in &mut buffer.data_mut()[8 + quoted..] {
+        *byte = 0;
+    }
+
+    structure(buffer)
+}
+
+/// Append an empty extension structure, returning its offset.
+pub fn structure<B: Buffer>(buffer: &mut B) -> Result<usize> {
+    let offset = buffer.length();
+    buffer.more(4)?;
+
+    let out = &mut buffer.data_mut()[offset..];
+    out[0] = 2 << 4;
+    out[1] = 0;
+    out[2] = 0;
+    out[3] = 0;
+
+    Ok(offset)
+}
+
+/// Append an object to the extension structure at the given offset, updating
+/// its checksum.
+pub fn object<B: Buffer>(buffer: &mut B, offset: usize, class: Class, kind: u8, data: &[u8]) -> Result<()> {
+    let length = 4 + data.len();
+
+    if length > u16::max_value() as usize || data.len() % 4 != 0 {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    let start = buffer.length();
+    buffer.more(length)?;
+
+    let out = &mut buffer.data_mut()[start..];
+    Cursor::new(&mut out[0..]).write_u16::<BigEndian>(length as u16)?;
+    out[2] = class.into();
+    out[3] = kind;
+    out[4..].copy_from_slice(data);
+
+    let checksum = checksum(&buffer.data()[offset..]);
+    Cursor::new(&mut buffer.data_mut()[offset + 2..]).write_u16::<BigEndian>(checksum)?;
+
+    Ok(())
+}
+
+/// Append an MPLS label stack object, as defined in RFC 4950.
+pub fn mpls<'a, B: Buffer, T: IntoIterator<Item = &'a Label>>(buffer: &mut B, offset: usize, value: T) -> Result<()> {
+    let mut data = Vec::new();
+
+    for label in value {
+        if label.label > 0xf_ffff || label.traffic_class > 0b111 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let entry = label.label << 12 | u32::from(label.traffic_class) << 9 | u32::from(label.bottom) << 8 | u32::from(label.ttl);
+        data.write_u32::<BigEndian>(entry)?;
+    }
+
+    if data.is_empty() {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    object(buffer, offset, Class::MplsLabelStack, 1, &data)
+}
+
+/// Append an interface information object, as defined in RFC 5837.
+pub fn interface<B: Buffer>(buffer: &mut B, offset: usize, role: Role, index: Option<u32>, address: Option<IpAddr>, name: Option<&str>, mtu: Option<u32>) -> Result<()> {
+    let mut kind = Into::<u8>::into(role) << 6;
+    let mut data = Vec::new();
+
+    if let Some(index) = index {
+        kind |= INDEX;
+        data.write_u32::<BigEndian>(index)?;
+    }
+
+    if let Some(address) = address {
+        kind |= ADDRESS;
+
+        match address {
+            IpAddr::V4(address) => {
+                data.write_u16::<BigEndian>(1)?;
+                data.write_u16::<BigEndian>(0)?;
+                data.extend_from_slice(&address.octets());
+            }
+
+            IpAddr::V6(address) => {
+                data.write_u16::<BigEndian>(2)?;
+                data.write_u16::<BigEndian>(0)?;
+                data.extend_from_slice(&address.octets());
+            }
+        }
+    }
+
+    if let Some(name) = name {
+        let length = (1 + name.len() + 3) / 4 * 4;
+
+        if length > 64 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        kind |= NAME;
+        data.push(length as u8);
+        data.extend_from_slice(name.as_bytes());
+        data.resize(data.len() + length - 1 - name.len(), 0);
+    }
+
+    if let Some(mtu) = mtu {
+        kind |= MTU;
+        data.write_u32::<BigEndian>(mtu)?;
+    }
+
+    object(buffer, offset, Class::InterfaceInformation, kind, &data)
+}
+
+/// Append an interface identification object, as defined in RFC 8335.
+pub fn identification<B: Buffer>(buffer: &mut B, offset: usize, value: Identification) -> Result<()> {
+    let mut data = Vec::new();
+
+    let kind = match value {
+        Identification::Name(name) => {
+            data.extend_from_slice(name.as_bytes());
+            data.resize((name.len() + 3) / 4 * 4, 0);
+
+            1
+        }
+
+        Identification::Index(index) => {
+            data.write_u32::<BigEndian>(index)?;
+
+            2
+        }
+
+        Identification::Address(IpAddr::V4(address)) => {
+            data.write_u16::<BigEndian>(1)?;
+            data.extend_from_slice(&[4, 0]);
+            data.extend_from_slice(&address.octets());
+
+            3
+        }
+
+        Identification::Address(IpAddr::V6(address)) => {
+            data.write_u16::<BigEndian>(2)?;
+            data.extend_from_slice(&[16, 0]);
+            data.extend_from_slice(&address.octets());
+
+            3
+        }
+    };
+
+    object(buffer, offset, Class::InterfaceIdentification, kind, &data)
+}
@@ -0,0 +1,202 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str;
+
+use crate::error::*;
+
+/// Interface information object parser, as defined in RFC 5837, over the
+/// whole object.
+pub struct Interface<B> {
+    buffer: B,
+}
+
+/// Role of the interface an interface information object describes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Role {
+    /// The interface the packet arrived on.
+    Incoming,
+
+    /// The sub-IP component of the interface the packet arrived on.
+    SubIp,
+
+    /// The interface the packet would have been forwarded on.
+    Outgoing,
+
+    /// The next hop the packet would have been forwarded to.
+    NextHop,
+}
+
+pub(in crate::icmp::extension) const INDEX: u8 = 0b1000;
+pub(in crate::icmp::extension) const ADDRESS: u8 = 0b0100;
+pub(in crate::icmp::extension) const NAME: u8 = 0b0010;
+pub(in crate::icmp::extension) const MTU: u8 = 0b0001;
+
+/// Offsets of the fields present in the object.
+#[derive(Default)]
+struct Fields {
+    index: Option<usize>,
+    address: Option<usize>,
+    name: Option<usize>,
+    mtu: Option<usize>,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Interface<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::extension::Interface")
+            .field("role", &self.role())
+            .field("index", &self.index())
+            .field("address", &self.address())
+            .field("name", &self.name())
+            .field("mtu", &self.mtu())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Interface<B> {
+    /// Parse an interface information object, checking the buffer contents
+    /// are correct.
+    pub fn new(buffer: B) -> Result<Interface<B>> {
+        let object = Interface { buffer };
+
+        if object.buffer.as_ref().len() < 4 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        fields(object.buffer.as_ref())?;
+
+        Ok(object)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Interface<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Interface<B> {
+    /// Role of the interface.
+    pub fn role(&self) -> Role {
+        (self.buffer.as_ref()[3] >> 6).into()
+    }
+
+    /// Interface index.
+    pub fn index(&self) -> Option<u32> {
+        let offset = fields(self.buffer.as_ref()).unwrap().index?;
+
+        Some((&self.buffer.as_ref()[offset..]).read_u32::<BigEndian>().unwrap())
+    }
+
+    /// Interface address.
+    pub fn address(&self) -> Option<IpAddr> {
+        let offset = fields(self.buffer.as_ref()).unwrap().address?;
+        let buffer = &self.buffer.as_ref()[offset..];
+
+        match (&buffer[0..]).read_u16::<BigEndian>().unwrap() {
+            1 => Some(IpAddr::V4(Ipv4Addr::from((&buffer[4..]).read_u32::<BigEndian>().unwrap()))),
+            _ => Some(IpAddr::V6(Ipv6Addr::from((&buffer[4..]).read_u128::<BigEndian>().unwrap()))),
+        }
+    }
+
+    /// Interface name.
+    pub fn name(&self) -> Option<&str> {
+        let offset = fields(self.buffer.as_ref()).unwrap().name?;
+        let buffer = &self.buffer.as_ref()[offset..];
+        let name = &buffer[1..buffer[0] as usize];
+        let length = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+
+        Some(str::from_utf8(&name[..length]).unwrap())
+    }
+
+    /// MTU of the interface.
+    pub fn mtu(&self) -> Option<u32> {
+        let offset = fields(self.buffer.as_ref()).unwrap().mtu?;
+
+        Some((&self.buffer.as_ref()[offset..]).read_u32::<BigEndian>().unwrap())
+    }
+}
+
+fn fields(buffer: &[u8]) -> Result<Fields> {
+    let kind = buffer[3];
+    let mut fields = Fields::default();
+    let mut offset = 4;
+
+    if kind & INDEX != 0 {
+        fields.index = Some(offset);
+        offset += 4;
+    }
+
+    if kind & ADDRESS != 0 {
+        if buffer.len() < offset + 4 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        fields.address = Some(offset);
+        offset += match (&buffer[offset..]).read_u16::<BigEndian>().unwrap() {
+            1 => 8,
+            2 => 20,
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        };
+    }
+
+    if kind & NAME != 0 {
+        if buffer.len() < offset + 1 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        let length = buffer[offset] as usize;
+
+        if length == 0 || length % 4 != 0 || length > 64 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if buffer.len() < offset + length {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if str::from_utf8(&buffer[offset + 1..offset + length]).is_err() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        fields.name = Some(offset);
+        offset += length;
+    }
+
+    if kind & MTU != 0 {
+        fields.mtu = Some(offset);
+        offset += 4;
+    }
+
+    if buffer.len() < offset {
+        return Err(ErrorKind::SmallBuffer.into());
+    }
+
+    Ok(fields)
+}
+
+impl From<u8> for Role {
+    fn from(value: u8) -> Self {
+        use self::Role::*;
+
+        match value & 0b11 {
+            0 => Incoming,
+            1 => SubIp,
+            2 => Outgoing,
+            _ => NextHop,
+        }
+    }
+}
+
+impl Into<u8> for Role {
+    fn into(self) -> u8 {
+        use self::Role::*;
+
+        match self {
+            Incoming => 0,
+            SubIp => 1,
+            Outgoing => 2,
+            NextHop => 3,
+        }
+    }
+}
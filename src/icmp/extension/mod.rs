@@ -0,0 +1,105 @@
+mod structure;
+pub use self::structure::Extension;
+
+/// Extension object parser.
+pub mod object;
+pub use self::object::Object;
+
+mod interface;
+pub use self::interface::{Interface, Role};
+
+pub(in crate::icmp) mod builder;
+
+use crate::error::*;
+
+/// Iterator over extension objects.
+pub struct ObjectIter<'a> {
+    pub(in crate::icmp) buffer: &'a [u8],
+}
+
+impl<'a> Iterator for ObjectIter<'a> {
+    type Item = Result<Object<&'a [u8]>>;
+
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        use crate::size::Size;
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        match Object::new(self.buffer) {
+            Ok(object) => {
+                self.buffer = &self.buffer[object.size()..];
+                Some(Ok(object))
+            }
+
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::icmp::extension::object::{Class, Label};
+    use crate::icmp::extension::{Extension, Role};
+    use std::net::IpAddr;
+
+    #[test]
+    fn values() {
+        let raw = [
+            0x20u8, 0x00, 0x03, 0x52, 0x00, 0x0c, 0x01, 0x01, 0x03, 0xe8, 0x00, 0x01, 0x05, 0xdc, 0x5b, 0x01, 0x00, 0x1c, 0x02, 0x0f, 0x00, 0x00, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0xc0, 0x00, 0x02,
+            0x01, 0x08, 0x65, 0x74, 0x68, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0xdc,
+        ];
+
+        let extension = Extension::new(&raw[..]).unwrap();
+        assert_eq!(extension.version(), 2);
+        assert!(extension.is_valid());
+
+        let objects = extension.objects().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(objects.len(), 2);
+
+        assert_eq!(objects[0].class(), Class::MplsLabelStack);
+        assert_eq!(
+            objects[0].mpls().unwrap().collect::<Vec<_>>(),
+            vec![
+                Label {
+                    label: 16000,
+                    traffic_class: 0,
+                    bottom: false,
+                    ttl: 1,
+                },
+                Label {
+                    label: 24005,
+                    traffic_class: 5,
+                    bottom: true,
+                    ttl: 1,
+                },
+            ]
+        );
+        assert!(objects[0].interface().is_err());
+
+        let interface = objects[1].interface().unwrap();
+        assert_eq!(interface.role(), Role::Incoming);
+        assert_eq!(interface.index(), Some(3));
+        assert_eq!(interface.address(), Some("192.0.2.1".parse::<IpAddr>().unwrap()));
+        assert_eq!(interface.name(), Some("eth0"));
+        assert_eq!(interface.mtu(), Some(1500));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(Extension::new(&[0x10u8, 0x00, 0x00, 0x00][..]).is_err());
+
+        let extension = Extension::new(&[0x20u8, 0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x01][..]).unwrap();
+        let mut objects = extension.objects();
+        assert!(objects.next().unwrap().is_err());
+        assert!(objects.next().is_none());
+
+        // Interface name length not a multiple of 4.
+        let extension = Extension::new(&[0x20u8, 0x00, 0x00, 0x00, 0x00, 0x08, 0x02, 0x02, 0x03, 0x61, 0x62, 0x00][..]).unwrap();
+        assert!(extension.objects().next().unwrap().unwrap().interface().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str;
+
+use crate::error::*;
+use crate::icmp::extension::Interface;
+use crate::packet::Packet as P;
+
+/// ICMP extension object parser.
+pub struct Object<B> {
+    buffer: B,
+}
+
+sized!(Object,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  0,
+		max:  u16::max_value() as usize - 4,
+		size: p => p.length() as usize - 4,
+	});
+
+/// ICMP extension object class.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Class {
+    ///
+    MplsLabelStack,
+
+    ///
+    InterfaceInformation,
+
+    ///
+    InterfaceIdentification,
+
+    ///
+    Unknown(u8),
+}
+
+/// Entry of an MPLS label stack, as defined in RFC 4950.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Label {
+    /// Label value.
+    pub label: u32,
+
+    /// Traffic class, formerly known as experimental use.
+    pub traffic_class: u8,
+
+    /// Whether it's the bottom of the stack.
+    pub bottom: bool,
+
+    /// Time to live.
+    pub ttl: u8,
+}
+
+/// Interface to probe, as defined in RFC 8335.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Identification<'a> {
+    /// Interface name.
+    Name(&'a str),
+
+    /// Interface index.
+    Index(u32),
+
+    /// Address assigned to the interface.
+    Address(IpAddr),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Object<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::extension::Object")
+            .field("length", &self.length())
+            .field("class", &self.class())
+            .field("kind", &self.kind())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Object<B> {
+    /// Parse an extension object, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Object<B>> {
+        use crate::size::header::Min;
+
+        let object = Object { buffer };
+
+        if object.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        // A length smaller than the header would make the object chain loop
+        // forever.
+        if (object.length() as usize) < Self::min() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if object.buffer.as_ref().len() < object.length() as usize {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(object)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Object<B> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer.as_ref()[..self.length() as usize]
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Object<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref()[..self.length() as usize].split_at(4)
+    }
+}
+
+impl<B: AsRef<[u8]>> Object<B> {
+    /// Length of the object, including the header.
+    pub fn length(&self) -> u16 {
+        (&self.buffer.as_ref()[0..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Object class.
+    pub fn class(&self) -> Class {
+        self.buffer.as_ref()[2].into()
+    }
+
+    /// Object type within the class.
+    pub fn kind(&self) -> u8 {
+        self.buffer.as_ref()[3]
+    }
+
+    /// Parse an MPLS label stack object.
+    pub fn mpls(&self) -> Result<LabelIter> {
+        if self.class() != Class::MplsLabelStack || self.kind() != 1 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if self.payload().len() % 4 != 0 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(LabelIter { buffer: self.payload() })
+    }
+
+    /// Parse an interface information object.
+    pub fn interface(&self) -> Result<Interface<&[u8]>> {
+        if self.class() != Class::InterfaceInformation {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Interface::new(self.as_ref())
+    }
+
+    /// Parse an interface identification object.
+    pub fn identification(&self) -> Result<Identification> {
+        if self.class() != Class::InterfaceIdentification {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let payload = self.payload();
+
+        match self.kind() {
+            1 => {
+                let length = payload.iter().position(|&b| b == 0).unwrap_or(payload.len());
+
+                match str::from_utf8(&payload[..length]) {
+                    Ok(name) => Ok(Identification::Name(name)),
+                    Err(_) => Err(ErrorKind::InvalidPacket.into()),
+                }
+            }
+
+            2 if payload.len() == 4 => Ok(Identification::Index((&payload[0..]).read_u32::<BigEndian>().unwrap())),
+
+            3 if payload.len() >= 4 => {
+                let afi = (&payload[0..]).read_u16::<BigEndian>().unwrap();
+                let address = &payload[4..];
+
+                match (afi, payload[2]) {
+                    (1, 4) if address.len() >= 4 => Ok(Identification::Address(IpAddr::V4(Ipv4Addr::from((&address[0..]).read_u32::<BigEndian>().unwrap())))),
+                    (2, 16) if address.len() >= 16 => Ok(Identification::Address(IpAddr::V6(Ipv6Addr::from((&address[0..]).read_u128::<BigEndian>().unwrap())))),
+                    _ => Err(ErrorKind::InvalidPacket.into()),
+                }
+            }
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+}
+
+/// Iterator over MPLS label stack entries.
+pub struct LabelIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for LabelIter<'a> {
+    type Item = Label;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < 4 {
+            return None;
+        }
+
+        let value = (&self.buffer[0..]).read_u32::<BigEndian>().unwrap();
+        self.buffer = &self.buffer[4..];
+
+        Some(Label {
+            label: value >> 12,
+            traffic_class: (value >> 9) as u8 & 0b111,
+            bottom: value & 0x100 != 0,
+            ttl: value as u8,
+        })
+    }
+}
+
+impl From<u8> for Class {
+    fn from(value: u8) -> Self {
+        use self::Class::*;
+
+        match value {
+            1 => MplsLabelStack,
+            2 => InterfaceInformation,
+            3 => InterfaceIdentification,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for Class {
+    fn into(self) -> u8 {
+        use self::Class::*;
+
+        match self {
+            MplsLabelStack => 1,
+            InterfaceInformation => 2,
+            InterfaceIdentification => 3,
+            Unknown(v) => v,
+        }
+    }
+}
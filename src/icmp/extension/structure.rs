@@ -0,0 +1,87 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+use crate::icmp::checksum;
+use crate::icmp::extension::ObjectIter;
+use crate::packet::Packet as P;
+
+/// ICMP extension structure parser, as defined in RFC 4884.
+pub struct Extension<B> {
+    buffer: B,
+}
+
+sized!(Extension,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Extension<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct(if self.is_valid() { "icmp::extension::Extension" } else { "icmp::extension::Extension!" })
+            .field("version", &self.version())
+            .field("checksum", &self.checksum())
+            .field("objects", &self.objects().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Extension<B> {
+    /// Parse an extension structure, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Extension<B>> {
+        use crate::size::header::Min;
+
+        let extension = Extension { buffer };
+
+        if extension.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if extension.version() != 2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(extension)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Extension<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Extension<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(4)
+    }
+}
+
+impl<B: AsRef<[u8]>> Extension<B> {
+    /// Extension version.
+    pub fn version(&self) -> u8 {
+        self.buffer.as_ref()[0] >> 4
+    }
+
+    /// Checksum of the extension structure.
+    pub fn checksum(&self) -> u16 {
+        (&self.buffer.as_ref()[2..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Verify the extension structure is valid by calculating the checksum.
+    pub fn is_valid(&self) -> bool {
+        checksum(self.buffer.as_ref()) == self.checksum()
+    }
+
+    /// Extension objects.
+    pub fn objects(&self) -> ObjectIter {
+        ObjectIter { buffer: self.payload() }
+    }
+}
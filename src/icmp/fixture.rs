@@ -0,0 +1,25 @@
+use crate::builder::Builder;
+use crate::ip;
+
+/// Build a UDP datagram with an expired TTL, to be quoted by error messages.
+pub fn invoking() -> ip::v4::Packet<Vec<u8>> {
+    let packet = ip::v4::Builder::default()
+        .ttl(1)
+        .unwrap()
+        .source("10.0.0.1".parse().unwrap())
+        .unwrap()
+        .destination("10.0.0.2".parse().unwrap())
+        .unwrap()
+        .udp()
+        .unwrap()
+        .source(1337)
+        .unwrap()
+        .destination(9001)
+        .unwrap()
+        .payload(b"abcdefghijklmnop")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    ip::v4::Packet::new(packet).unwrap()
+}
@@ -49,6 +49,12 @@ pub enum Kind {
     ///
     TraceRoute,
 
+    ///
+    ExtendedEchoRequest,
+
+    ///
+    ExtendedEchoReply,
+
     ///
     Unknown(u8),
 }
@@ -74,6 +80,8 @@ impl From<u8> for Kind {
             17 => AddressMaskRequest,
             18 => AddressMaskReply,
             30 => TraceRoute,
+            42 => ExtendedEchoRequest,
+            43 => ExtendedEchoReply,
             v => Unknown(v),
         }
     }
@@ -100,6 +108,8 @@ impl Into<u8> for Kind {
             AddressMaskRequest => 17,
             AddressMaskReply => 18,
             TraceRoute => 30,
+            ExtendedEchoRequest => 42,
+            ExtendedEchoReply => 43,
             Unknown(v) => v,
         }
     }
@@ -10,9 +10,18 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+/// Address Mask Request/Reply.
+pub mod address_mask;
+
 /// Echo Request/Reply.
 pub mod echo;
 
+/// Extended Echo Request/Reply.
+pub mod extended_echo;
+
+/// Multi-part message extensions.
+pub mod extension;
+
 /// Information Request/Reply.
 pub mod information;
 
@@ -25,9 +34,21 @@ pub mod previous;
 /// Redirect Message.
 pub mod redirect_message;
 
+/// Router Advertisement.
+pub mod router_advertisement;
+
+/// Router Solicitation.
+pub mod router_solicitation;
+
 /// Timestamp Request/Reply.
 pub mod timestamp;
 
+/// Traceroute.
+pub mod traceroute;
+
+#[cfg(test)]
+pub(crate) mod fixture;
+
 /// Calculate the checksum for an ICMP packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
     use byteorder::{BigEndian, ReadBytesExt};
@@ -160,6 +160,21 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
     kind!(/// Parse a Source Quench, Destination Unreachable or Time Exceeded packet.
 		fn previous[previous_mut]);
+
+    kind!(/// Parse a Router Advertisement packet.
+		fn router_advertisement[router_advertisement_mut]);
+
+    kind!(/// Parse a Router Solicitation packet.
+		fn router_solicitation[router_solicitation_mut]);
+
+    kind!(/// Parse an Address Mask Request/Reply packet.
+		fn address_mask[address_mask_mut]);
+
+    kind!(/// Parse an Extended Echo Request/Reply packet.
+		fn extended_echo[extended_echo_mut]);
+
+    kind!(/// Parse a Traceroute packet.
+		fn traceroute[traceroute_mut]);
 }
 
 /// Checked wrapper for ICMP packets.
@@ -0,0 +1,239 @@
+use std::net::IpAddr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::extension::{self, object, Role};
+use crate::icmp::parameter_problem::Packet;
+use crate::icmp::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Parameter Problem packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    quote: usize,
+    packet: bool,
+    extension: Option<usize>,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::ParameterProblem.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            quote: 8,
+            packet: false,
+            extension: None,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.packet {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Parameter Problem code.
+    pub fn code(mut self, value: code::ParameterProblem) -> Result<Self> {
+        self.buffer.data_mut()[1] = value.into();
+
+        Ok(self)
+    }
+
+    /// Pointer to the packet area that caused the problem.
+    pub fn pointer(mut self, value: u8) -> Result<Self> {
+        self.buffer.data_mut()[4] = value;
+
+        Ok(self)
+    }
+
+    /// Number of payload bytes of the packet to cause the message to quote, at
+    /// least 8.
+    ///
+    /// # Note
+    ///
+    /// It must be set before the packet.
+    pub fn quote(mut self, value: usize) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        if value < 8 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.quote = value;
+
+        Ok(self)
+    }
+
+    /// The packet that caused the problem, its header and the beginning of its payload are
+    /// quoted.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v4::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet, self.quote)?;
+
+        Ok(self)
+    }
+
+    /// Append an extension object, as defined in RFC 4884, the payload must
+    /// be a multiple of 4 octets.
+    ///
+    /// # Note
+    ///
+    /// Extensions must be added after the packet, which is padded to at least
+    /// 128 octets.
+    pub fn object(mut self, class: object::Class, kind: u8, data: &[u8]) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::object(&mut self.buffer, offset, class, kind, data)?;
+
+        Ok(self)
+    }
+
+    /// Append an MPLS label stack extension object, as defined in RFC 4950.
+    pub fn mpls<'a, T: IntoIterator<Item = &'a object::Label>>(mut self, value: T) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::mpls(&mut self.buffer, offset, value)?;
+
+        Ok(self)
+    }
+
+    /// Append an interface information extension object, as defined in RFC
+    /// 5837.
+    pub fn interface(mut self, role: Role, index: Option<u32>, address: Option<IpAddr>, name: Option<&str>, mtu: Option<u32>) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::interface(&mut self.buffer, offset, role, index, address, name, mtu)?;
+
+        Ok(self)
+    }
+
+    fn extension(&mut self) -> Result<usize> {
+        if !self.packet {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if let Some(offset) = self.extension {
+            return Ok(offset);
+        }
+
+        let offset = extension::builder::start(&mut self.buffer)?;
+        self.extension = Some(offset);
+
+        Ok(offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use crate::icmp::fixture::invoking;
+    use crate::icmp::extension::Role;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let invoking = invoking();
+
+        let packet = icmp::Builder::default()
+            .parameter_problem()
+            .unwrap()
+            .code(icmp::code::ParameterProblem::PointerIndicatesError)
+            .unwrap()
+            .pointer(8)
+            .unwrap()
+            .quote(16)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let icmp = icmp::Packet::new(packet).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::ParameterProblem);
+        assert_eq!(icmp::code::ParameterProblem::from(icmp.code()), icmp::code::ParameterProblem::PointerIndicatesError);
+
+        let problem = icmp.parameter_problem().unwrap();
+        assert_eq!(problem.pointer(), 8);
+
+        let quoted = problem.packet().unwrap();
+        assert_eq!(quoted.ttl(), invoking.ttl());
+        assert_eq!(quoted.payload(), &invoking.payload()[..16]);
+    }
+
+    #[test]
+    fn extension() {
+        let packet = icmp::Builder::default()
+            .parameter_problem()
+            .unwrap()
+            .pointer(1)
+            .unwrap()
+            .packet(&invoking())
+            .unwrap()
+            .interface(Role::Outgoing, None, Some("2001:db8::1".parse().unwrap()), None, None)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let icmp = icmp::Packet::new(packet).unwrap();
+        assert!(icmp.is_valid());
+
+        let problem = icmp.parameter_problem().unwrap();
+        assert_eq!(problem.pointer(), 1);
+        assert_eq!(problem.length(), 32);
+
+        let extension = problem.extension().unwrap();
+        assert!(extension.is_valid());
+
+        let object = extension.objects().next().unwrap().unwrap();
+        let interface = object.interface().unwrap();
+        assert_eq!(interface.role(), Role::Outgoing);
+        assert_eq!(interface.index(), None);
+        assert_eq!(interface.address(), Some("2001:db8::1".parse().unwrap()));
+    }
+}
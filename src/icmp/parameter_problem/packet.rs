@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::error::*;
+use crate::icmp::extension::Extension;
 use crate::icmp::Kind;
 use crate::ip;
 use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
@@ -21,13 +22,9 @@ sized!(Packet,
 	payload {
 		min:  <ip::v4::Packet<()> as size::header::Min>::min(),
 		max:  <ip::v4::Packet<()> as size::header::Max>::max(),
-		size: p => {
-			if let Ok(ip) = p.packet() {
-				size::header::Size::size(&ip)
-			}
-			else {
-				p.buffer.as_ref().len() - 8
-			}
+		size: p => match p.length() {
+			0 => p.buffer.as_ref().len() - 8,
+			n => (n as usize * 4).min(p.buffer.as_ref().len() - 8),
 		},
 	});
 
@@ -35,7 +32,9 @@ impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("icmp::parameter_problem::Packet")
             .field("pointer", &self.pointer())
+            .field("length", &self.length())
             .field("packet", &self.packet())
+            .field("extension", &self.extension().ok())
             .finish()
     }
 }
@@ -110,14 +109,20 @@ impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for
 
 impl<B: AsRef<[u8]>> P for Packet<B> {
     fn split(&self) -> (&[u8], &[u8]) {
-        let (header, payload) = self.buffer.as_ref().split_at(8);
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        let (header, payload) = self.buffer.as_ref()[..8 + payload].split_at(8);
         (&header[..5], payload)
     }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
     fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
-        let (header, payload) = self.buffer.as_mut().split_at_mut(8);
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        let (header, payload) = self.buffer.as_mut()[..8 + payload].split_at_mut(8);
         (&mut header[..5], payload)
     }
 }
@@ -130,6 +135,28 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
     /// The packet that caused the problem.
     pub fn packet(&self) -> Result<ip::v4::Packet<&[u8]>> {
-        ip::v4::Packet::new(&self.buffer.as_ref()[8..])
+        ip::v4::Packet::no_payload(self.payload())
+    }
+
+    /// Length of the quoted packet in 32-bit words, zero when the message
+    /// has no extensions.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[5]
+    }
+
+    /// Extension structure following the quoted packet, as defined in RFC
+    /// 4884.
+    pub fn extension(&self) -> Result<Extension<&[u8]>> {
+        if self.length() == 0 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let offset = 8 + self.length() as usize * 4;
+
+        if self.buffer.as_ref().len() < offset {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Extension::new(&self.buffer.as_ref()[offset..])
     }
 }
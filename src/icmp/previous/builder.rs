@@ -0,0 +1,352 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+use std::net::IpAddr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::extension::{self, object, Role};
+use crate::icmp::previous::Packet;
+use crate::icmp::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Source Quench, Destination Unreachable and Time Exceeded packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    kind: bool,
+    quote: usize,
+    packet: bool,
+    extension: Option<usize>,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            kind: false,
+            quote: 8,
+            packet: false,
+            extension: None,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.kind || !self.packet {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it a Destination Unreachable packet.
+    pub fn destination_unreachable(mut self, code: code::DestinationUnreachable) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::DestinationUnreachable.into();
+        self.buffer.data_mut()[1] = code.into();
+
+        Ok(self)
+    }
+
+    /// Make it a Time Exceeded packet.
+    pub fn time_exceeded(mut self, code: code::TimeExceeded) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::TimeExceeded.into();
+        self.buffer.data_mut()[1] = code.into();
+
+        Ok(self)
+    }
+
+    /// Make it a Source Quench packet.
+    pub fn source_quench(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::SourceQuench.into();
+        self.buffer.data_mut()[1] = 0;
+
+        Ok(self)
+    }
+
+    /// Next-hop MTU, for Fragmentation Required messages.
+    pub fn mtu(mut self, value: u16) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Number of payload bytes of the packet to cause the message to quote, at
+    /// least 8.
+    ///
+    /// # Note
+    ///
+    /// It must be set before the packet.
+    pub fn quote(mut self, value: usize) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        if value < 8 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.quote = value;
+
+        Ok(self)
+    }
+
+    /// Packet to cause the message, its header and the beginning of its
+    /// payload are quoted.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v4::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet, self.quote)?;
+
+        Ok(self)
+    }
+
+    /// Append an extension object, as defined in RFC 4884, the payload must
+    /// be a multiple of 4 octets.
+    ///
+    /// # Note
+    ///
+    /// Extensions must be added after the packet, which is padded to at least
+    /// 128 octets.
+    pub fn object(mut self, class: object::Class, kind: u8, data: &[u8]) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::object(&mut self.buffer, offset, class, kind, data)?;
+
+        Ok(self)
+    }
+
+    /// Append an MPLS label stack extension object, as defined in RFC 4950.
+    pub fn mpls<'a, T: IntoIterator<Item = &'a object::Label>>(mut self, value: T) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::mpls(&mut self.buffer, offset, value)?;
+
+        Ok(self)
+    }
+
+    /// Append an interface information extension object, as defined in RFC
+    /// 5837.
+    pub fn interface(mut self, role: Role, index: Option<u32>, address: Option<IpAddr>, name: Option<&str>, mtu: Option<u32>) -> Result<Self> {
+        let offset = self.extension()?;
+        extension::builder::interface(&mut self.buffer, offset, role, index, address, name, mtu)?;
+
+        Ok(self)
+    }
+
+    fn extension(&mut self) -> Result<usize> {
+        if !self.packet || self.buffer.data()[0] == Kind::SourceQuench.into() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if let Some(offset) = self.extension {
+            return Ok(offset);
+        }
+
+        let offset = extension::builder::start(&mut self.buffer)?;
+        self.extension = Some(offset);
+
+        Ok(offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use crate::icmp::fixture::invoking;
+    use crate::icmp::extension::object::{Class, Label};
+    use crate::icmp::extension::Role;
+    use crate::ip;
+    use crate::packet::Packet;
+    use crate::udp;
+
+    #[test]
+    fn destination_unreachable() {
+        let invoking = invoking();
+
+        let packet = ip::v4::Builder::default()
+            .source("10.0.0.2".parse().unwrap())
+            .unwrap()
+            .destination("10.0.0.1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .previous()
+            .unwrap()
+            .destination_unreachable(icmp::code::DestinationUnreachable::DestinationPortUnreachable)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.protocol(), ip::Protocol::Icmp);
+        assert_eq!(ip.length(), 20 + 8 + 20 + 8);
+
+        let icmp = icmp::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::DestinationUnreachable);
+        assert_eq!(icmp::code::DestinationUnreachable::from(icmp.code()), icmp::code::DestinationUnreachable::DestinationPortUnreachable);
+
+        let previous = icmp.previous().unwrap();
+        let quoted = previous.packet().unwrap();
+        assert_eq!(quoted.header(), invoking.header());
+        assert_eq!(quoted.source(), invoking.source());
+        assert_eq!(quoted.destination(), invoking.destination());
+
+        let udp = udp::Packet::no_payload(quoted.payload()).unwrap();
+        assert_eq!(udp.source(), 1337);
+        assert_eq!(udp.destination(), 9001);
+    }
+
+    #[test]
+    fn time_exceeded() {
+        let packet = icmp::Builder::default()
+            .previous()
+            .unwrap()
+            .time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit)
+            .unwrap()
+            .quote(12)
+            .unwrap()
+            .packet(&invoking())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let icmp = icmp::Packet::new(packet).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::TimeExceeded);
+        assert_eq!(icmp.code(), 0);
+        let previous = icmp.previous().unwrap();
+        let quoted = previous.packet().unwrap();
+        assert_eq!(quoted.payload().len(), 12);
+        assert_eq!(&quoted.payload()[8..], b"abcd");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(icmp::Builder::default().previous().unwrap().source_quench().unwrap().build().is_err());
+        assert!(icmp::Builder::default().previous().unwrap().quote(4).is_err());
+        assert!(icmp::Builder::default().previous().unwrap().packet(&invoking()).unwrap().quote(16).is_err());
+    }
+
+    #[test]
+    fn extension() {
+        let invoking = invoking();
+
+        let packet = icmp::Builder::default()
+            .previous()
+            .unwrap()
+            .time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .mpls(&[Label {
+                label: 16000,
+                traffic_class: 0,
+                bottom: true,
+                ttl: 1,
+            }])
+            .unwrap()
+            .interface(Role::Incoming, Some(3), Some("192.0.2.1".parse().unwrap()), Some("ge-0/0/1.0"), Some(1500))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let icmp = icmp::Packet::new(packet).unwrap();
+        assert!(icmp.is_valid());
+
+        let previous = icmp.previous().unwrap();
+        assert_eq!(previous.length(), 32);
+        assert_eq!(previous.payload().len(), 128);
+        assert_eq!(previous.packet().unwrap().destination(), invoking.destination());
+        assert_eq!(&previous.payload()[28..], &[0; 100][..]);
+
+        let extension = previous.extension().unwrap();
+        assert!(extension.is_valid());
+
+        let mut objects = extension.objects();
+        let labels = objects.next().unwrap().unwrap().mpls().unwrap().collect::<Vec<_>>();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].label, 16000);
+        assert!(labels[0].bottom);
+
+        let object = objects.next().unwrap().unwrap();
+        let interface = object.interface().unwrap();
+        assert_eq!(interface.index(), Some(3));
+        assert_eq!(interface.name(), Some("ge-0/0/1.0"));
+        assert_eq!(interface.mtu(), Some(1500));
+        assert!(objects.next().is_none());
+    }
+
+    #[test]
+    fn no_extension() {
+        let packet = icmp::Builder::default()
+            .previous()
+            .unwrap()
+            .destination_unreachable(icmp::code::DestinationUnreachable::DestinationHostUnreachable)
+            .unwrap()
+            .packet(&invoking())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let icmp = icmp::Packet::new(packet).unwrap();
+        let previous = icmp.previous().unwrap();
+        assert_eq!(previous.length(), 0);
+        assert!(previous.extension().is_err());
+
+        assert!(icmp::Builder::default().previous().unwrap().mpls(&[]).is_err());
+        assert!(icmp::Builder::default()
+            .previous()
+            .unwrap()
+            .source_quench()
+            .unwrap()
+            .packet(&invoking())
+            .unwrap()
+            .object(Class::Unknown(42), 1, &[])
+            .is_err());
+    }
+}
@@ -1,2 +1,5 @@
 mod packet;
 pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
@@ -0,0 +1,172 @@
+use std::net::Ipv4Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::redirect_message::Packet;
+use crate::icmp::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Redirect Message packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    quote: usize,
+    packet: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::RedirectMessage.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            quote: 8,
+            packet: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.packet {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Redirect code.
+    pub fn code(mut self, value: code::RedirectMessage) -> Result<Self> {
+        self.buffer.data_mut()[1] = value.into();
+
+        Ok(self)
+    }
+
+    /// Gateway to redirect to.
+    pub fn gateway(mut self, value: Ipv4Addr) -> Result<Self> {
+        self.buffer.data_mut()[4..8].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+
+    /// Number of payload bytes of the packet to cause the message to quote, at
+    /// least 8.
+    ///
+    /// # Note
+    ///
+    /// It must be set before the packet.
+    pub fn quote(mut self, value: usize) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        if value < 8 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.quote = value;
+
+        Ok(self)
+    }
+
+    /// Packet to redirect, its header and the beginning of its payload are
+    /// quoted.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v4::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet, self.quote)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use crate::icmp::fixture::invoking;
+    use crate::ip;
+    use crate::packet::Packet;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn simple() {
+        let invoking = invoking();
+
+        let packet = ip::v4::Builder::default()
+            .source("10.0.0.254".parse().unwrap())
+            .unwrap()
+            .destination("10.0.0.1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .redirect_message()
+            .unwrap()
+            .code(icmp::code::RedirectMessage::RedirectDatagramForHost)
+            .unwrap()
+            .gateway("10.0.0.253".parse().unwrap())
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 20 + 8 + 20 + 8);
+
+        let icmp = icmp::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::RedirectMessage);
+        assert_eq!(icmp::code::RedirectMessage::from(icmp.code()), icmp::code::RedirectMessage::RedirectDatagramForHost);
+
+        let redirect = icmp.redirect_message().unwrap();
+        assert_eq!(redirect.gateway(), "10.0.0.253".parse::<Ipv4Addr>().unwrap());
+
+        let quoted = redirect.packet().unwrap();
+        assert_eq!(quoted.destination(), invoking.destination());
+        assert_eq!(quoted.payload(), &invoking.payload()[..8]);
+    }
+
+    #[test]
+    fn no_packet() {
+        assert!(icmp::Builder::default().redirect_message().unwrap().gateway(Ipv4Addr::LOCALHOST).unwrap().build().is_err());
+    }
+}
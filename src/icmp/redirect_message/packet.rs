@@ -22,14 +22,7 @@ sized!(Packet,
 	payload {
 		min:  <ip::v4::Packet<()> as size::header::Min>::min(),
 		max:  <ip::v4::Packet<()> as size::header::Max>::max(),
-		size: p => {
-			if let Ok(ip) = p.packet() {
-				size::header::Size::size(&ip)
-			}
-			else {
-				p.buffer.as_ref().len() - 8
-			}
-		},
+		size: p => p.buffer.as_ref().len() - 8,
 	});
 
 impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
@@ -128,6 +121,6 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
     /// Packet to redirect.
     pub fn packet(&self) -> Result<ip::v4::Packet<&[u8]>> {
-        ip::v4::Packet::new(&self.buffer.as_ref()[8..])
+        ip::v4::Packet::no_payload(&self.buffer.as_ref()[8..])
     }
 }
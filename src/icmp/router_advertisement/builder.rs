@@ -0,0 +1,147 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::net::Ipv4Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::router_advertisement::Packet;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Router Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::RouterAdvertisement.into();
+        buffer.data_mut()[5] = 2;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Router Advertisement code.
+    pub fn code(mut self, value: code::RouterAdvertisement) -> Result<Self> {
+        self.buffer.data_mut()[1] = value.into();
+
+        Ok(self)
+    }
+
+    /// Number of seconds the addresses are valid for.
+    pub fn lifetime(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_lifetime(value)?;
+        Ok(self)
+    }
+
+    /// Router address and its preference level.
+    pub fn entry(mut self, address: Ipv4Addr, preference: i32) -> Result<Self> {
+        let count = self.buffer.data()[4];
+
+        if count == u8::max_value() {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let start = self.buffer.length();
+        self.buffer.more(8)?;
+
+        let mut out = &mut self.buffer.data_mut()[start..];
+        out.write_u32::<BigEndian>(address.into())?;
+        out.write_i32::<BigEndian>(preference)?;
+
+        self.buffer.data_mut()[4] = count + 1;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let packet = ip::v4::Builder::default()
+            .source("192.168.0.1".parse().unwrap())
+            .unwrap()
+            .destination("224.0.0.1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .router_advertisement()
+            .unwrap()
+            .lifetime(1800)
+            .unwrap()
+            .entry("192.168.0.1".parse().unwrap(), 0)
+            .unwrap()
+            .entry("192.168.0.2".parse().unwrap(), -1)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 20 + 8 + 16);
+
+        let icmp = icmp::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp::code::RouterAdvertisement::from(icmp.code()), icmp::code::RouterAdvertisement::NormalRouterAdvertisement);
+
+        let advertisement = icmp.router_advertisement().unwrap();
+        assert_eq!(advertisement.count(), 2);
+        assert_eq!(advertisement.entry_size(), 2);
+        assert_eq!(advertisement.lifetime(), 1800);
+        assert_eq!(
+            advertisement.entries().collect::<Vec<_>>(),
+            vec![("192.168.0.1".parse().unwrap(), 0), ("192.168.0.2".parse().unwrap(), -1)]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(icmp::router_advertisement::Packet::new(&[0x09u8, 0x00, 0x00, 0x00, 0x01, 0x02, 0x00, 0x00][..]).is_err());
+        assert!(icmp::router_advertisement::Packet::new(&[0x09u8, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00][..]).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::icmp::packet::Checked;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Router Advertisement packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  255 * 255 * 4,
+		size: p => p.count() as usize * p.entry_size() as usize * 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::router_advertisement::Packet")
+            .field("count", &self.count())
+            .field("entry_size", &self.entry_size())
+            .field("lifetime", &self.lifetime())
+            .field("entries", &self.entries().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Router Advertisement packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Router Advertisement packet, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+        use crate::size::payload::Size;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::RouterAdvertisement {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        // Each entry holds at least an address and its preference.
+        if packet.entry_size() < 2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if packet.buffer.as_ref().len() < 8 + packet.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_ref()[..8 + payload].split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_mut()[..8 + payload].split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Number of router addresses.
+    pub fn count(&self) -> u8 {
+        self.buffer.as_ref()[4]
+    }
+
+    /// Size of each address entry in 32-bit words.
+    pub fn entry_size(&self) -> u8 {
+        self.buffer.as_ref()[5]
+    }
+
+    /// Number of seconds the addresses are valid for.
+    pub fn lifetime(&self) -> u16 {
+        (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Router addresses and their preference level.
+    pub fn entries(&self) -> EntryIter {
+        EntryIter {
+            buffer: self.payload(),
+            size: self.entry_size() as usize * 4,
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Number of seconds the addresses are valid for.
+    pub fn set_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Create a checksumed setter.
+    pub fn checked(&mut self) -> Checked<Self> {
+        Checked { packet: self }
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]> + 'a> Checked<'a, Packet<B>> {
+    /// Number of seconds the addresses are valid for.
+    pub fn set_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_lifetime(value)?;
+        Ok(self)
+    }
+}
+
+/// Iterator over Router Advertisement entries.
+pub struct EntryIter<'a> {
+    buffer: &'a [u8],
+    size: usize,
+}
+
+impl<'a> Iterator for EntryIter<'a> {
+    type Item = (Ipv4Addr, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size < 8 || self.buffer.len() < self.size {
+            return None;
+        }
+
+        let address = Ipv4Addr::from((&self.buffer[0..]).read_u32::<BigEndian>().unwrap());
+        let preference = (&self.buffer[4..]).read_i32::<BigEndian>().unwrap();
+        self.buffer = &self.buffer[self.size..];
+
+        Some((address, preference))
+    }
+}
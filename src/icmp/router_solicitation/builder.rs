@@ -0,0 +1,73 @@
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::router_solicitation::Packet;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Router Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::RouterSolicitation.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+
+    #[test]
+    fn simple() {
+        let packet = icmp::Builder::default().router_solicitation().unwrap().build().unwrap();
+        assert_eq!(packet, [0x0a, 0x00, 0xf5, 0xff, 0x00, 0x00, 0x00, 0x00]);
+
+        let icmp = icmp::Packet::new(&packet[..]).unwrap();
+        assert!(icmp.is_valid());
+        assert!(icmp.router_solicitation().is_ok());
+        assert!(icmp.router_advertisement().is_err());
+    }
+}
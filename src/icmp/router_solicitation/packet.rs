@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::error::*;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Router Solicitation packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::router_solicitation::Packet").finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Router Solicitation packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Router Solicitation packet, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::RouterSolicitation {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_ref()[..8 + payload].split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::payload::Size;
+
+        let payload = self.size();
+        self.buffer.as_mut()[..8 + payload].split_at_mut(8)
+    }
+}
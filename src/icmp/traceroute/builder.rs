@@ -0,0 +1,142 @@
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmp::builder;
+use crate::icmp::code;
+use crate::icmp::traceroute::Packet;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Traceroute packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        buffer.next(20)?;
+        buffer.data_mut()[0] = Kind::TraceRoute.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Traceroute code.
+    pub fn code(mut self, value: code::TraceRoute) -> Result<Self> {
+        self.buffer.data_mut()[1] = value.into();
+
+        Ok(self)
+    }
+
+    /// Identifier copied from the IP Traceroute option.
+    pub fn identifier(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_identifier(value)?;
+        Ok(self)
+    }
+
+    /// Outbound Hop Count copied from the IP Traceroute option.
+    pub fn outbound_hop_count(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_outbound_hop_count(value)?;
+        Ok(self)
+    }
+
+    /// Return Hop Count copied from the IP Traceroute option.
+    pub fn return_hop_count(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_return_hop_count(value)?;
+        Ok(self)
+    }
+
+    /// Speed of the link the packet is sent on, in bytes per second.
+    pub fn output_link_speed(mut self, value: u32) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_output_link_speed(value)?;
+        Ok(self)
+    }
+
+    /// MTU of the link the packet is sent on, in bytes.
+    pub fn output_link_mtu(mut self, value: u32) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_output_link_mtu(value)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmp;
+
+    #[test]
+    fn simple() {
+        let mut packet = icmp::Builder::default()
+            .traceroute()
+            .unwrap()
+            .code(icmp::code::TraceRoute::NoRoute)
+            .unwrap()
+            .identifier(42)
+            .unwrap()
+            .outbound_hop_count(3)
+            .unwrap()
+            .return_hop_count(0xffff)
+            .unwrap()
+            .output_link_speed(125_000_000)
+            .unwrap()
+            .output_link_mtu(1500)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 20);
+
+        let icmp = icmp::Packet::new(&packet[..]).unwrap();
+        assert!(icmp.is_valid());
+        assert_eq!(icmp.kind(), icmp::Kind::TraceRoute);
+        assert_eq!(icmp::code::TraceRoute::from(icmp.code()), icmp::code::TraceRoute::NoRoute);
+
+        let traceroute = icmp.traceroute().unwrap();
+        assert_eq!(traceroute.identifier(), 42);
+        assert_eq!(traceroute.outbound_hop_count(), 3);
+        assert_eq!(traceroute.return_hop_count(), 0xffff);
+        assert_eq!(traceroute.output_link_speed(), 125_000_000);
+        assert_eq!(traceroute.output_link_mtu(), 1500);
+
+        let mut icmp = icmp::Packet::new(&mut packet[..]).unwrap();
+        icmp.traceroute_mut().unwrap().checked().set_outbound_hop_count(4).unwrap();
+        assert_eq!(icmp.traceroute().unwrap().outbound_hop_count(), 4);
+        assert!(icmp.is_valid());
+    }
+}
@@ -0,0 +1,218 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmp::packet::Checked;
+use crate::icmp::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Traceroute packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  20,
+		max:  20,
+		size: 20,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmp::traceroute::Packet")
+            .field("identifier", &self.identifier())
+            .field("outbound_hop_count", &self.outbound_hop_count())
+            .field("return_hop_count", &self.return_hop_count())
+            .field("output_link_speed", &self.output_link_speed())
+            .field("output_link_mtu", &self.output_link_mtu())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Traceroute packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Traceroute packet, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        match Kind::from(packet.buffer.as_ref()[0]) {
+            Kind::TraceRoute => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(20)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(20)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Identifier copied from the IP Traceroute option.
+    pub fn identifier(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Outbound Hop Count copied from the IP Traceroute option.
+    pub fn outbound_hop_count(&self) -> u16 {
+        (&self.buffer.as_ref()[8..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Return Hop Count copied from the IP Traceroute option.
+    pub fn return_hop_count(&self) -> u16 {
+        (&self.buffer.as_ref()[10..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Speed of the link the packet is sent on, in bytes per second.
+    pub fn output_link_speed(&self) -> u32 {
+        (&self.buffer.as_ref()[12..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// MTU of the link the packet is sent on, in bytes.
+    pub fn output_link_mtu(&self) -> u32 {
+        (&self.buffer.as_ref()[16..]).read_u32::<BigEndian>().unwrap()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Identifier copied from the IP Traceroute option.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Outbound Hop Count copied from the IP Traceroute option.
+    pub fn set_outbound_hop_count(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[8..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Return Hop Count copied from the IP Traceroute option.
+    pub fn set_return_hop_count(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[10..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Speed of the link the packet is sent on, in bytes per second.
+    pub fn set_output_link_speed(&mut self, value: u32) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[12..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// MTU of the link the packet is sent on, in bytes.
+    pub fn set_output_link_mtu(&mut self, value: u32) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[16..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Create a checksumed setter.
+    pub fn checked(&mut self) -> Checked<Self> {
+        Checked { packet: self }
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]> + 'a> Checked<'a, Packet<B>> {
+    /// Identifier copied from the IP Traceroute option.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_identifier(value)?;
+        Ok(self)
+    }
+
+    /// Outbound Hop Count copied from the IP Traceroute option.
+    pub fn set_outbound_hop_count(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_outbound_hop_count(value)?;
+        Ok(self)
+    }
+
+    /// Return Hop Count copied from the IP Traceroute option.
+    pub fn set_return_hop_count(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_return_hop_count(value)?;
+        Ok(self)
+    }
+
+    /// Speed of the link the packet is sent on, in bytes per second.
+    pub fn set_output_link_speed(&mut self, value: u32) -> Result<&mut Self> {
+        self.packet.set_output_link_speed(value)?;
+        Ok(self)
+    }
+
+    /// MTU of the link the packet is sent on, in bytes.
+    pub fn set_output_link_mtu(&mut self, value: u32) -> Result<&mut Self> {
+        self.packet.set_output_link_mtu(value)?;
+        Ok(self)
+    }
+}
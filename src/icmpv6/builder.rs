@@ -0,0 +1,220 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::checksum;
+use crate::icmpv6::mld;
+use crate::icmpv6::{echo, packet_too_big, parameter_problem, previous};
+use crate::icmpv6::{neighbor_advertisement, neighbor_solicitation, redirect, router_advertisement, router_solicitation};
+use crate::ip;
+use crate::packet::Packet as P;
+
+/// Maximum amount of the invoking packet quoted in an error message, so the
+/// whole message fits in the minimum IPv6 MTU.
+const QUOTE: usize = 1280 - 40 - 8;
+
+/// ICMPv6 packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(buffer: B) -> Result<Self> {
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(self) -> Result<B::Inner> {
+        Err(ErrorKind::InvalidPacket.into())
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Build an Echo Request/Reply packet.
+    pub fn echo(self) -> Result<echo::Builder<B>> {
+        let mut echo = echo::Builder::with(self.buffer)?;
+        echo.finalizer().extend(self.finalizer);
+
+        Ok(echo)
+    }
+
+    /// Build a Destination Unreachable or Time Exceeded packet.
+    pub fn previous(self) -> Result<previous::Builder<B>> {
+        let mut previous = previous::Builder::with(self.buffer)?;
+        previous.finalizer().extend(self.finalizer);
+
+        Ok(previous)
+    }
+
+    /// Build a Packet Too Big packet.
+    pub fn packet_too_big(self) -> Result<packet_too_big::Builder<B>> {
+        let mut packet_too_big = packet_too_big::Builder::with(self.buffer)?;
+        packet_too_big.finalizer().extend(self.finalizer);
+
+        Ok(packet_too_big)
+    }
+
+    /// Build a Parameter Problem packet.
+    pub fn parameter_problem(self) -> Result<parameter_problem::Builder<B>> {
+        let mut parameter_problem = parameter_problem::Builder::with(self.buffer)?;
+        parameter_problem.finalizer().extend(self.finalizer);
+
+        Ok(parameter_problem)
+    }
+
+    /// Build a Router Solicitation packet.
+    pub fn router_solicitation(self) -> Result<router_solicitation::Builder<B>> {
+        let mut router_solicitation = router_solicitation::Builder::with(self.buffer)?;
+        router_solicitation.finalizer().extend(self.finalizer);
+
+        Ok(router_solicitation)
+    }
+
+    /// Build a Router Advertisement packet.
+    pub fn router_advertisement(self) -> Result<router_advertisement::Builder<B>> {
+        let mut router_advertisement = router_advertisement::Builder::with(self.buffer)?;
+        router_advertisement.finalizer().extend(self.finalizer);
+
+        Ok(router_advertisement)
+    }
+
+    /// Build a Neighbor Solicitation packet.
+    pub fn neighbor_solicitation(self) -> Result<neighbor_solicitation::Builder<B>> {
+        let mut neighbor_solicitation = neighbor_solicitation::Builder::with(self.buffer)?;
+        neighbor_solicitation.finalizer().extend(self.finalizer);
+
+        Ok(neighbor_solicitation)
+    }
+
+    /// Build a Neighbor Advertisement packet.
+    pub fn neighbor_advertisement(self) -> Result<neighbor_advertisement::Builder<B>> {
+        let mut neighbor_advertisement = neighbor_advertisement::Builder::with(self.buffer)?;
+        neighbor_advertisement.finalizer().extend(self.finalizer);
+
+        Ok(neighbor_advertisement)
+    }
+
+    /// Build a Redirect packet.
+    pub fn redirect(self) -> Result<redirect::Builder<B>> {
+        let mut redirect = redirect::Builder::with(self.buffer)?;
+        redirect.finalizer().extend(self.finalizer);
+
+        Ok(redirect)
+    }
+
+    /// Build an MLDv1 or MLDv2 Multicast Listener Query packet.
+    pub fn mld_query(self) -> Result<mld::query::Builder<B>> {
+        let mut query = mld::query::Builder::with(self.buffer)?;
+        query.finalizer().extend(self.finalizer);
+
+        Ok(query)
+    }
+
+    /// Build an MLDv1 Multicast Listener Report or Done packet.
+    pub fn mld_listener(self) -> Result<mld::listener::Builder<B>> {
+        let mut listener = mld::listener::Builder::with(self.buffer)?;
+        listener.finalizer().extend(self.finalizer);
+
+        Ok(listener)
+    }
+
+    /// Build an MLDv2 Multicast Listener Report packet.
+    pub fn mld_report(self) -> Result<mld::report::Builder<B>> {
+        let mut report = mld::report::Builder::with(self.buffer)?;
+        report.finalizer().extend(self.finalizer);
+
+        Ok(report)
+    }
+}
+
+pub(in crate::icmpv6) fn prepare<B: Buffer>(finalizer: &mut Finalization, ip: (usize, usize), buffer: &B) {
+    let length = buffer.length();
+
+    finalizer.add(move |out| {
+        let (before, after) = out.split_at_mut(ip.0 + ip.1);
+        let ip = ip::v6::Packet::no_payload(&before[ip.0..])?;
+        let icmp = &mut after[..length];
+
+        let checksum = checksum(&ip, icmp);
+        Cursor::new(&mut icmp[2..]).write_u16::<BigEndian>(checksum)?;
+
+        Ok(())
+    });
+}
+
+pub(in crate::icmpv6) fn quote<B: Buffer, I: AsRef<[u8]>>(buffer: &mut B, packet: &ip::v6::Packet<I>) -> Result<()> {
+    let (header, payload) = packet.split();
+    let payload = &payload[..payload.len().min(QUOTE - header.len())];
+
+    for byte in header.iter().chain(payload) {
+        buffer.more(1)?;
+        *buffer.data_mut().last_mut().unwrap() = *byte;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn standalone() {
+        assert!(icmpv6::Builder::default().echo().unwrap().request().unwrap().build().is_err());
+    }
+
+    #[test]
+    fn simple() {
+        let packet = ip::v6::Builder::default()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("fe80::2".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .echo()
+            .unwrap()
+            .request()
+            .unwrap()
+            .identifier(42)
+            .unwrap()
+            .sequence(2)
+            .unwrap()
+            .payload(b"test")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(ip.next_header(), ip::Protocol::Ipv6Icmp);
+        assert_eq!(ip.payload_length(), 12);
+
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert_eq!(icmp.kind(), icmpv6::Kind::EchoRequest);
+        assert!(icmp.is_valid(&ip));
+
+        let echo = icmp.echo().unwrap();
+        assert_eq!(echo.identifier(), 42);
+        assert_eq!(echo.sequence(), 2);
+        assert_eq!(echo.payload(), b"test");
+    }
+}
@@ -0,0 +1,150 @@
+/// Codes for Destination Unreachable packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum DestinationUnreachable {
+    ///
+    NoRoute,
+
+    ///
+    AdministrativelyProhibited,
+
+    ///
+    BeyondScope,
+
+    ///
+    AddressUnreachable,
+
+    ///
+    PortUnreachable,
+
+    ///
+    SourceAddressFailedPolicy,
+
+    ///
+    RejectRoute,
+
+    ///
+    SourceRoutingHeaderError,
+
+    ///
+    Unknown(u8),
+}
+
+/// Codes for Time Exceeded packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimeExceeded {
+    ///
+    HopLimitExceeded,
+
+    ///
+    FragmentReassemblyTimeExceeded,
+
+    ///
+    Unknown(u8),
+}
+
+/// Codes for Parameter Problem packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ParameterProblem {
+    ///
+    ErroneousHeaderField,
+
+    ///
+    UnrecognizedNextHeader,
+
+    ///
+    UnrecognizedOption,
+
+    ///
+    IncompleteHeaderChain,
+
+    ///
+    Unknown(u8),
+}
+
+impl From<u8> for DestinationUnreachable {
+    fn from(value: u8) -> Self {
+        use self::DestinationUnreachable::*;
+
+        match value {
+            0 => NoRoute,
+            1 => AdministrativelyProhibited,
+            2 => BeyondScope,
+            3 => AddressUnreachable,
+            4 => PortUnreachable,
+            5 => SourceAddressFailedPolicy,
+            6 => RejectRoute,
+            7 => SourceRoutingHeaderError,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for DestinationUnreachable {
+    fn into(self) -> u8 {
+        use self::DestinationUnreachable::*;
+
+        match self {
+            NoRoute => 0,
+            AdministrativelyProhibited => 1,
+            BeyondScope => 2,
+            AddressUnreachable => 3,
+            PortUnreachable => 4,
+            SourceAddressFailedPolicy => 5,
+            RejectRoute => 6,
+            SourceRoutingHeaderError => 7,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for TimeExceeded {
+    fn from(value: u8) -> Self {
+        use self::TimeExceeded::*;
+
+        match value {
+            0 => HopLimitExceeded,
+            1 => FragmentReassemblyTimeExceeded,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for TimeExceeded {
+    fn into(self) -> u8 {
+        use self::TimeExceeded::*;
+
+        match self {
+            HopLimitExceeded => 0,
+            FragmentReassemblyTimeExceeded => 1,
+            Unknown(v) => v,
+        }
+    }
+}
+
+impl From<u8> for ParameterProblem {
+    fn from(value: u8) -> Self {
+        use self::ParameterProblem::*;
+
+        match value {
+            0 => ErroneousHeaderField,
+            1 => UnrecognizedNextHeader,
+            2 => UnrecognizedOption,
+            3 => IncompleteHeaderChain,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for ParameterProblem {
+    fn into(self) -> u8 {
+        use self::ParameterProblem::*;
+
+        match self {
+            ErroneousHeaderField => 0,
+            UnrecognizedNextHeader => 1,
+            UnrecognizedOption => 2,
+            IncompleteHeaderChain => 3,
+            Unknown(v) => v,
+        }
+    }
+}
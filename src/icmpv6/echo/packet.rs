@@ -0,0 +1,201 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmpv6::packet::Checked;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Echo Request/Reply packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::echo::Packet")
+            .field("request", &self.is_request())
+            .field("identifier", &self.identifier())
+            .field("sequence", &self.sequence())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create an Echo Request/Reply packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse an Echo Request/Reply packet, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        match Kind::from(packet.buffer.as_ref()[0]) {
+            Kind::EchoRequest | Kind::EchoReply => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Check if it's a Request packet.
+    pub fn is_request(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::EchoRequest
+    }
+
+    /// Check if it's a Reply packet.
+    pub fn is_reply(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::EchoReply
+    }
+
+    /// Packet identifier.
+    pub fn identifier(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Packet sequence.
+    pub fn sequence(&self) -> u16 {
+        (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Make the packet an Echo Request.
+    pub fn make_request(&mut self) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = Kind::EchoRequest.into();
+
+        Ok(self)
+    }
+
+    /// Make the packet an Echo Reply.
+    pub fn make_reply(&mut self) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = Kind::EchoReply.into();
+
+        Ok(self)
+    }
+
+    /// Packet identifier.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn set_sequence(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Create a checksumed setter, the enclosing IPv6 packet is needed for the
+    /// pseudo-header.
+    pub fn checked<'a, 'b, I: AsRef<[u8]>>(&'a mut self, ip: &'b ip::v6::Packet<I>) -> Checked<'a, 'b, Self, I> {
+        Checked { packet: self, ip }
+    }
+}
+
+impl<'a, 'b, B: AsRef<[u8]> + AsMut<[u8]> + 'a, I: AsRef<[u8]> + 'b> Checked<'a, 'b, Packet<B>, I> {
+    /// Make the packet an Echo Request.
+    pub fn make_request(&mut self) -> Result<&mut Self> {
+        self.packet.make_request()?;
+        Ok(self)
+    }
+
+    /// Make the packet an Echo Reply.
+    pub fn make_reply(&mut self) -> Result<&mut Self> {
+        self.packet.make_reply()?;
+        Ok(self)
+    }
+
+    /// Packet identifier.
+    pub fn set_identifier(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_identifier(value)?;
+        Ok(self)
+    }
+
+    /// Packet sequence.
+    pub fn set_sequence(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_sequence(value)?;
+        Ok(self)
+    }
+}
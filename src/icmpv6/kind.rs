@@ -0,0 +1,106 @@
+/// ICMPv6 packet types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+    ///
+    DestinationUnreachable,
+
+    ///
+    PacketTooBig,
+
+    ///
+    TimeExceeded,
+
+    ///
+    ParameterProblem,
+
+    ///
+    EchoRequest,
+
+    ///
+    EchoReply,
+
+    ///
+    MulticastListenerQuery,
+
+    ///
+    MulticastListenerReport,
+
+    ///
+    MulticastListenerDone,
+
+    ///
+    RouterSolicitation,
+
+    ///
+    RouterAdvertisement,
+
+    ///
+    NeighborSolicitation,
+
+    ///
+    NeighborAdvertisement,
+
+    ///
+    Redirect,
+
+    ///
+    RouterRenumbering,
+
+    ///
+    MulticastListenerReportV2,
+
+    ///
+    Unknown(u8),
+}
+
+impl From<u8> for Kind {
+    fn from(value: u8) -> Kind {
+        use self::Kind::*;
+
+        match value {
+            1 => DestinationUnreachable,
+            2 => PacketTooBig,
+            3 => TimeExceeded,
+            4 => ParameterProblem,
+            128 => EchoRequest,
+            129 => EchoReply,
+            130 => MulticastListenerQuery,
+            131 => MulticastListenerReport,
+            132 => MulticastListenerDone,
+            133 => RouterSolicitation,
+            134 => RouterAdvertisement,
+            135 => NeighborSolicitation,
+            136 => NeighborAdvertisement,
+            137 => Redirect,
+            138 => RouterRenumbering,
+            143 => MulticastListenerReportV2,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for Kind {
+    fn into(self) -> u8 {
+        use self::Kind::*;
+
+        match self {
+            DestinationUnreachable => 1,
+            PacketTooBig => 2,
+            TimeExceeded => 3,
+            ParameterProblem => 4,
+            EchoRequest => 128,
+            EchoReply => 129,
+            MulticastListenerQuery => 130,
+            MulticastListenerReport => 131,
+            MulticastListenerDone => 132,
+            RouterSolicitation => 133,
+            RouterAdvertisement => 134,
+            NeighborSolicitation => 135,
+            NeighborAdvertisement => 136,
+            Redirect => 137,
+            RouterRenumbering => 138,
+            MulticastListenerReportV2 => 143,
+            Unknown(v) => v,
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::mld::listener::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// MLDv1 Multicast Listener Report and Done packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    kind: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(24)?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            kind: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.kind {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it a Report.
+    pub fn report(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::MulticastListenerReport.into();
+
+        Ok(self)
+    }
+
+    /// Make it a Done.
+    pub fn done(mut self) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::MulticastListenerDone.into();
+
+        Ok(self)
+    }
+
+    /// Multicast address being reported or left.
+    pub fn multicast_address(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_multicast_address(value)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::ip;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn done() {
+        let group = "ff05::1:3".parse::<Ipv6Addr>().unwrap();
+
+        let packet = ip::v6::Builder::default()
+            .hop_limit(1)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::2".parse().unwrap())
+            .unwrap()
+            .hop_by_hop()
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .mld_listener()
+            .unwrap()
+            .done()
+            .unwrap()
+            .multicast_address(group)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let (_, payload) = ip.upper_layer().unwrap();
+        let icmp = icmpv6::Packet::new(payload).unwrap();
+        assert_eq!(icmp.kind(), icmpv6::Kind::MulticastListenerDone);
+        assert!(icmp.is_valid(&ip));
+
+        let listener = icmp.mld_listener().unwrap();
+        assert!(listener.is_done());
+        assert_eq!(listener.multicast_address(), group);
+    }
+
+    #[test]
+    fn no_kind() {
+        assert!(ip::v6::Builder::default().icmp().unwrap().mld_listener().unwrap().build().is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// MLDv1 Multicast Listener Report and Done packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  24,
+		max:  24,
+		size: 24,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 24,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::mld::listener::Packet")
+            .field("report", &self.is_report())
+            .field("maximum_response_delay", &self.maximum_response_delay())
+            .field("multicast_address", &self.multicast_address())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Multicast Listener Report and Done packet without checking the
+    /// buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Multicast Listener Report and Done packet, checking the buffer
+    /// contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        match Kind::from(packet.buffer.as_ref()[0]) {
+            Kind::MulticastListenerReport | Kind::MulticastListenerDone => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(24)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(24)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Check if it's a Report packet.
+    pub fn is_report(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::MulticastListenerReport
+    }
+
+    /// Check if it's a Done packet.
+    pub fn is_done(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::MulticastListenerDone
+    }
+
+    /// Maximum response delay, unused outside of queries.
+    pub fn maximum_response_delay(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Multicast address being reported or left.
+    pub fn multicast_address(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[8..24]);
+
+        Ipv6Addr::from(octets)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Make the packet a Report.
+    pub fn make_report(&mut self) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = Kind::MulticastListenerReport.into();
+
+        Ok(self)
+    }
+
+    /// Make the packet a Done.
+    pub fn make_done(&mut self) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = Kind::MulticastListenerDone.into();
+
+        Ok(self)
+    }
+
+    /// Maximum response delay, unused outside of queries.
+    pub fn set_maximum_response_delay(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Multicast address being reported or left.
+    pub fn set_multicast_address(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+        self.buffer.as_mut()[8..24].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+}
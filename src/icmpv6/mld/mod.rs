@@ -0,0 +1,35 @@
+/// MLDv1 and MLDv2 Multicast Listener Query.
+pub mod query;
+
+/// MLDv1 Multicast Listener Report and Done.
+pub mod listener;
+
+/// MLDv2 Multicast Listener Report.
+pub mod report;
+
+/// MLDv2 multicast address record parser.
+pub mod record;
+pub use self::record::Record;
+
+use std::net::Ipv6Addr;
+
+/// Iterator over a list of source addresses.
+pub struct SourceIter<'a> {
+    pub(in crate::icmpv6) buffer: &'a [u8],
+}
+
+impl<'a> Iterator for SourceIter<'a> {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < 16 {
+            return None;
+        }
+
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer[..16]);
+        self.buffer = &self.buffer[16..];
+
+        Some(Ipv6Addr::from(octets))
+    }
+}
@@ -0,0 +1,241 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::mld::query::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Multicast Listener Query packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    v2: bool,
+    sources: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(24)?;
+        buffer.data_mut()[0] = Kind::MulticastListenerQuery.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            v2: false,
+            sources: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it an MLDv2 query, implied by any of the MLDv2 only fields.
+    pub fn v2(mut self) -> Result<Self> {
+        if !self.v2 {
+            self.v2 = true;
+            self.buffer.more(4)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Maximum Response Code.
+    pub fn maximum_response_code(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_maximum_response_code(value)?;
+        Ok(self)
+    }
+
+    /// Multicast address being queried, leave unset for a general query.
+    pub fn multicast_address(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_multicast_address(value)?;
+        Ok(self)
+    }
+
+    /// Whether routers should suppress timer updates on receipt.
+    pub fn suppress(self, value: bool) -> Result<Self> {
+        let mut builder = self.v2()?;
+        Packet::unchecked(builder.buffer.data_mut()).set_suppress(value)?;
+        Ok(builder)
+    }
+
+    /// Querier's Robustness Variable.
+    pub fn robustness(self, value: u8) -> Result<Self> {
+        let mut builder = self.v2()?;
+        Packet::unchecked(builder.buffer.data_mut()).set_robustness(value)?;
+        Ok(builder)
+    }
+
+    /// Querier's Query Interval Code.
+    pub fn querier_interval_code(self, value: u8) -> Result<Self> {
+        let mut builder = self.v2()?;
+        Packet::unchecked(builder.buffer.data_mut()).set_querier_interval_code(value)?;
+        Ok(builder)
+    }
+
+    /// Source addresses being queried.
+    pub fn sources<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(self, value: T) -> Result<Self> {
+        let mut builder = self.v2()?;
+
+        if builder.sources {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        builder.sources = true;
+
+        let mut count = 0usize;
+
+        for address in value {
+            let offset = builder.buffer.length();
+            builder.buffer.more(16)?;
+            builder.buffer.data_mut()[offset..].copy_from_slice(&address.octets());
+
+            count += 1;
+        }
+
+        if count > usize::from(u16::MAX) {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        Cursor::new(&mut builder.buffer.data_mut()[26..]).write_u16::<BigEndian>(count as u16)?;
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::ip;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn v1() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(1)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::1".parse().unwrap())
+            .unwrap()
+            .hop_by_hop()
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .mld_query()
+            .unwrap()
+            .maximum_response_code(10000)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        assert_eq!(ip.next_header(), ip::Protocol::Hopopt);
+
+        let (protocol, payload) = ip.upper_layer().unwrap();
+        assert_eq!(protocol, ip::Protocol::Ipv6Icmp);
+        assert_eq!(payload.len(), 24);
+
+        let icmp = icmpv6::Packet::new(payload).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let query = icmp.mld_query().unwrap();
+        assert!(!query.is_v2());
+        assert_eq!(query.maximum_response_delay(), 10000);
+        assert_eq!(query.multicast_address(), Ipv6Addr::UNSPECIFIED);
+        assert_eq!(query.sources().count(), 0);
+    }
+
+    #[test]
+    fn v2() {
+        let sources: [Ipv6Addr; 2] = ["2001:db8::1".parse().unwrap(), "2001:db8::2".parse().unwrap()];
+
+        let packet = ip::v6::Builder::default()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::1".parse().unwrap())
+            .unwrap()
+            .hop_by_hop()
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .mld_query()
+            .unwrap()
+            .maximum_response_code(0x8123)
+            .unwrap()
+            .multicast_address("ff3e::1234".parse().unwrap())
+            .unwrap()
+            .suppress(true)
+            .unwrap()
+            .robustness(2)
+            .unwrap()
+            .querier_interval_code(0x83)
+            .unwrap()
+            .sources(&sources)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let (_, payload) = ip.upper_layer().unwrap();
+        assert_eq!(payload.len(), 28 + 32);
+
+        let icmp = icmpv6::Packet::new(payload).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let query = icmp.mld_query().unwrap();
+        assert!(query.is_v2());
+        assert_eq!(query.maximum_response_code(), 0x8123);
+        assert_eq!(query.maximum_response_delay(), 0x1123 << 3);
+        assert_eq!(query.multicast_address(), "ff3e::1234".parse::<Ipv6Addr>().unwrap());
+        assert!(query.suppress());
+        assert_eq!(query.robustness(), 2);
+        assert_eq!(query.querier_interval(), 0x13 << 3);
+        assert_eq!(query.number_of_sources(), 2);
+        assert_eq!(query.sources().collect::<Vec<_>>(), sources);
+    }
+}
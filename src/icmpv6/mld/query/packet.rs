@@ -0,0 +1,287 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::icmpv6::mld::SourceIter;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Multicast Listener Query packet parser.
+///
+/// # Note
+///
+/// MLDv1 and MLDv2 queries share the same type and are told apart by length,
+/// the MLDv2 only fields read as zero on MLDv1 queries.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  24,
+		max:  28,
+		size: p => if p.is_v2() { 28 } else { 24 },
+	}
+
+	payload {
+		min:  0,
+		max:  16 * 0xffff,
+		size: p => p.number_of_sources() as usize * 16,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::mld::query::Packet")
+            .field("is_v2", &self.is_v2())
+            .field("maximum_response_delay", &self.maximum_response_delay())
+            .field("multicast_address", &self.multicast_address())
+            .field("suppress", &self.suppress())
+            .field("robustness", &self.robustness())
+            .field("querier_interval", &self.querier_interval())
+            .field("sources", &self.sources().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Multicast Listener Query packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Multicast Listener Query packet, checking the buffer contents
+    /// are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::MulticastListenerQuery {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if packet.buffer.as_ref().len() < packet.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::payload::Size;
+
+        let header = if self.is_v2() { 28 } else { 24 };
+        let payload = self.size();
+
+        self.buffer.as_ref()[..header + payload].split_at(header)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::payload::Size;
+
+        let header = if self.is_v2() { 28 } else { 24 };
+        let payload = self.size();
+
+        self.buffer.as_mut()[..header + payload].split_at_mut(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Check if it's an MLDv2 query.
+    pub fn is_v2(&self) -> bool {
+        self.buffer.as_ref().len() >= 28
+    }
+
+    /// Maximum Response Code, as found in the packet.
+    pub fn maximum_response_code(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Maximum delay before sending a response, in milliseconds.
+    pub fn maximum_response_delay(&self) -> u32 {
+        let code = self.maximum_response_code();
+
+        // MLDv2 encodes large delays as a floating point value.
+        if !self.is_v2() || code < 0x8000 {
+            return u32::from(code);
+        }
+
+        let mantissa = u32::from(code & 0x0fff);
+        let exponent = u32::from((code >> 12) & 0b111);
+
+        (mantissa | 0x1000) << (exponent + 3)
+    }
+
+    /// Multicast address being queried, unspecified for a general query.
+    pub fn multicast_address(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[8..24]);
+
+        Ipv6Addr::from(octets)
+    }
+
+    /// Whether routers should suppress timer updates on receipt.
+    pub fn suppress(&self) -> bool {
+        self.is_v2() && self.buffer.as_ref()[24] & 0b1000 != 0
+    }
+
+    /// Querier's Robustness Variable.
+    pub fn robustness(&self) -> u8 {
+        if !self.is_v2() {
+            return 0;
+        }
+
+        self.buffer.as_ref()[24] & 0b111
+    }
+
+    /// Querier's Query Interval Code, as found in the packet.
+    pub fn querier_interval_code(&self) -> u8 {
+        if !self.is_v2() {
+            return 0;
+        }
+
+        self.buffer.as_ref()[25]
+    }
+
+    /// Querier's Query Interval, in seconds.
+    pub fn querier_interval(&self) -> u32 {
+        let code = self.querier_interval_code();
+
+        // Large intervals are encoded as a floating point value.
+        if code < 0x80 {
+            return u32::from(code);
+        }
+
+        let mantissa = u32::from(code & 0x0f);
+        let exponent = u32::from((code >> 4) & 0b111);
+
+        (mantissa | 0x10) << (exponent + 3)
+    }
+
+    /// Number of source addresses.
+    pub fn number_of_sources(&self) -> u16 {
+        if !self.is_v2() {
+            return 0;
+        }
+
+        (&self.buffer.as_ref()[26..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Source addresses being queried.
+    pub fn sources(&self) -> SourceIter {
+        let length = self.number_of_sources() as usize * 16;
+
+        SourceIter {
+            buffer: &self.buffer.as_ref()[self.buffer.as_ref().len().min(28)..][..length],
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Maximum Response Code.
+    pub fn set_maximum_response_code(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Multicast address being queried.
+    pub fn set_multicast_address(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+        self.buffer.as_mut()[8..24].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+
+    /// Whether routers should suppress timer updates on receipt.
+    pub fn set_suppress(&mut self, value: bool) -> Result<&mut Self> {
+        if !self.is_v2() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if value {
+            self.buffer.as_mut()[24] |= 0b1000;
+        } else {
+            self.buffer.as_mut()[24] &= !0b1000;
+        }
+
+        Ok(self)
+    }
+
+    /// Querier's Robustness Variable.
+    pub fn set_robustness(&mut self, value: u8) -> Result<&mut Self> {
+        if !self.is_v2() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if value > 0b111 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.buffer.as_mut()[24] = (self.buffer.as_ref()[24] & !0b111) | value;
+
+        Ok(self)
+    }
+
+    /// Querier's Query Interval Code.
+    pub fn set_querier_interval_code(&mut self, value: u8) -> Result<&mut Self> {
+        if !self.is_v2() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        self.buffer.as_mut()[25] = value;
+
+        Ok(self)
+    }
+}
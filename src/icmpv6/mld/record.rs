@@ -0,0 +1,199 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::icmpv6::mld::SourceIter;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// MLDv2 multicast address record parser.
+pub struct Record<B> {
+    buffer: B,
+}
+
+sized!(Record,
+	header {
+		min:  20,
+		max:  20,
+		size: 20,
+	}
+
+	payload {
+		min:  0,
+		max:  16 * 0xffff + 4 * 0xff,
+		size: p => p.number_of_sources() as usize * 16 + p.auxiliary_length() as usize * 4,
+	});
+
+/// Record type.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+    ///
+    ModeIsInclude,
+
+    ///
+    ModeIsExclude,
+
+    ///
+    ChangeToIncludeMode,
+
+    ///
+    ChangeToExcludeMode,
+
+    ///
+    AllowNewSources,
+
+    ///
+    BlockOldSources,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Record<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::mld::Record")
+            .field("kind", &self.kind())
+            .field("multicast_address", &self.multicast_address())
+            .field("sources", &self.sources().collect::<Vec<_>>())
+            .field("auxiliary", &self.auxiliary())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Record<B> {
+    /// Parse a multicast address record, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Record<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let record = Record { buffer };
+
+        if record.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if record.buffer.as_ref().len() < record.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(record)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Record<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Record<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Record<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Record<&[u8]>> {
+        Record::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Record<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Record<&mut [u8]>> {
+        Record::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Record<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::Size;
+
+        self.buffer.as_ref()[..self.size()].split_at(20)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Record<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::Size;
+
+        let size = self.size();
+        self.buffer.as_mut()[..size].split_at_mut(20)
+    }
+}
+
+impl<B: AsRef<[u8]>> Record<B> {
+    /// Record type.
+    pub fn kind(&self) -> Kind {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the auxiliary data in 4 octet units.
+    pub fn auxiliary_length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Number of source addresses.
+    pub fn number_of_sources(&self) -> u16 {
+        (&self.buffer.as_ref()[2..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Multicast address the record refers to.
+    pub fn multicast_address(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[4..20]);
+
+        Ipv6Addr::from(octets)
+    }
+
+    /// Source addresses.
+    pub fn sources(&self) -> SourceIter {
+        let length = self.number_of_sources() as usize * 16;
+
+        SourceIter {
+            buffer: &self.buffer.as_ref()[20..20 + length],
+        }
+    }
+
+    /// Auxiliary data.
+    pub fn auxiliary(&self) -> &[u8] {
+        &self.payload()[self.number_of_sources() as usize * 16..]
+    }
+}
+
+impl From<u8> for Kind {
+    fn from(value: u8) -> Self {
+        use self::Kind::*;
+
+        match value {
+            1 => ModeIsInclude,
+            2 => ModeIsExclude,
+            3 => ChangeToIncludeMode,
+            4 => ChangeToExcludeMode,
+            5 => AllowNewSources,
+            6 => BlockOldSources,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for Kind {
+    fn into(self) -> u8 {
+        use self::Kind::*;
+
+        match self {
+            ModeIsInclude => 1,
+            ModeIsExclude => 2,
+            ChangeToIncludeMode => 3,
+            ChangeToExcludeMode => 4,
+            AllowNewSources => 5,
+            BlockOldSources => 6,
+            Unknown(v) => v,
+        }
+    }
+}
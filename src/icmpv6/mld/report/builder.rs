@@ -0,0 +1,176 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::mld::record;
+use crate::icmpv6::mld::report::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// MLDv2 Multicast Listener Report packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    records: u16,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::MulticastListenerReportV2.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            records: 0,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Add a multicast address record with the given source addresses.
+    pub fn record<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(mut self, kind: record::Kind, address: Ipv6Addr, sources: T) -> Result<Self> {
+        if self.records == u16::MAX {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let start = self.buffer.length();
+        self.buffer.more(20)?;
+
+        let mut count = 0usize;
+
+        for source in sources {
+            let offset = self.buffer.length();
+            self.buffer.more(16)?;
+            self.buffer.data_mut()[offset..].copy_from_slice(&source.octets());
+
+            count += 1;
+        }
+
+        if count > usize::from(u16::MAX) {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let data = &mut self.buffer.data_mut()[start..];
+        data[0] = kind.into();
+        data[1] = 0;
+        Cursor::new(&mut data[2..]).write_u16::<BigEndian>(count as u16)?;
+        data[4..20].copy_from_slice(&address.octets());
+
+        self.records += 1;
+        let records = self.records;
+        Packet::unchecked(self.buffer.data_mut()).set_number_of_records(records)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::mld::record;
+    use crate::ip;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn simple() {
+        let first = "ff3e::8000:1".parse::<Ipv6Addr>().unwrap();
+        let second = "ff05::1:3".parse::<Ipv6Addr>().unwrap();
+        let sources: [Ipv6Addr; 2] = ["2001:db8::1".parse().unwrap(), "2001:db8::2".parse().unwrap()];
+
+        let packet = ip::v6::Builder::default()
+            .hop_limit(1)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::16".parse().unwrap())
+            .unwrap()
+            .hop_by_hop()
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .mld_report()
+            .unwrap()
+            .record(record::Kind::ModeIsInclude, first, &sources)
+            .unwrap()
+            .record(record::Kind::ChangeToExcludeMode, second, &[])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let (_, payload) = ip.upper_layer().unwrap();
+        assert_eq!(payload.len(), 8 + 20 + 32 + 20);
+
+        let icmp = icmpv6::Packet::new(payload).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let report = icmp.mld_report().unwrap();
+        assert_eq!(report.number_of_records(), 2);
+
+        let records = report.records().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+
+        assert_eq!(records[0].kind(), record::Kind::ModeIsInclude);
+        assert_eq!(records[0].multicast_address(), first);
+        assert_eq!(records[0].sources().collect::<Vec<_>>(), sources);
+        assert!(records[0].auxiliary().is_empty());
+
+        assert_eq!(records[1].kind(), record::Kind::ChangeToExcludeMode);
+        assert_eq!(records[1].multicast_address(), second);
+        assert_eq!(records[1].number_of_sources(), 0);
+    }
+
+    #[test]
+    fn truncated() {
+        let raw = [143, 0, 0, 0, 0, 0, 0, 2, 1, 0, 0, 1, 0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+        let report = icmpv6::mld::report::Packet::new(&raw[..]).unwrap();
+
+        let mut records = report.records();
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmpv6::mld::Record;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// MLDv2 Multicast Listener Report packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::mld::report::Packet")
+            .field("records", &self.records().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Multicast Listener Report packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Multicast Listener Report packet, checking the buffer contents
+    /// are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::MulticastListenerReportV2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Number of multicast address records.
+    pub fn number_of_records(&self) -> u16 {
+        (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Multicast address records.
+    pub fn records(&self) -> RecordIter {
+        RecordIter {
+            buffer: self.payload(),
+            remaining: self.number_of_records(),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Number of multicast address records.
+    pub fn set_number_of_records(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+}
+
+/// Iterator over multicast address records.
+pub struct RecordIter<'a> {
+    buffer: &'a [u8],
+    remaining: u16,
+}
+
+impl<'a> Iterator for RecordIter<'a> {
+    type Item = Result<Record<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use crate::size::Size;
+
+        if self.remaining == 0 {
+            return None;
+        }
+
+        match Record::new(self.buffer) {
+            Ok(record) => {
+                self.buffer = &self.buffer[record.size()..];
+                self.remaining -= 1;
+
+                Some(Ok(record))
+            }
+
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+}
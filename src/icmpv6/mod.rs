@@ -0,0 +1,102 @@
+mod kind;
+pub use self::kind::Kind;
+
+/// ICMPv6 codes.
+pub mod code;
+
+mod packet;
+pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
+
+/// Echo Request/Reply.
+pub mod echo;
+
+/// Packet Too Big.
+pub mod packet_too_big;
+
+/// Parameter Problem.
+pub mod parameter_problem;
+
+/// Destination Unreachable and Time Exceeded.
+pub mod previous;
+
+/// Neighbor Discovery options.
+pub mod ndp;
+
+/// Router Solicitation.
+pub mod router_solicitation;
+
+/// Router Advertisement.
+pub mod router_advertisement;
+
+/// Neighbor Solicitation.
+pub mod neighbor_solicitation;
+
+/// Neighbor Advertisement.
+pub mod neighbor_advertisement;
+
+/// Redirect.
+pub mod redirect;
+
+/// Multicast Listener Discovery.
+pub mod mld;
+
+use crate::ip;
+use crate::ip::Protocol;
+
+/// Calculate the checksum for an ICMPv6 packet.
+///
+/// # Note
+///
+/// Since the checksum for ICMPv6 packets includes a pseudo-header based on the
+/// enclosing IPv6 packet, one has to be given.
+pub fn checksum<B: AsRef<[u8]>>(ip: &ip::v6::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+    use std::io::Cursor;
+
+    let mut prefix = [0u8; 40];
+    prefix[0..16].copy_from_slice(&ip.source().octets());
+    prefix[16..32].copy_from_slice(&ip.final_destination().octets());
+
+    Cursor::new(&mut prefix[32..]).write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+    prefix[39] = Protocol::Ipv6Icmp.into();
+
+    let mut result = 0xffffu32;
+    let mut buffer = Cursor::new(buffer);
+    let mut prefix = Cursor::new(&prefix[..]);
+
+    while let Ok(value) = prefix.read_u16::<BigEndian>() {
+        result += u32::from(value);
+
+        if result > 0xffff {
+            result -= 0xffff;
+        }
+    }
+
+    while let Ok(value) = buffer.read_u16::<BigEndian>() {
+        // Skip checksum field.
+        if buffer.position() == 4 {
+            continue;
+        }
+
+        result += u32::from(value);
+
+        if result > 0xffff {
+            result -= 0xffff;
+        }
+    }
+
+    // A failed read may consume the odd byte, so take it from the slice.
+    if buffer.get_ref().len() % 2 == 1 {
+        let last = u32::from(buffer.get_ref()[buffer.get_ref().len() - 1]) << 8;
+        result += last;
+
+        if result > 0xffff {
+            result -= 0xffff;
+        }
+    }
+
+    !result as u16
+}
@@ -0,0 +1,123 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use eui48::MacAddress;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
+
+use crate::buffer::Buffer;
+use crate::error::*;
+use crate::icmpv6::ndp::option::Kind;
+use crate::ip;
+use crate::packet::Packet as P;
+
+/// Append an option, padding it with zeros to a multiple of 8 octets.
+pub fn option<B: Buffer>(buffer: &mut B, kind: Kind, data: &[u8]) -> Result<()> {
+    let length = (2 + data.len() + 7) / 8;
+
+    if length > 255 {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    let start = buffer.length();
+    buffer.more(length * 8)?;
+
+    let out = &mut buffer.data_mut()[start..];
+    out[0] = kind.into();
+    out[1] = length as u8;
+    out[2..2 + data.len()].copy_from_slice(data);
+
+    for byte in &mut out[2 + data.len()..] {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
+pub fn link_layer_address<B: Buffer>(buffer: &mut B, kind: Kind, value: MacAddress) -> Result<()> {
+    option(buffer, kind, value.as_bytes())
+}
+
+pub fn mtu<B: Buffer>(buffer: &mut B, value: u32) -> Result<()> {
+    let mut data = [0u8; 6];
+    Cursor::new(&mut data[2..]).write_u32::<BigEndian>(value)?;
+
+    option(buffer, Kind::Mtu, &data)
+}
+
+pub fn prefix_information<B: Buffer>(buffer: &mut B, prefix: Ipv6Addr, length: u8, on_link: bool, autonomous: bool, valid: u32, preferred: u32) -> Result<()> {
+    if length > 128 {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    let mut data = [0u8; 30];
+    data[0] = length;
+
+    if on_link {
+        data[1] |= 0b1000_0000;
+    }
+
+    if autonomous {
+        data[1] |= 0b0100_0000;
+    }
+
+    Cursor::new(&mut data[2..]).write_u32::<BigEndian>(valid)?;
+    Cursor::new(&mut data[6..]).write_u32::<BigEndian>(preferred)?;
+    data[14..].copy_from_slice(&prefix.octets());
+
+    option(buffer, Kind::PrefixInformation, &data)
+}
+
+pub fn rdnss<B: Buffer>(buffer: &mut B, lifetime: u32, servers: &[Ipv6Addr]) -> Result<()> {
+    if servers.is_empty() {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    let mut data = vec![0u8; 6];
+    Cursor::new(&mut data[2..]).write_u32::<BigEndian>(lifetime)?;
+
+    for server in servers {
+        data.extend_from_slice(&server.octets());
+    }
+
+    option(buffer, Kind::RecursiveDnsServer, &data)
+}
+
+pub fn dnssl<B: Buffer>(buffer: &mut B, lifetime: u32, domains: &[&str]) -> Result<()> {
+    if domains.is_empty() {
+        return Err(ErrorKind::InvalidValue.into());
+    }
+
+    let mut data = vec![0u8; 6];
+    Cursor::new(&mut data[2..]).write_u32::<BigEndian>(lifetime)?;
+
+    for domain in domains {
+        for label in domain.trim_end_matches('.').split('.') {
+            if label.is_empty() || label.len() > 63 {
+                return Err(ErrorKind::InvalidValue.into());
+            }
+
+            data.push(label.len() as u8);
+            data.extend_from_slice(label.as_bytes());
+        }
+
+        data.push(0);
+    }
+
+    option(buffer, Kind::DnsSearchList, &data)
+}
+
+/// Append a Redirected Header option, truncating the packet so the whole
+/// message fits in the minimum IPv6 MTU.
+pub fn redirected_header<B: Buffer, I: AsRef<[u8]>>(buffer: &mut B, packet: &ip::v6::Packet<I>) -> Result<()> {
+    let available = 1280usize.saturating_sub(40 + buffer.length() + 8) / 8 * 8;
+    let (header, payload) = packet.split();
+
+    if available < header.len() {
+        return Err(ErrorKind::SmallBuffer.into());
+    }
+
+    let mut data = vec![0u8; 6];
+    data.extend_from_slice(header);
+    data.extend_from_slice(&payload[..payload.len().min(available - header.len())]);
+
+    option(buffer, Kind::RedirectedHeader, &data)
+}
@@ -0,0 +1,93 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+
+/// DNS Search List option parser, over the option payload.
+pub struct Dnssl<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Dnssl<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::ndp::Dnssl")
+            .field("lifetime", &self.lifetime())
+            .field("domains", &self.domains().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Dnssl<B> {
+    /// Parse a DNS Search List option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Dnssl<B>> {
+        let option = Dnssl { buffer };
+
+        if option.buffer.as_ref().len() < 6 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Dnssl<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Dnssl<B> {
+    /// Seconds the domain names can be used for name resolution.
+    pub fn lifetime(&self) -> u32 {
+        (&self.buffer.as_ref()[2..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Domain names in the search list.
+    pub fn domains(&self) -> DomainIter {
+        DomainIter {
+            buffer: &self.buffer.as_ref()[6..],
+        }
+    }
+}
+
+/// Iterator over the domain names in a DNS Search List option.
+pub struct DomainIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for DomainIter<'a> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The list is padded with zeros, which read as empty names.
+        if let None | Some(0) = self.buffer.first() {
+            return None;
+        }
+
+        let mut domain = String::new();
+
+        while let Some(&length) = self.buffer.first() {
+            let length = length as usize;
+
+            if length == 0 {
+                self.buffer = &self.buffer[1..];
+                return Some(Ok(domain));
+            }
+
+            if length > 63 || self.buffer.len() < length + 1 {
+                break;
+            }
+
+            if !domain.is_empty() {
+                domain.push('.');
+            }
+
+            domain.push_str(&String::from_utf8_lossy(&self.buffer[1..length + 1]));
+            self.buffer = &self.buffer[length + 1..];
+        }
+
+        self.buffer = &[];
+        Some(Err(ErrorKind::InvalidPacket.into()))
+    }
+}
@@ -0,0 +1,45 @@
+/// Neighbor Discovery option parser.
+pub mod option;
+pub use self::option::Option;
+
+mod prefix_information;
+pub use self::prefix_information::PrefixInformation;
+
+mod rdnss;
+pub use self::rdnss::Rdnss;
+
+mod dnssl;
+pub use self::dnssl::Dnssl;
+
+pub(in crate::icmpv6) mod builder;
+
+use crate::error::*;
+
+/// Iterator over Neighbor Discovery options.
+pub struct OptionIter<'a> {
+    pub(in crate::icmpv6) buffer: &'a [u8],
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+    type Item = Result<Option<&'a [u8]>>;
+
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        use crate::size::Size;
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        match Option::new(self.buffer) {
+            Ok(option) => {
+                self.buffer = &self.buffer[option.size()..];
+                Some(Ok(option))
+            }
+
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
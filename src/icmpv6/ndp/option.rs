@@ -0,0 +1,274 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use eui48::MacAddress;
+use std::fmt;
+
+use crate::error::*;
+use crate::icmpv6::ndp::{Dnssl, PrefixInformation, Rdnss};
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Neighbor Discovery option parser.
+pub struct Option<B> {
+    buffer: B,
+}
+
+sized!(Option,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  6,
+		max:  2038,
+		size: p => p.length() as usize * 8 - 2,
+	});
+
+/// Neighbor Discovery option type.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+    ///
+    SourceLinkLayerAddress,
+
+    ///
+    TargetLinkLayerAddress,
+
+    ///
+    PrefixInformation,
+
+    ///
+    RedirectedHeader,
+
+    ///
+    Mtu,
+
+    ///
+    RecursiveDnsServer,
+
+    ///
+    DnsSearchList,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::ndp::Option")
+            .field("kind", &self.kind())
+            .field("length", &self.length())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Parse a Neighbor Discovery option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Option<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let option = Option { buffer };
+
+        if option.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        // A zero length would make the option chain loop forever.
+        if option.length() == 0 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if option.buffer.as_ref().len() < option.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Option<&[u8]>> {
+        Option::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+        Option::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref()[..self.length() as usize * 8].split_at(2)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        let length = self.length() as usize;
+        self.buffer.as_mut()[..length * 8].split_at_mut(2)
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Option type.
+    pub fn kind(&self) -> Kind {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the option in 8 octet units.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Link-layer address of a Source or Target Link-Layer Address option.
+    pub fn link_layer_address(&self) -> Result<MacAddress> {
+        match self.kind() {
+            Kind::SourceLinkLayerAddress | Kind::TargetLinkLayerAddress => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(MacAddress::from_bytes(&self.payload()[..6]).unwrap())
+    }
+
+    /// Link MTU of an MTU option.
+    pub fn mtu(&self) -> Result<u32> {
+        if self.kind() != Kind::Mtu {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok((&self.payload()[2..]).read_u32::<BigEndian>()?)
+    }
+
+    /// Parse a Prefix Information option.
+    pub fn prefix_information(&self) -> Result<PrefixInformation<&[u8]>> {
+        if self.kind() != Kind::PrefixInformation {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        PrefixInformation::new(self.payload())
+    }
+
+    /// Parse a Recursive DNS Server option.
+    pub fn rdnss(&self) -> Result<Rdnss<&[u8]>> {
+        if self.kind() != Kind::RecursiveDnsServer {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Rdnss::new(self.payload())
+    }
+
+    /// Parse a DNS Search List option.
+    pub fn dnssl(&self) -> Result<Dnssl<&[u8]>> {
+        if self.kind() != Kind::DnsSearchList {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Dnssl::new(self.payload())
+    }
+
+    /// Packet quoted by a Redirected Header option, possibly truncated.
+    pub fn redirected_header(&self) -> Result<ip::v6::Packet<&[u8]>> {
+        if self.kind() != Kind::RedirectedHeader {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        ip::v6::Packet::no_payload(&self.payload()[6..])
+    }
+}
+
+impl From<u8> for Kind {
+    fn from(value: u8) -> Self {
+        use self::Kind::*;
+
+        match value {
+            1 => SourceLinkLayerAddress,
+            2 => TargetLinkLayerAddress,
+            3 => PrefixInformation,
+            4 => RedirectedHeader,
+            5 => Mtu,
+            25 => RecursiveDnsServer,
+            31 => DnsSearchList,
+            v => Unknown(v),
+        }
+    }
+}
+
+impl Into<u8> for Kind {
+    fn into(self) -> u8 {
+        use self::Kind::*;
+
+        match self {
+            SourceLinkLayerAddress => 1,
+            TargetLinkLayerAddress => 2,
+            PrefixInformation => 3,
+            RedirectedHeader => 4,
+            Mtu => 5,
+            RecursiveDnsServer => 25,
+            DnsSearchList => 31,
+            Unknown(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::icmpv6::ndp::{option, Option, OptionIter};
+    use crate::packet::Packet;
+
+    #[test]
+    fn chain() {
+        let raw = [
+            // source link-layer address
+            0x01, 0x01, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55,
+            // MTU
+            0x05, 0x01, 0x00, 0x00, 0x00, 0x00, 0x05, 0xdc,
+            // unknown
+            0xfe, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let options = OptionIter { buffer: &raw[..] }.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options.len(), 3);
+
+        assert_eq!(options[0].kind(), option::Kind::SourceLinkLayerAddress);
+        assert_eq!(options[0].link_layer_address().unwrap().to_hex_string(), "00:11:22:33:44:55");
+        assert!(options[0].mtu().is_err());
+
+        assert_eq!(options[1].kind(), option::Kind::Mtu);
+        assert_eq!(options[1].mtu().unwrap(), 1500);
+
+        assert_eq!(options[2].kind(), option::Kind::Unknown(0xfe));
+        assert_eq!(options[2].payload().len(), 6);
+    }
+
+    #[test]
+    fn zero_length() {
+        assert!(Option::new(&[0x01, 0x00, 0, 0, 0, 0, 0, 0][..]).is_err());
+
+        let mut options = OptionIter { buffer: &[0x01, 0x00, 0, 0, 0, 0, 0, 0][..] };
+        assert!(options.next().unwrap().is_err());
+        assert!(options.next().is_none());
+    }
+}
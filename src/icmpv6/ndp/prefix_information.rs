@@ -0,0 +1,78 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+
+/// Prefix Information option parser, over the option payload.
+pub struct PrefixInformation<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for PrefixInformation<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::ndp::PrefixInformation")
+            .field("prefix", &self.prefix())
+            .field("prefix_length", &self.prefix_length())
+            .field("is_on_link", &self.is_on_link())
+            .field("is_autonomous", &self.is_autonomous())
+            .field("valid_lifetime", &self.valid_lifetime())
+            .field("preferred_lifetime", &self.preferred_lifetime())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> PrefixInformation<B> {
+    /// Parse a Prefix Information option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<PrefixInformation<B>> {
+        let option = PrefixInformation { buffer };
+
+        if option.buffer.as_ref().len() < 30 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for PrefixInformation<B> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer.as_ref()[..30]
+    }
+}
+
+impl<B: AsRef<[u8]>> PrefixInformation<B> {
+    /// Number of leading bits in the prefix that are valid.
+    pub fn prefix_length(&self) -> u8 {
+        self.buffer.as_ref()[0]
+    }
+
+    /// Whether the prefix can be used for on-link determination.
+    pub fn is_on_link(&self) -> bool {
+        self.buffer.as_ref()[1] & 0b1000_0000 != 0
+    }
+
+    /// Whether the prefix can be used for stateless address configuration.
+    pub fn is_autonomous(&self) -> bool {
+        self.buffer.as_ref()[1] & 0b0100_0000 != 0
+    }
+
+    /// Seconds the prefix is valid for on-link determination.
+    pub fn valid_lifetime(&self) -> u32 {
+        (&self.buffer.as_ref()[2..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Seconds addresses generated from the prefix remain preferred.
+    pub fn preferred_lifetime(&self) -> u32 {
+        (&self.buffer.as_ref()[6..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// The prefix.
+    pub fn prefix(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[14..30]);
+
+        Ipv6Addr::from(octets)
+    }
+}
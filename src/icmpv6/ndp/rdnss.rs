@@ -0,0 +1,74 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+
+/// Recursive DNS Server option parser, over the option payload.
+pub struct Rdnss<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Rdnss<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::ndp::Rdnss")
+            .field("lifetime", &self.lifetime())
+            .field("servers", &self.servers().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Rdnss<B> {
+    /// Parse a Recursive DNS Server option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Rdnss<B>> {
+        let option = Rdnss { buffer };
+
+        if option.buffer.as_ref().len() < 6 + 16 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Rdnss<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Rdnss<B> {
+    /// Seconds the servers can be used for name resolution.
+    pub fn lifetime(&self) -> u32 {
+        (&self.buffer.as_ref()[2..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Addresses of the recursive DNS servers.
+    pub fn servers(&self) -> ServerIter {
+        ServerIter {
+            buffer: &self.buffer.as_ref()[6..],
+        }
+    }
+}
+
+/// Iterator over the addresses in a Recursive DNS Server option.
+pub struct ServerIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for ServerIter<'a> {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < 16 {
+            return None;
+        }
+
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer[..16]);
+        self.buffer = &self.buffer[16..];
+
+        Some(Ipv6Addr::from(octets))
+    }
+}
@@ -0,0 +1,145 @@
+use eui48::MacAddress;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::ndp::{self, option};
+use crate::icmpv6::neighbor_advertisement::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Neighbor Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(24)?;
+        buffer.data_mut()[0] = Kind::NeighborAdvertisement.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Whether the sender is a router.
+    pub fn router(mut self, value: bool) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_router(value)?;
+        Ok(self)
+    }
+
+    /// Whether the advertisement answers a solicitation.
+    pub fn solicited(mut self, value: bool) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_solicited(value)?;
+        Ok(self)
+    }
+
+    /// Whether the advertisement should override cached link-layer addresses.
+    pub fn overrides(mut self, value: bool) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_override(value)?;
+        Ok(self)
+    }
+
+    /// Address whose link-layer address is advertised.
+    pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_target(value)?;
+        Ok(self)
+    }
+
+    /// Link-layer address of the target.
+    pub fn target_link_layer_address(mut self, value: MacAddress) -> Result<Self> {
+        ndp::builder::link_layer_address(&mut self.buffer, option::Kind::TargetLinkLayerAddress, value)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::ndp::option;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(255)
+            .unwrap()
+            .source("fe80::2".parse().unwrap())
+            .unwrap()
+            .destination("fe80::1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .neighbor_advertisement()
+            .unwrap()
+            .solicited(true)
+            .unwrap()
+            .overrides(true)
+            .unwrap()
+            .target("fe80::2".parse().unwrap())
+            .unwrap()
+            .target_link_layer_address("66:77:88:99:aa:bb".parse().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let advertisement = icmp.neighbor_advertisement().unwrap();
+        assert!(!advertisement.is_router());
+        assert!(advertisement.is_solicited());
+        assert!(advertisement.is_override());
+        assert_eq!(advertisement.target(), ip.source());
+
+        let option = advertisement.options().next().unwrap().unwrap();
+        assert_eq!(option.kind(), option::Kind::TargetLinkLayerAddress);
+        assert_eq!(option.link_layer_address().unwrap().to_hex_string(), "66:77:88:99:aa:bb");
+    }
+}
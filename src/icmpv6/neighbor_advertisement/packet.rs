@@ -0,0 +1,180 @@
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::icmpv6::ndp::OptionIter;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Neighbor Advertisement packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  24,
+		max:  24,
+		size: 24,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 24,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::neighbor_advertisement::Packet")
+            .field("is_router", &self.is_router())
+            .field("is_solicited", &self.is_solicited())
+            .field("is_override", &self.is_override())
+            .field("target", &self.target())
+            .field("options", &self.options().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Neighbor Advertisement packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Neighbor Advertisement packet, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::NeighborAdvertisement {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(24)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(24)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Whether the sender is a router.
+    pub fn is_router(&self) -> bool {
+        self.buffer.as_ref()[4] & 0b1000_0000 != 0
+    }
+
+    /// Whether the advertisement answers a solicitation.
+    pub fn is_solicited(&self) -> bool {
+        self.buffer.as_ref()[4] & 0b0100_0000 != 0
+    }
+
+    /// Whether the advertisement should override cached link-layer addresses.
+    pub fn is_override(&self) -> bool {
+        self.buffer.as_ref()[4] & 0b0010_0000 != 0
+    }
+
+    /// Address whose link-layer address is advertised.
+    pub fn target(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[8..24]);
+
+        Ipv6Addr::from(octets)
+    }
+
+    /// Neighbor Discovery options.
+    pub fn options(&self) -> OptionIter {
+        OptionIter { buffer: self.payload() }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Whether the sender is a router.
+    pub fn set_router(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[4] |= 0b1000_0000;
+        } else {
+            self.buffer.as_mut()[4] &= !0b1000_0000;
+        }
+
+        Ok(self)
+    }
+
+    /// Whether the advertisement answers a solicitation.
+    pub fn set_solicited(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[4] |= 0b0100_0000;
+        } else {
+            self.buffer.as_mut()[4] &= !0b0100_0000;
+        }
+
+        Ok(self)
+    }
+
+    /// Whether the advertisement should override cached link-layer addresses.
+    pub fn set_override(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[4] |= 0b0010_0000;
+        } else {
+            self.buffer.as_mut()[4] &= !0b0010_0000;
+        }
+
+        Ok(self)
+    }
+
+    /// Address whose link-layer address is advertised.
+    pub fn set_target(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+        self.buffer.as_mut()[8..24].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,129 @@
+use eui48::MacAddress;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::ndp::{self, option};
+use crate::icmpv6::neighbor_solicitation::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Neighbor Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(24)?;
+        buffer.data_mut()[0] = Kind::NeighborSolicitation.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Address of the target of the solicitation.
+    pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_target(value)?;
+        Ok(self)
+    }
+
+    /// Link-layer address of the sender.
+    pub fn source_link_layer_address(mut self, value: MacAddress) -> Result<Self> {
+        ndp::builder::link_layer_address(&mut self.buffer, option::Kind::SourceLinkLayerAddress, value)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    const RAW: [u8; 72] = [
+        0x60, 0x00, 0x00, 0x00, 0x00, 0x20, 0x3a, 0xff, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xff, 0x02, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xff, 0x00, 0x00, 0x02, 0x87, 0x00, 0x15, 0xff, 0x00, 0x00, 0x00, 0x00, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x01, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55,
+    ];
+
+    #[test]
+    fn simple() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(255)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::1:ff00:2".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .neighbor_solicitation()
+            .unwrap()
+            .target("fe80::2".parse().unwrap())
+            .unwrap()
+            .source_link_layer_address("00:11:22:33:44:55".parse().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(&packet[..], &RAW[..]);
+    }
+
+    #[test]
+    fn parse() {
+        let ip = ip::v6::Packet::new(&RAW[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert_eq!(icmp.kind(), icmpv6::Kind::NeighborSolicitation);
+        assert_eq!(icmp.checksum(), 0x15ff);
+        assert!(icmp.is_valid(&ip));
+
+        let solicitation = icmp.neighbor_solicitation().unwrap();
+        assert_eq!(solicitation.target(), "fe80::2".parse::<std::net::Ipv6Addr>().unwrap());
+        assert_eq!(solicitation.options().count(), 1);
+    }
+}
@@ -0,0 +1,211 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmpv6::checksum;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// ICMPv6 packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::Packet")
+            .field("kind", &self.kind())
+            .field("code", &self.code())
+            .field("checksum", &self.checksum())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create an ICMPv6 packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse an ICMPv6 packet, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(4)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(4)
+    }
+}
+
+macro_rules! kind {
+	($(#[$attr:meta])* fn $module:ident[$mutable:ident]) => (
+		kind!($(#[$attr])* fn $module[$mutable] => $module);
+	);
+
+	($(#[$attr:meta])* fn $name:ident[$mutable:ident] => $($module:ident)::+) => (
+		$(#[$attr])*
+		pub fn $name(&self) -> Result<crate::icmpv6::$($module)::+::Packet<&B>> {
+			crate::icmpv6::$($module)::+::Packet::new(&self.buffer)
+		}
+
+		$(#[$attr])*
+		pub fn $mutable(&mut self) -> Result<crate::icmpv6::$($module)::+::Packet<&mut B>> {
+			crate::icmpv6::$($module)::+::Packet::new(&mut self.buffer)
+		}
+	)
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Packet type.
+    pub fn kind(&self) -> Kind {
+        Kind::from(self.buffer.as_ref()[0])
+    }
+
+    /// Packet code.
+    pub fn code(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Packet checksum.
+    pub fn checksum(&self) -> u16 {
+        (&self.buffer.as_ref()[2..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Verify the packet is valid by calculating the checksum.
+    pub fn is_valid<I: AsRef<[u8]>>(&self, ip: &ip::v6::Packet<I>) -> bool {
+        checksum(ip, self.buffer.as_ref()) == self.checksum()
+    }
+
+    kind!(/// Parse an Echo Request/Reply packet.
+		fn echo[echo_mut]);
+
+    kind!(/// Parse a Packet Too Big packet.
+		fn packet_too_big[packet_too_big_mut]);
+
+    kind!(/// Parse a Parameter Problem packet.
+		fn parameter_problem[parameter_problem_mut]);
+
+    kind!(/// Parse a Destination Unreachable or Time Exceeded packet.
+		fn previous[previous_mut]);
+
+    kind!(/// Parse a Router Solicitation packet.
+		fn router_solicitation[router_solicitation_mut]);
+
+    kind!(/// Parse a Router Advertisement packet.
+		fn router_advertisement[router_advertisement_mut]);
+
+    kind!(/// Parse a Neighbor Solicitation packet.
+		fn neighbor_solicitation[neighbor_solicitation_mut]);
+
+    kind!(/// Parse a Neighbor Advertisement packet.
+		fn neighbor_advertisement[neighbor_advertisement_mut]);
+
+    kind!(/// Parse a Redirect packet.
+		fn redirect[redirect_mut]);
+
+    kind!(/// Parse an MLDv1 or MLDv2 Multicast Listener Query packet.
+		fn mld_query[mld_query_mut] => mld::query);
+
+    kind!(/// Parse an MLDv1 Multicast Listener Report or Done packet.
+		fn mld_listener[mld_listener_mut] => mld::listener);
+
+    kind!(/// Parse an MLDv2 Multicast Listener Report packet.
+		fn mld_report[mld_report_mut] => mld::report);
+}
+
+/// Checked wrapper for ICMPv6 packets.
+///
+/// # Note
+///
+/// The checksum recalculation happens on `Drop`, so don't leak it.
+pub struct Checked<'a, 'b, P, BI>
+where
+    P: PM + AsRef<[u8]> + AsMut<[u8]> + 'a,
+    BI: AsRef<[u8]> + 'b,
+{
+    pub(in crate::icmpv6) packet: &'a mut P,
+    pub(in crate::icmpv6) ip: &'b ip::v6::Packet<BI>,
+}
+
+impl<'a, 'b, P, BI> Drop for Checked<'a, 'b, P, BI>
+where
+    P: PM + AsRef<[u8]> + AsMut<[u8]> + 'a,
+    BI: AsRef<[u8]> + 'b,
+{
+    fn drop(&mut self) {
+        let checksum = checksum(self.ip, self.packet.as_ref());
+        Cursor::new(&mut self.packet.as_mut()[2..]).write_u16::<BigEndian>(checksum).unwrap();
+    }
+}
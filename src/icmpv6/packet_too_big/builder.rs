@@ -0,0 +1,123 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::packet_too_big::Packet;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Packet Too Big packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    packet: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::PacketTooBig.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            packet: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Maximum transmission unit of the next-hop link.
+    pub fn mtu(mut self, value: u32) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[4..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet to cause the message, truncated so the message fits in the
+    /// minimum IPv6 MTU.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v6::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let invoking = ip::v6::Builder::default().udp().unwrap().payload(&[0xaa; 1400][..]).unwrap().build().unwrap();
+        let invoking = ip::v6::Packet::new(&invoking[..]).unwrap();
+
+        let packet = ip::v6::Builder::default()
+            .icmp()
+            .unwrap()
+            .packet_too_big()
+            .unwrap()
+            .mtu(1280)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let packet_too_big = icmp.packet_too_big().unwrap();
+        assert_eq!(packet_too_big.mtu(), 1280);
+        assert_eq!(packet_too_big.packet().unwrap().payload_length(), 1408);
+    }
+}
@@ -0,0 +1,135 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::code;
+use crate::icmpv6::parameter_problem::Packet;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Parameter Problem packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    packet: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::ParameterProblem.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            packet: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Code of the problem.
+    pub fn code(mut self, value: code::ParameterProblem) -> Result<Self> {
+        self.buffer.data_mut()[1] = value.into();
+
+        Ok(self)
+    }
+
+    /// Offset within the invoking packet where the problem was detected.
+    pub fn pointer(mut self, value: u32) -> Result<Self> {
+        Cursor::new(&mut self.buffer.data_mut()[4..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Packet to cause the message, truncated so the message fits in the
+    /// minimum IPv6 MTU.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v6::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::code;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let invoking = ip::v6::Builder::default().udp().unwrap().build().unwrap();
+        let invoking = ip::v6::Packet::new(&invoking[..]).unwrap();
+
+        let packet = ip::v6::Builder::default()
+            .icmp()
+            .unwrap()
+            .parameter_problem()
+            .unwrap()
+            .code(code::ParameterProblem::UnrecognizedNextHeader)
+            .unwrap()
+            .pointer(6)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let parameter_problem = icmp.parameter_problem().unwrap();
+        assert_eq!(parameter_problem.code(), code::ParameterProblem::UnrecognizedNextHeader);
+        assert_eq!(parameter_problem.pointer(), 6);
+        assert_eq!(parameter_problem.packet().unwrap().next_header(), ip::Protocol::Udp);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::code;
+use crate::icmpv6::previous::Packet;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Destination Unreachable and Time Exceeded packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    kind: bool,
+    packet: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(8)?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            kind: false,
+            packet: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        if !self.kind {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Make it a Destination Unreachable packet.
+    pub fn destination_unreachable(mut self, code: code::DestinationUnreachable) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::DestinationUnreachable.into();
+        self.buffer.data_mut()[1] = code.into();
+
+        Ok(self)
+    }
+
+    /// Make it a Time Exceeded packet.
+    pub fn time_exceeded(mut self, code: code::TimeExceeded) -> Result<Self> {
+        self.kind = true;
+        self.buffer.data_mut()[0] = Kind::TimeExceeded.into();
+        self.buffer.data_mut()[1] = code.into();
+
+        Ok(self)
+    }
+
+    /// Packet to cause the message, truncated so the message fits in the
+    /// minimum IPv6 MTU.
+    pub fn packet<I: AsRef<[u8]>>(mut self, packet: &ip::v6::Packet<I>) -> Result<Self> {
+        if self.packet {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.packet = true;
+        builder::quote(&mut self.buffer, packet)?;
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::code;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn quote() {
+        let invoking = ip::v6::Builder::default()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(1000)
+            .unwrap()
+            .destination(53)
+            .unwrap()
+            .payload(&[0xaa; 2000][..])
+            .unwrap()
+            .build()
+            .unwrap();
+        let invoking = ip::v6::Packet::new(&invoking[..]).unwrap();
+
+        let packet = ip::v6::Builder::default()
+            .source("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .previous()
+            .unwrap()
+            .destination_unreachable(code::DestinationUnreachable::PortUnreachable)
+            .unwrap()
+            .packet(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 1280);
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+        assert_eq!(icmp.kind(), icmpv6::Kind::DestinationUnreachable);
+
+        let previous = icmp.previous().unwrap();
+        assert_eq!(previous.destination_unreachable(), Some(code::DestinationUnreachable::PortUnreachable));
+        assert_eq!(previous.time_exceeded(), None);
+
+        let quoted = previous.packet().unwrap();
+        assert_eq!(quoted.source(), invoking.source());
+        assert_eq!(quoted.payload_length(), invoking.payload_length());
+        assert_eq!(&previous.payload()[40..48], &invoking.payload()[..8]);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+use crate::error::*;
+use crate::icmpv6::code;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Destination Unreachable and Time Exceeded packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::previous::Packet").field("packet", &self.packet()).finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Destination Unreachable and Time Exceeded packet without
+    /// checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Destination Unreachable and Time Exceeded packet, checking the
+    /// buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        match Kind::from(packet.buffer.as_ref()[0]) {
+            Kind::DestinationUnreachable | Kind::TimeExceeded => (),
+
+            _ => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        let (header, payload) = self.buffer.as_ref().split_at(8);
+        (&header[..4], payload)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        let (header, payload) = self.buffer.as_mut().split_at_mut(8);
+        (&mut header[..4], payload)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Check if it's a Destination Unreachable packet.
+    pub fn is_destination_unreachable(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::DestinationUnreachable
+    }
+
+    /// Check if it's a Time Exceeded packet.
+    pub fn is_time_exceeded(&self) -> bool {
+        Kind::from(self.buffer.as_ref()[0]) == Kind::TimeExceeded
+    }
+
+    /// Code for Destination Unreachable packets.
+    pub fn destination_unreachable(&self) -> Option<code::DestinationUnreachable> {
+        if self.is_destination_unreachable() {
+            Some(self.buffer.as_ref()[1].into())
+        } else {
+            None
+        }
+    }
+
+    /// Code for Time Exceeded packets.
+    pub fn time_exceeded(&self) -> Option<code::TimeExceeded> {
+        if self.is_time_exceeded() {
+            Some(self.buffer.as_ref()[1].into())
+        } else {
+            None
+        }
+    }
+
+    /// Packet to cause the message, possibly truncated.
+    pub fn packet(&self) -> Result<ip::v6::Packet<&[u8]>> {
+        ip::v6::Packet::no_payload(&self.buffer.as_ref()[8..])
+    }
+}
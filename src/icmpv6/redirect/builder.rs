@@ -0,0 +1,157 @@
+use eui48::MacAddress;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::ndp::{self, option};
+use crate::icmpv6::redirect::Packet;
+use crate::icmpv6::Kind;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Redirect packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(40)?;
+        buffer.data_mut()[0] = Kind::Redirect.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Better first hop to use for the destination.
+    pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_target(value)?;
+        Ok(self)
+    }
+
+    /// Destination being redirected.
+    pub fn destination(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
+        Ok(self)
+    }
+
+    /// Link-layer address of the target.
+    pub fn target_link_layer_address(mut self, value: MacAddress) -> Result<Self> {
+        ndp::builder::link_layer_address(&mut self.buffer, option::Kind::TargetLinkLayerAddress, value)?;
+        Ok(self)
+    }
+
+    /// Packet that triggered the redirect, truncated so the message fits in
+    /// the minimum IPv6 MTU.
+    pub fn redirected_header<I: AsRef<[u8]>>(mut self, packet: &ip::v6::Packet<I>) -> Result<Self> {
+        ndp::builder::redirected_header(&mut self.buffer, packet)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::ndp::option;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let invoking = ip::v6::Builder::default()
+            .source("2001:db8::10".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8:1::20".parse().unwrap())
+            .unwrap()
+            .udp()
+            .unwrap()
+            .payload(&[0xaa; 1500][..])
+            .unwrap()
+            .build()
+            .unwrap();
+        let invoking = ip::v6::Packet::new(&invoking[..]).unwrap();
+
+        let packet = ip::v6::Builder::default()
+            .hop_limit(255)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::10".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .redirect()
+            .unwrap()
+            .target("fe80::2".parse().unwrap())
+            .unwrap()
+            .destination(invoking.destination())
+            .unwrap()
+            .target_link_layer_address("66:77:88:99:aa:bb".parse().unwrap())
+            .unwrap()
+            .redirected_header(&invoking)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(packet.len(), 1280);
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let redirect = icmp.redirect().unwrap();
+        assert_eq!(redirect.target(), "fe80::2".parse::<std::net::Ipv6Addr>().unwrap());
+        assert_eq!(redirect.destination(), invoking.destination());
+
+        let options = redirect.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options[1].kind(), option::Kind::RedirectedHeader);
+
+        let quoted = options[1].redirected_header().unwrap();
+        assert_eq!(quoted.source(), invoking.source());
+        assert_eq!(quoted.payload_length(), invoking.payload_length());
+    }
+}
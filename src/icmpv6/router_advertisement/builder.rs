@@ -0,0 +1,238 @@
+use eui48::MacAddress;
+use std::net::Ipv6Addr;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::ndp::{self, option};
+use crate::icmpv6::router_advertisement::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Router Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(16)?;
+        buffer.data_mut()[0] = Kind::RouterAdvertisement.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Default hop limit for outgoing packets, zero if unspecified.
+    pub fn hop_limit(mut self, value: u8) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_hop_limit(value)?;
+        Ok(self)
+    }
+
+    /// Whether addresses are available via DHCPv6.
+    pub fn managed(mut self, value: bool) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_managed(value)?;
+        Ok(self)
+    }
+
+    /// Whether other configuration is available via DHCPv6.
+    pub fn other(mut self, value: bool) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_other(value)?;
+        Ok(self)
+    }
+
+    /// Seconds the router can be used as a default router.
+    pub fn router_lifetime(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_router_lifetime(value)?;
+        Ok(self)
+    }
+
+    /// Milliseconds a neighbor is considered reachable after a confirmation.
+    pub fn reachable_time(mut self, value: u32) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_reachable_time(value)?;
+        Ok(self)
+    }
+
+    /// Milliseconds between retransmitted Neighbor Solicitation packets.
+    pub fn retransmit_timer(mut self, value: u32) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_retransmit_timer(value)?;
+        Ok(self)
+    }
+
+    /// Link-layer address of the sender.
+    pub fn source_link_layer_address(mut self, value: MacAddress) -> Result<Self> {
+        ndp::builder::link_layer_address(&mut self.buffer, option::Kind::SourceLinkLayerAddress, value)?;
+        Ok(self)
+    }
+
+    /// MTU of the link.
+    pub fn mtu(mut self, value: u32) -> Result<Self> {
+        ndp::builder::mtu(&mut self.buffer, value)?;
+        Ok(self)
+    }
+
+    /// Prefix for on-link determination and address configuration.
+    pub fn prefix_information(mut self, prefix: Ipv6Addr, length: u8, on_link: bool, autonomous: bool, valid: u32, preferred: u32) -> Result<Self> {
+        ndp::builder::prefix_information(&mut self.buffer, prefix, length, on_link, autonomous, valid, preferred)?;
+        Ok(self)
+    }
+
+    /// Recursive DNS servers, usable for `lifetime` seconds.
+    pub fn rdnss(mut self, lifetime: u32, servers: &[Ipv6Addr]) -> Result<Self> {
+        ndp::builder::rdnss(&mut self.buffer, lifetime, servers)?;
+        Ok(self)
+    }
+
+    /// DNS search list, usable for `lifetime` seconds.
+    pub fn dnssl(mut self, lifetime: u32, domains: &[&str]) -> Result<Self> {
+        ndp::builder::dnssl(&mut self.buffer, lifetime, domains)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::ndp::option;
+    use crate::ip;
+    use crate::packet::Packet;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn simple() {
+        let servers: [Ipv6Addr; 2] = ["2001:db8::53".parse().unwrap(), "2001:db8::5353".parse().unwrap()];
+
+        let packet = ip::v6::Builder::default()
+            .hop_limit(255)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::1".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .router_advertisement()
+            .unwrap()
+            .hop_limit(64)
+            .unwrap()
+            .other(true)
+            .unwrap()
+            .router_lifetime(1800)
+            .unwrap()
+            .reachable_time(30000)
+            .unwrap()
+            .retransmit_timer(1000)
+            .unwrap()
+            .source_link_layer_address("00:11:22:33:44:55".parse().unwrap())
+            .unwrap()
+            .mtu(1500)
+            .unwrap()
+            .prefix_information("2001:db8::".parse().unwrap(), 64, true, true, 86400, 14400)
+            .unwrap()
+            .rdnss(600, &servers)
+            .unwrap()
+            .dnssl(600, &["example.com", "lab.example.org."])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let advertisement = icmp.router_advertisement().unwrap();
+        assert_eq!(advertisement.hop_limit(), 64);
+        assert!(!advertisement.is_managed());
+        assert!(advertisement.is_other());
+        assert_eq!(advertisement.router_lifetime(), 1800);
+        assert_eq!(advertisement.reachable_time(), 30000);
+        assert_eq!(advertisement.retransmit_timer(), 1000);
+
+        let options = advertisement.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            options.iter().map(|o| o.kind()).collect::<Vec<_>>(),
+            vec![
+                option::Kind::SourceLinkLayerAddress,
+                option::Kind::Mtu,
+                option::Kind::PrefixInformation,
+                option::Kind::RecursiveDnsServer,
+                option::Kind::DnsSearchList,
+            ]
+        );
+
+        assert_eq!(options[1].mtu().unwrap(), 1500);
+
+        let prefix = options[2].prefix_information().unwrap();
+        assert_eq!(options[2].length(), 4);
+        assert_eq!(prefix.prefix(), "2001:db8::".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(prefix.prefix_length(), 64);
+        assert!(prefix.is_on_link());
+        assert!(prefix.is_autonomous());
+        assert_eq!(prefix.valid_lifetime(), 86400);
+        assert_eq!(prefix.preferred_lifetime(), 14400);
+
+        let rdnss = options[3].rdnss().unwrap();
+        assert_eq!(options[3].length(), 5);
+        assert_eq!(rdnss.lifetime(), 600);
+        assert_eq!(rdnss.servers().collect::<Vec<_>>(), servers);
+
+        let dnssl = options[4].dnssl().unwrap();
+        assert_eq!(dnssl.lifetime(), 600);
+        assert_eq!(dnssl.domains().collect::<Result<Vec<_>, _>>().unwrap(), vec!["example.com", "lab.example.org"]);
+    }
+
+    #[test]
+    fn invalid_options() {
+        let builder = ip::v6::Builder::default().icmp().unwrap().router_advertisement().unwrap();
+        assert!(builder.dnssl(0, &["bad..label"]).is_err());
+
+        let builder = ip::v6::Builder::default().icmp().unwrap().router_advertisement().unwrap();
+        assert!(builder.rdnss(0, &[]).is_err());
+
+        let builder = ip::v6::Builder::default().icmp().unwrap().router_advertisement().unwrap();
+        assert!(builder.prefix_information(Ipv6Addr::UNSPECIFIED, 129, false, false, 0, 0).is_err());
+    }
+}
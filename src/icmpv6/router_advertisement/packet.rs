@@ -0,0 +1,200 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::icmpv6::ndp::OptionIter;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Router Advertisement packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  16,
+		max:  16,
+		size: 16,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 16,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("icmpv6::router_advertisement::Packet")
+            .field("hop_limit", &self.hop_limit())
+            .field("is_managed", &self.is_managed())
+            .field("is_other", &self.is_other())
+            .field("router_lifetime", &self.router_lifetime())
+            .field("reachable_time", &self.reachable_time())
+            .field("retransmit_timer", &self.retransmit_timer())
+            .field("options", &self.options().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a Router Advertisement packet without checking the buffer.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a Router Advertisement packet, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if Kind::from(packet.buffer.as_ref()[0]) != Kind::RouterAdvertisement {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet::unchecked(self.buffer.as_ref().to_vec())
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(16)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(16)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Default hop limit for outgoing packets, zero if unspecified.
+    pub fn hop_limit(&self) -> u8 {
+        self.buffer.as_ref()[4]
+    }
+
+    /// Whether addresses are available via DHCPv6.
+    pub fn is_managed(&self) -> bool {
+        self.buffer.as_ref()[5] & 0b1000_0000 != 0
+    }
+
+    /// Whether other configuration is available via DHCPv6.
+    pub fn is_other(&self) -> bool {
+        self.buffer.as_ref()[5] & 0b0100_0000 != 0
+    }
+
+    /// Seconds the router can be used as a default router.
+    pub fn router_lifetime(&self) -> u16 {
+        (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Milliseconds a neighbor is considered reachable after a confirmation.
+    pub fn reachable_time(&self) -> u32 {
+        (&self.buffer.as_ref()[8..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Milliseconds between retransmitted Neighbor Solicitation packets.
+    pub fn retransmit_timer(&self) -> u32 {
+        (&self.buffer.as_ref()[12..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Neighbor Discovery options.
+    pub fn options(&self) -> OptionIter {
+        OptionIter { buffer: self.payload() }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Default hop limit for outgoing packets, zero if unspecified.
+    pub fn set_hop_limit(&mut self, value: u8) -> Result<&mut Self> {
+        self.buffer.as_mut()[4] = value;
+
+        Ok(self)
+    }
+
+    /// Whether addresses are available via DHCPv6.
+    pub fn set_managed(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[5] |= 0b1000_0000;
+        } else {
+            self.buffer.as_mut()[5] &= !0b1000_0000;
+        }
+
+        Ok(self)
+    }
+
+    /// Whether other configuration is available via DHCPv6.
+    pub fn set_other(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[5] |= 0b0100_0000;
+        } else {
+            self.buffer.as_mut()[5] &= !0b0100_0000;
+        }
+
+        Ok(self)
+    }
+
+    /// Seconds the router can be used as a default router.
+    pub fn set_router_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Milliseconds a neighbor is considered reachable after a confirmation.
+    pub fn set_reachable_time(&mut self, value: u32) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[8..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Milliseconds between retransmitted Neighbor Solicitation packets.
+    pub fn set_retransmit_timer(&mut self, value: u32) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[12..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,113 @@
+use eui48::MacAddress;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::icmpv6::builder;
+use crate::icmpv6::ndp::{self, option};
+use crate::icmpv6::router_solicitation::Packet;
+use crate::icmpv6::Kind;
+use crate::packet::{AsPacket, AsPacketMut};
+
+/// Router Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+        buffer.next(8)?;
+        buffer.data_mut()[0] = Kind::RouterSolicitation.into();
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Link-layer address of the sender.
+    pub fn source_link_layer_address(mut self, value: MacAddress) -> Result<Self> {
+        ndp::builder::link_layer_address(&mut self.buffer, option::Kind::SourceLinkLayerAddress, value)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::icmpv6;
+    use crate::icmpv6::ndp::option;
+    use crate::ip;
+    use crate::packet::Packet;
+
+    #[test]
+    fn simple() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(255)
+            .unwrap()
+            .source("fe80::1".parse().unwrap())
+            .unwrap()
+            .destination("ff02::2".parse().unwrap())
+            .unwrap()
+            .icmp()
+            .unwrap()
+            .router_solicitation()
+            .unwrap()
+            .source_link_layer_address("00:11:22:33:44:55".parse().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+        assert_eq!(ip.payload_length(), 16);
+
+        let icmp = icmpv6::Packet::new(ip.payload()).unwrap();
+        assert!(icmp.is_valid(&ip));
+
+        let solicitation = icmp.router_solicitation().unwrap();
+        let options = solicitation.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].kind(), option::Kind::SourceLinkLayerAddress);
+        assert_eq!(options[0].link_layer_address().unwrap().to_hex_string(), "00:11:22:33:44:55");
+        assert!(solicitation.payload().len() == 8);
+    }
+}
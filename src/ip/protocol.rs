@@ -1,5 +1,5 @@
 /// Protocols supported by IP packets.
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
 pub enum Protocol {
     ///
     Hopopt,
@@ -6,6 +6,7 @@ use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::error::*;
 use crate::ip::v4::checksum;
+use crate::ip::v4::option::{self, TimestampFlag};
 use crate::ip::v4::Flags;
 use crate::ip::v4::Packet;
 use crate::ip::Protocol;
@@ -48,7 +49,10 @@ impl<B: Buffer> Build<B> for Builder<B> {
     }
 
     fn build(mut self) -> Result<B::Inner> {
-        self.prepare();
+        // The payload already prepared the header.
+        if !self.payload {
+            self.prepare()?;
+        }
 
         let mut buffer = self.buffer.into_inner();
         self.finalizer.finalize(buffer.as_mut())?;
@@ -83,7 +87,7 @@ macro_rules! protocol {
 			}
 
 			self = self.protocol(Protocol::$protocol)?;
-			self.prepare();
+			self.prepare()?;
 
 			let mut builder = crate::$module::Builder::with(self.buffer)?;
 			builder.finalizer().extend(self.finalizer);
@@ -148,6 +152,104 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
+    /// Add an option, the header is padded with End of Options List octets
+    /// once it's complete.
+    pub fn option(mut self, copied: bool, class: option::Class, number: option::Number, value: &[u8]) -> Result<Self> {
+        let class: u8 = class.into();
+        let number: u8 = number.into();
+
+        if class > 0b11 || number > 0b1_1111 || value.len() > 38 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let kind = (copied as u8) << 7 | class << 5 | number;
+        let start = self.extend(2 + value.len())?;
+
+        let data = &mut self.buffer.data_mut()[start..];
+        data[0] = kind;
+        data[1] = (2 + value.len()) as u8;
+        data[2..].copy_from_slice(value);
+
+        Ok(self)
+    }
+
+    /// Add a No Operation option, used to align the following options.
+    pub fn no_operation(mut self) -> Result<Self> {
+        let start = self.extend(1)?;
+        self.buffer.data_mut()[start] = option::Number::NoOperation.into();
+
+        Ok(self)
+    }
+
+    /// Add an End of Options List option.
+    pub fn end(mut self) -> Result<Self> {
+        let start = self.extend(1)?;
+        self.buffer.data_mut()[start] = option::Number::End.into();
+
+        Ok(self)
+    }
+
+    /// Add a Record Route option with room for the given number of addresses.
+    pub fn record_route(self, slots: usize) -> Result<Self> {
+        self.route(false, option::Number::RecordRoute, &vec![0; slots * 4])
+    }
+
+    /// Add a Loose Source and Record Route option with the given addresses.
+    pub fn loose_source_route<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, addresses: T) -> Result<Self> {
+        let addresses = addresses.into_iter().flat_map(|a| a.octets().to_vec()).collect::<Vec<_>>();
+        self.route(true, option::Number::LooseSourceRoute, &addresses)
+    }
+
+    /// Add a Strict Source and Record Route option with the given addresses.
+    pub fn strict_source_route<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, addresses: T) -> Result<Self> {
+        let addresses = addresses.into_iter().flat_map(|a| a.octets().to_vec()).collect::<Vec<_>>();
+        self.route(true, option::Number::StrictSourceRoute, &addresses)
+    }
+
+    /// Add a Timestamp option with room for the given number of entries.
+    ///
+    /// # Note
+    ///
+    /// Prespecified addresses are added with `timestamp_prespecified()`.
+    pub fn timestamp(self, flag: TimestampFlag, slots: usize) -> Result<Self> {
+        let size = match flag {
+            TimestampFlag::Timestamps => 4,
+            TimestampFlag::Addresses => 8,
+            _ => return Err(ErrorKind::InvalidValue.into()),
+        };
+
+        self.timestamps(flag, &vec![0; slots * size])
+    }
+
+    /// Add a Timestamp option where only the given addresses register a
+    /// timestamp.
+    pub fn timestamp_prespecified<'a, T: IntoIterator<Item = &'a Ipv4Addr>>(self, addresses: T) -> Result<Self> {
+        let entries = addresses.into_iter().flat_map(|a| [a.octets(), [0; 4]].concat()).collect::<Vec<_>>();
+        self.timestamps(TimestampFlag::Prespecified, &entries)
+    }
+
+    /// Add a Router Alert option.
+    pub fn router_alert(self, value: u16) -> Result<Self> {
+        self.option(true, option::Class::Control, option::Number::RouterAlert, &value.to_be_bytes())
+    }
+
+    /// Add a Basic Security option, as defined in RFC 1108.
+    pub fn security(self, classification: u8, authority: &[u8]) -> Result<Self> {
+        if authority.is_empty() {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let value = [&[classification][..], authority].concat();
+        self.option(true, option::Class::Control, option::Number::Security, &value)
+    }
+
+    /// Add a Commercial IP Security Option, with the given domain of
+    /// interpretation and already encoded tags.
+    pub fn cipso(self, doi: u32, tags: &[u8]) -> Result<Self> {
+        let value = [&doi.to_be_bytes()[..], tags].concat();
+        self.option(true, option::Class::Control, option::Number::CommercialSecurity, &value)
+    }
+
     /// Payload for the packet.
     pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
         if self.payload {
@@ -156,6 +258,11 @@ impl<B: Buffer> Builder<B> {
 
         self.payload = true;
 
+        // Close the header before the payload, so its length isn't mistaken for
+        // part of the header.
+        self.prepare()?;
+        self.buffer.next(0)?;
+
         for byte in value {
             self.buffer.more(1)?;
             *self.buffer.data_mut().last_mut().unwrap() = *byte;
@@ -164,7 +271,49 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
-    fn prepare(&mut self) {
+    fn route(self, copied: bool, number: option::Number, addresses: &[u8]) -> Result<Self> {
+        // The pointer starts at the first address.
+        let value = [&[4][..], addresses].concat();
+        self.option(copied, option::Class::Control, number, &value)
+    }
+
+    fn timestamps(self, flag: TimestampFlag, entries: &[u8]) -> Result<Self> {
+        let flag: u8 = flag.into();
+
+        // The pointer starts at the first entry, with no overflow.
+        let value = [&[5, flag][..], entries].concat();
+        self.option(false, option::Class::Debugging, option::Number::TimeStamp, &value)
+    }
+
+    /// Grow the header for an option, returning where it starts.
+    fn extend(&mut self, size: usize) -> Result<usize> {
+        use crate::size::header::Max;
+
+        if self.payload {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let start = self.buffer.length();
+
+        if start + size > Packet::<()>::max() {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.buffer.more(size)?;
+        self.options = true;
+
+        Ok(start)
+    }
+
+    fn prepare(&mut self) -> Result<()> {
+        // Pad the options with End of Options List to a multiple of 4 octets.
+        if self.options {
+            while self.buffer.length() % 4 != 0 {
+                self.buffer.more(1)?;
+                *self.buffer.data_mut().last_mut().unwrap() = option::Number::End.into();
+            }
+        }
+
         let offset = self.buffer.offset();
         let length = self.buffer.length();
 
@@ -183,6 +332,8 @@ impl<B: Buffer> Builder<B> {
 
             Ok(())
         });
+
+        Ok(())
     }
 
     protocol!(/// Build an ICMP packet.
@@ -193,13 +344,18 @@ impl<B: Buffer> Builder<B> {
 
     protocol!(/// Build a UDP packet.
 		fn udp(Udp));
+
+    protocol!(/// Build a UDP-Lite packet.
+		fn udplite(UdpLite));
 }
 
 #[cfg(test)]
 mod test {
     use crate::builder::Builder;
     use crate::ip;
+    use crate::packet::Packet;
     use crate::tcp;
+    use crate::udp;
     use std::net::Ipv4Addr;
 
     #[test]
@@ -272,4 +428,76 @@ mod test {
         assert_eq!(packet.destination(), "192.168.0.79".parse::<Ipv4Addr>().unwrap());
         assert!(packet.is_valid());
     }
+
+    #[test]
+    fn options() {
+        let packet = ip::v4::Builder::default()
+            .ttl(64)
+            .unwrap()
+            .source("10.0.0.1".parse().unwrap())
+            .unwrap()
+            .destination("10.0.0.2".parse().unwrap())
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .no_operation()
+            .unwrap()
+            .loose_source_route(&["10.0.1.1".parse().unwrap(), "10.0.2.1".parse().unwrap()])
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(53)
+            .unwrap()
+            .destination(1053)
+            .unwrap()
+            .payload(b"test")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::v4::Packet::new(packet).unwrap();
+        assert!(packet.is_valid());
+        assert_eq!(packet.header(), 9);
+        assert_eq!(packet.length(), 36 + 8 + 4);
+        assert_eq!(&packet.as_ref()[20..36], &[0x94, 0x04, 0x00, 0x00, 0x01, 0x83, 0x0b, 0x04, 10, 0, 1, 1, 10, 0, 2, 1][..]);
+
+        let options = packet.options().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].number(), ip::v4::option::Number::RouterAlert);
+        assert_eq!(options[1].number(), ip::v4::option::Number::NoOperation);
+        assert_eq!(options[2].number(), ip::v4::option::Number::LooseSourceRoute);
+
+        let udp = udp::Packet::new(packet.payload()).unwrap();
+        assert!(udp.is_valid(&ip::Packet::from(&packet)));
+        assert_eq!(udp.payload(), b"test");
+    }
+
+    #[test]
+    fn options_padding() {
+        let packet = ip::v4::Builder::default()
+            .record_route(2)
+            .unwrap()
+            .timestamp(ip::v4::option::TimestampFlag::Timestamps, 1)
+            .unwrap()
+            .protocol(ip::Protocol::Udp)
+            .unwrap()
+            .payload(&[0xff; 8])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::v4::Packet::new(packet).unwrap();
+        assert!(packet.is_valid());
+        assert_eq!(packet.header(), 10);
+        assert_eq!(&packet.as_ref()[20..40], &[0x07, 0x0b, 0x04, 0, 0, 0, 0, 0, 0, 0, 0, 0x44, 0x08, 0x05, 0x00, 0, 0, 0, 0, 0x00][..]);
+        assert_eq!(packet.payload(), &[0xff; 8]);
+    }
+
+    #[test]
+    fn options_limit() {
+        assert!(ip::v4::Builder::default().record_route(9).is_ok());
+        assert!(ip::v4::Builder::default().record_route(10).is_err());
+        assert!(ip::v4::Builder::default().record_route(9).unwrap().no_operation().unwrap().no_operation().is_err());
+        assert!(ip::v4::Builder::default().payload(b"x").unwrap().no_operation().is_err());
+    }
 }
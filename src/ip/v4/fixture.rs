@@ -0,0 +1,33 @@
+use crate::builder::Builder;
+use crate::ip;
+use crate::ip::v4::flag;
+
+/// Build a UDP datagram from 10.0.0.1 to 10.0.0.2 with the given id.
+pub fn packet(id: u16, payload: &[u8]) -> Vec<u8> {
+    ip::v4::Builder::default()
+        .id(id)
+        .unwrap()
+        .ttl(64)
+        .unwrap()
+        .source("10.0.0.1".parse().unwrap())
+        .unwrap()
+        .destination("10.0.0.2".parse().unwrap())
+        .unwrap()
+        .protocol(ip::Protocol::Udp)
+        .unwrap()
+        .payload(payload)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Build a fragment of the datagram with the given id at the given offset,
+/// in units of 8 octets.
+pub fn fragment(id: u16, offset: u16, more: bool, payload: &[u8]) -> ip::v4::Packet<Vec<u8>> {
+    let mut packet = ip::v4::Packet::unchecked(packet(id, payload));
+    packet.set_offset(offset).unwrap();
+    packet.set_flags(if more { flag::MORE_FRAGMENTS } else { flag::Flags::empty() }).unwrap();
+    packet.update_checksum().unwrap();
+
+    packet
+}
@@ -0,0 +1,264 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::ip::v4::option;
+use crate::ip::v4::{flag, Packet};
+use crate::packet::Packet as P;
+
+/// Iterator over the fragments of an IPv4 packet.
+///
+/// # Note
+///
+/// A packet that already fits in the MTU is yielded untouched as the only
+/// fragment, a packet that is itself a fragment is split further keeping its
+/// offset and More Fragments flag.
+#[derive(Debug)]
+pub struct Fragments<'a> {
+    first: Vec<u8>,
+    rest: Vec<u8>,
+    payload: &'a [u8],
+    mtu: usize,
+
+    position: usize,
+    done: bool,
+}
+
+impl<'a> Fragments<'a> {
+    /// Prepare the fragments for a packet, failing if the packet has the Don't
+    /// Fragment flag but does not fit, if the MTU cannot hold any data, or if
+    /// the fragment offsets would not fit.
+    pub fn new<B: AsRef<[u8]>>(packet: &'a Packet<B>, mtu: usize) -> Result<Fragments<'a>> {
+        let (header, payload) = packet.split();
+        let first = header.to_vec();
+        let rest = copied(header)?;
+
+        if header.len() + payload.len() > mtu {
+            if packet.flags().contains(flag::DONT_FRAGMENT) {
+                return Err(ErrorKind::InvalidPacket.into());
+            }
+
+            if mtu < first.len() + 8 || mtu < rest.len() + 8 {
+                return Err(ErrorKind::InvalidValue.into());
+            }
+
+            // The offset of the last fragment has to fit in 13 bits.
+            let head = (mtu - first.len()) / 8 * 8;
+            let available = mtu - rest.len();
+            let step = available / 8 * 8;
+            let remaining = payload.len() - head;
+            let last = if remaining <= available { head } else { head + ((remaining - available - 1) / step + 1) * step };
+
+            if usize::from(packet.offset()) + last / 8 > 0x1fff {
+                return Err(ErrorKind::InvalidPacket.into());
+            }
+        }
+
+        Ok(Fragments {
+            first,
+            rest,
+            payload,
+            mtu,
+
+            position: 0,
+            done: false,
+        })
+    }
+}
+
+impl<'a> Iterator for Fragments<'a> {
+    type Item = Packet<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let header = if self.position == 0 { &self.first } else { &self.rest };
+        let remaining = self.payload.len() - self.position;
+        let available = self.mtu.saturating_sub(header.len());
+
+        let original = Packet::unchecked(&self.first[..]);
+        let (size, mut flags) = if remaining <= available {
+            self.done = true;
+            (remaining, original.flags())
+        } else {
+            (available / 8 * 8, original.flags() | flag::MORE_FRAGMENTS)
+        };
+
+        if !self.done {
+            flags.remove(flag::DONT_FRAGMENT);
+        }
+
+        // The offset is only needed for the fragments after the first.
+        let offset = original.offset() + (self.position / 8) as u16;
+
+        let mut buffer = Vec::with_capacity(header.len() + size);
+        buffer.extend_from_slice(header);
+        buffer.extend_from_slice(&self.payload[self.position..self.position + size]);
+        self.position += size;
+
+        let length = buffer.len() as u16;
+        Cursor::new(&mut buffer[2..]).write_u16::<BigEndian>(length).unwrap();
+
+        let mut packet = Packet::unchecked(buffer);
+        packet.set_offset(offset).unwrap();
+        packet.set_flags(flags).unwrap();
+        packet.update_checksum().unwrap();
+
+        Some(packet)
+    }
+}
+
+/// Build the header for the fragments after the first, which only carry the
+/// options with the copied flag set.
+fn copied(header: &[u8]) -> Result<Vec<u8>> {
+    use crate::size::Size;
+
+    let mut result = header[..20].to_vec();
+    let mut options = &header[20..];
+
+    while !options.is_empty() {
+        let option = option::Option::new(options).map_err(|_| Error::from(ErrorKind::InvalidPacket))?;
+
+        if option.number() == option::Number::End {
+            break;
+        }
+
+        if option.is_copied() {
+            result.extend_from_slice(&options[..option.size()]);
+        }
+
+        options = &options[option.size()..];
+    }
+
+    // Pad with End of Options List to a multiple of 4 octets.
+    while result.len() % 4 != 0 {
+        result.push(0);
+    }
+
+    result[0] = (4 << 4) | (result.len() / 4) as u8;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::ip;
+    use crate::ip::v4::fixture::packet;
+    use crate::ip::v4::flag;
+    use crate::packet::Packet;
+
+    #[test]
+    fn fits() {
+        let raw = packet(0x1234, &[0xaa; 100]);
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+
+        let fragments = packet.fragments(1500).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].as_ref(), &raw[..]);
+    }
+
+    #[test]
+    fn split() {
+        let payload = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let raw = packet(0x1234, &payload);
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+
+        let fragments = packet.fragments(300).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.len(), 4);
+
+        let mut data = Vec::new();
+
+        for (i, fragment) in fragments.iter().enumerate() {
+            let fragment = ip::v4::Packet::new(fragment.as_ref()).unwrap();
+
+            assert!(fragment.is_valid());
+            assert!(fragment.length() <= 300);
+            assert_eq!(fragment.id(), 0x1234);
+            assert_eq!(fragment.offset() as usize * 8, data.len());
+            assert_eq!(fragment.flags().contains(flag::MORE_FRAGMENTS), i != 3);
+
+            data.extend_from_slice(fragment.payload());
+        }
+
+        assert_eq!(fragments[0].payload().len(), 280);
+        assert_eq!(data, payload);
+    }
+
+    #[test]
+    fn options() {
+        // Security (copied), Record Route (not copied) and End of Options List.
+        let mut raw = vec![
+            0x48, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x40, 0x11, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00, 0x00, 0x02, 0x82, 0x04, 0xab, 0xcd, 0x07, 0x07,
+            0x04, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        raw.extend_from_slice(&[0x55; 64]);
+
+        let length = raw.len() as u16;
+        raw[2..4].copy_from_slice(&length.to_be_bytes());
+
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+        let fragments = packet.fragments(32 + 24).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.len(), 3);
+
+        assert_eq!(fragments[0].header(), 8);
+        assert_eq!(fragments[0].options().count(), 2);
+
+        for fragment in &fragments[1..] {
+            assert!(fragment.is_valid());
+            assert_eq!(fragment.header(), 6);
+
+            let options = fragment.options().collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(options.len(), 1);
+            assert_eq!(options[0].number(), ip::v4::option::Number::Security);
+        }
+
+        assert_eq!(fragments.iter().map(|f| f.payload().len()).sum::<usize>(), 64);
+    }
+
+    #[test]
+    fn dont_fragment() {
+        let raw = ip::v4::Builder::default()
+            .flags(flag::DONT_FRAGMENT)
+            .unwrap()
+            .protocol(ip::Protocol::Udp)
+            .unwrap()
+            .payload(&[0u8; 100][..])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+        assert_eq!(packet.flags(), flag::DONT_FRAGMENT);
+        assert!(packet.fragments(100).is_err());
+        assert!(packet.fragments(120).is_ok());
+    }
+
+    #[test]
+    fn small_mtu() {
+        let raw = packet(0x1234, &[0u8; 100]);
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+
+        assert!(packet.fragments(27).is_err());
+        assert_eq!(packet.fragments(28).unwrap().count(), 13);
+    }
+
+    #[test]
+    fn offset_overflow() {
+        // The fragments of 1000 octets at an MTU of 300 start at 0, 280, 560 and 840.
+        let mut raw = packet(0x1234, &[0u8; 1000]);
+        ip::v4::Packet::unchecked(&mut raw[..]).set_offset(0x1fff - 105).unwrap();
+
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+        let fragments = packet.fragments(300).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.last().unwrap().offset(), 0x1fff);
+
+        ip::v4::Packet::unchecked(&mut raw[..]).set_offset(0x1fff - 104).unwrap();
+
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+        assert!(packet.fragments(300).is_err());
+        assert!(packet.fragments(1020).is_ok());
+    }
+}
@@ -12,6 +12,17 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+/// IPv4 fragmentation.
+pub mod fragment;
+pub use self::fragment::Fragments;
+
+/// IPv4 reassembly.
+pub mod reassembly;
+pub use self::reassembly::Reassembler;
+
+#[cfg(test)]
+pub(crate) mod fixture;
+
 /// Calculate the checksum for an IPv4 packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
     use byteorder::{BigEndian, ReadBytesExt};
@@ -0,0 +1,240 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+
+/// Commercial IP Security Option parser, over the option payload.
+pub struct Cipso<B> {
+    buffer: B,
+}
+
+/// Type of a CIPSO tag.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+    /// Sensitivity level and bitmap of categories.
+    RestrictedBitmap,
+
+    /// Sensitivity level and list of categories.
+    Enumerated,
+
+    /// Sensitivity level and ranges of categories.
+    Ranged,
+
+    /// Sensitivity level and bitmap of categories, not to be enforced.
+    PermissiveBitmap,
+
+    /// Free form data.
+    FreeForm,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Cipso<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::option::Cipso")
+            .field("doi", &self.doi())
+            .field("tags", &self.tags().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Cipso<B> {
+    /// Parse a CIPSO option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Cipso<B>> {
+        let option = Cipso { buffer };
+
+        if option.buffer.as_ref().len() < 4 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Cipso<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Cipso<B> {
+    /// Domain of interpretation.
+    pub fn doi(&self) -> u32 {
+        (&self.buffer.as_ref()[0..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Tags in the option.
+    pub fn tags(&self) -> TagIter {
+        TagIter { buffer: &self.buffer.as_ref()[4..] }
+    }
+}
+
+/// CIPSO tag parser.
+pub struct Tag<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Tag<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::option::cipso::Tag")
+            .field("kind", &self.kind())
+            .field("level", &self.level().ok())
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+    /// Parse a CIPSO tag, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Tag<B>> {
+        let tag = Tag { buffer };
+
+        if tag.buffer.as_ref().len() < 2 || tag.buffer.as_ref().len() < tag.length() as usize {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if tag.length() < 2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        match tag.kind() {
+            Kind::RestrictedBitmap | Kind::Enumerated | Kind::Ranged | Kind::PermissiveBitmap if tag.length() < 4 => Err(ErrorKind::InvalidPacket.into()),
+
+            _ => Ok(tag),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Tag<B> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer.as_ref()[..self.length() as usize]
+    }
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+    /// Tag type.
+    pub fn kind(&self) -> Kind {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Tag length, including type and length.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Tag data after type and length.
+    pub fn data(&self) -> &[u8] {
+        &self.buffer.as_ref()[2..self.length() as usize]
+    }
+
+    /// Sensitivity level.
+    pub fn level(&self) -> Result<u8> {
+        match self.kind() {
+            Kind::RestrictedBitmap | Kind::Enumerated | Kind::Ranged | Kind::PermissiveBitmap => Ok(self.data()[1]),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+
+    /// Categories of a bitmap or enumerated tag.
+    pub fn categories(&self) -> Result<Vec<u16>> {
+        let data = &self.data()[2..];
+
+        match self.kind() {
+            Kind::RestrictedBitmap | Kind::PermissiveBitmap => Ok(data
+                .iter()
+                .enumerate()
+                .flat_map(|(i, byte)| (0..8).filter(move |bit| byte & (0x80 >> bit) != 0).map(move |bit| (i * 8 + bit) as u16))
+                .collect()),
+
+            Kind::Enumerated => Ok(data.chunks(2).filter(|c| c.len() == 2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect()),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+
+    /// Ranges of categories of a ranged tag, as pairs of high and low end.
+    pub fn ranges(&self) -> Result<Vec<(u16, u16)>> {
+        if self.kind() != Kind::Ranged {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let data = &self.data()[2..];
+        let mut result = Vec::new();
+
+        for i in (0..data.len()).step_by(4) {
+            let high = match data.get(i..i + 2) {
+                Some(high) => u16::from_be_bytes([high[0], high[1]]),
+                None => return Err(ErrorKind::InvalidPacket.into()),
+            };
+
+            // The low end can be omitted for the last range, meaning zero.
+            let low = match data.get(i + 2..i + 4) {
+                Some(low) => u16::from_be_bytes([low[0], low[1]]),
+                None => 0,
+            };
+
+            result.push((high, low));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Iterator over the tags of a CIPSO option.
+pub struct TagIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for TagIter<'a> {
+    type Item = Result<Tag<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        match Tag::new(self.buffer) {
+            Ok(tag) => {
+                self.buffer = &self.buffer[tag.length() as usize..];
+                Some(Ok(tag))
+            }
+
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl From<u8> for Kind {
+    fn from(value: u8) -> Self {
+        use self::Kind::*;
+
+        match value {
+            1 => RestrictedBitmap,
+            2 => Enumerated,
+            5 => Ranged,
+            6 => PermissiveBitmap,
+            7 => FreeForm,
+            n => Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Kind {
+    fn into(self) -> u8 {
+        use self::Kind::*;
+
+        match self {
+            RestrictedBitmap => 1,
+            Enumerated => 2,
+            Ranged => 5,
+            PermissiveBitmap => 6,
+            FreeForm => 7,
+            Unknown(n) => n,
+        }
+    }
+}
@@ -0,0 +1,569 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+mod route;
+pub use self::route::Route;
+
+mod timestamp;
+pub use self::timestamp::Timestamp;
+
+/// Basic Security option parser.
+pub mod security;
+pub use self::security::Security;
+
+/// Commercial IP Security Option parser.
+pub mod cipso;
+pub use self::cipso::Cipso;
+
+/// IPv4 Option parser.
+pub struct Option<B> {
+    buffer: B,
+}
+
+sized!(Option,
+	header {
+		min: 1,
+		max: 2,
+		size: p => match p.length() {
+			1 => 1,
+			_ => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  32,
+		size: p => match p.length() {
+			1 => 0,
+			n => n as usize - 2,
+		},
+	});
+
+/// IPv4 Option class.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Class {
+    ///
+    Control,
+
+    ///
+    Debugging,
+
+    ///
+    Reserved(u8),
+}
+
+/// IPv4 Option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+    ///
+    End,
+
+    ///
+    NoOperation,
+
+    ///
+    Security,
+
+    ///
+    LooseSourceRoute,
+
+    ///
+    TimeStamp,
+
+    ///
+    ExtendedSecurity,
+
+    ///
+    CommercialSecurity,
+
+    ///
+    RecordRoute,
+
+    ///
+    StreamId,
+
+    ///
+    StrictSourceRoute,
+
+    ///
+    ExperimentalMeasurement,
+
+    ///
+    MtuProbe,
+
+    ///
+    MtuReply,
+
+    ///
+    ExperimentalFlowControl,
+
+    ///
+    ExperimentalAccessControl,
+
+    ///
+    ImiTrafficDescriptor,
+
+    ///
+    ExtendedInternetProtocol,
+
+    ///
+    TraceRoute,
+
+    ///
+    AddressExtension,
+
+    ///
+    RouterAlert,
+
+    ///
+    SelectiveDirectedBroadcast,
+
+    ///
+    DynamicPacketState,
+
+    ///
+    UpstreamMulticastPacket,
+
+    ///
+    QuickStart,
+
+    ///
+    Unknown(u8),
+}
+
+/// Content of a Timestamp option.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimestampFlag {
+    /// Timestamps only.
+    Timestamps,
+
+    /// Each timestamp is preceded by the address of the registering entity.
+    Addresses,
+
+    /// The addresses are prespecified, and only those entities register a
+    /// timestamp.
+    Prespecified,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::Option")
+            .field("is_copied", &self.is_copied())
+            .field("class", &self.class())
+            .field("number", &self.number())
+            .field("length", &self.length())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Parse an IPv4 option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Option<B>> {
+        use crate::size::header::Min;
+
+        let option = Option { buffer: buffer };
+
+        if option.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        // Only End of Options List and No Operation are a single octet.
+        match option.number() {
+            Number::End | Number::NoOperation => (),
+
+            _ if option.buffer.as_ref().len() < 2 => return Err(ErrorKind::SmallBuffer.into()),
+
+            _ if option.length() < 2 => return Err(ErrorKind::InvalidPacket.into()),
+
+            _ => (),
+        }
+
+        if option.buffer.as_ref().len() < option.length() as usize {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Option<&[u8]>> {
+        Option::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+        Option::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        match self.length() {
+            1 => self.buffer.as_ref().split_at(1),
+
+            length => self.buffer.as_ref()[..length as usize].split_at(2),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        match self.length() {
+            1 => self.buffer.as_mut().split_at_mut(1),
+
+            length => self.buffer.as_mut()[..length as usize].split_at_mut(2),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Whether the option has to be copied in fragments.
+    pub fn is_copied(&self) -> bool {
+        self.buffer.as_ref()[0] >> 7 == 1
+    }
+
+    /// Option class.
+    pub fn class(&self) -> Class {
+        match (self.buffer.as_ref()[0] >> 5) & 0b011 {
+            0 => Class::Control,
+            2 => Class::Debugging,
+            v => Class::Reserved(v),
+        }
+    }
+
+    /// Option number.
+    pub fn number(&self) -> Number {
+        (self.buffer.as_ref()[0] & 0b11111).into()
+    }
+
+    /// Packet length
+    pub fn length(&self) -> u8 {
+        match self.number() {
+            Number::End | Number::NoOperation => 1,
+
+            _ => self.buffer.as_ref()[1],
+        }
+    }
+
+    /// Parse a Record Route, Loose Source Route or Strict Source Route option.
+    pub fn route(&self) -> Result<Route<&[u8]>> {
+        match self.number() {
+            Number::RecordRoute | Number::LooseSourceRoute | Number::StrictSourceRoute => Route::new(self.payload()),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+
+    /// Parse a Timestamp option.
+    pub fn timestamp(&self) -> Result<Timestamp<&[u8]>> {
+        if self.number() != Number::TimeStamp {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Timestamp::new(self.payload())
+    }
+
+    /// Value of a Router Alert option.
+    pub fn router_alert(&self) -> Result<u16> {
+        if self.number() != Number::RouterAlert {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(self.payload().read_u16::<BigEndian>()?)
+    }
+
+    /// Parse a Basic Security option.
+    pub fn security(&self) -> Result<Security<&[u8]>> {
+        if self.number() != Number::Security {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Security::new(self.payload())
+    }
+
+    /// Parse a Commercial IP Security Option.
+    pub fn cipso(&self) -> Result<Cipso<&[u8]>> {
+        if self.number() != Number::CommercialSecurity {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Cipso::new(self.payload())
+    }
+}
+
+impl From<u8> for Class {
+    fn from(value: u8) -> Self {
+        use self::Class::*;
+
+        match value {
+            0 => Control,
+            1 => Reserved(1),
+            2 => Debugging,
+            3 => Reserved(3),
+            _ => panic!("invalid IPv4 option class"),
+        }
+    }
+}
+
+impl Into<u8> for Class {
+    fn into(self) -> u8 {
+        match self {
+            Class::Control => 0,
+            Class::Debugging => 2,
+            Class::Reserved(n) => n,
+        }
+    }
+}
+
+impl From<u8> for Number {
+    fn from(value: u8) -> Self {
+        use self::Number::*;
+
+        match value {
+            0 => End,
+            1 => NoOperation,
+            2 => Security,
+            3 => LooseSourceRoute,
+            4 => TimeStamp,
+            5 => ExtendedSecurity,
+            6 => CommercialSecurity,
+            7 => RecordRoute,
+            8 => StreamId,
+            9 => StrictSourceRoute,
+            10 => ExperimentalMeasurement,
+            11 => MtuProbe,
+            12 => MtuReply,
+            13 => ExperimentalFlowControl,
+            14 => ExperimentalAccessControl,
+            16 => ImiTrafficDescriptor,
+            17 => ExtendedInternetProtocol,
+            18 => TraceRoute,
+            19 => AddressExtension,
+            20 => RouterAlert,
+            21 => SelectiveDirectedBroadcast,
+            23 => DynamicPacketState,
+            24 => UpstreamMulticastPacket,
+            25 => QuickStart,
+            n => Number::Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Number {
+    fn into(self) -> u8 {
+        use self::Number::*;
+
+        match self {
+            End => 0,
+            NoOperation => 1,
+            Security => 2,
+            LooseSourceRoute => 3,
+            TimeStamp => 4,
+            ExtendedSecurity => 5,
+            CommercialSecurity => 6,
+            RecordRoute => 7,
+            StreamId => 8,
+            StrictSourceRoute => 9,
+            ExperimentalMeasurement => 10,
+            MtuProbe => 11,
+            MtuReply => 12,
+            ExperimentalFlowControl => 13,
+            ExperimentalAccessControl => 14,
+            ImiTrafficDescriptor => 16,
+            ExtendedInternetProtocol => 17,
+            TraceRoute => 18,
+            AddressExtension => 19,
+            RouterAlert => 20,
+            SelectiveDirectedBroadcast => 21,
+            DynamicPacketState => 23,
+            UpstreamMulticastPacket => 24,
+            QuickStart => 25,
+            Number::Unknown(n) => n,
+        }
+    }
+}
+
+impl From<u8> for TimestampFlag {
+    fn from(value: u8) -> Self {
+        use self::TimestampFlag::*;
+
+        match value {
+            0 => Timestamps,
+            1 => Addresses,
+            3 => Prespecified,
+            n => TimestampFlag::Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for TimestampFlag {
+    fn into(self) -> u8 {
+        use self::TimestampFlag::*;
+
+        match self {
+            Timestamps => 0,
+            Addresses => 1,
+            Prespecified => 3,
+            TimestampFlag::Unknown(n) => n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::ip;
+    use crate::ip::v4::option::{cipso, security, Number, Option, TimestampFlag};
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn route() {
+        let hops = ["10.0.1.1".parse::<Ipv4Addr>().unwrap(), "10.0.2.1".parse().unwrap(), "10.0.3.1".parse().unwrap()];
+
+        // Loose source route with the first hop already visited.
+        let raw = [0x83, 0x0f, 0x08, 10, 0, 1, 1, 10, 0, 2, 1, 10, 0, 3, 1];
+        let option = Option::new(&raw[..]).unwrap();
+        let route = option.route().unwrap();
+
+        assert_eq!(route.pointer(), 8);
+        assert!(!route.is_full());
+        assert_eq!(route.addresses().collect::<Vec<_>>(), &hops[..]);
+        assert_eq!(route.recorded().collect::<Vec<_>>(), &hops[..1]);
+        assert_eq!(route.remaining().collect::<Vec<_>>(), &hops[1..]);
+        assert!(option.timestamp().is_err());
+
+        let packet = ip::v4::Builder::default().record_route(2).unwrap().build().unwrap();
+        let packet = ip::v4::Packet::new(packet).unwrap();
+        let option = packet.options().next().unwrap().unwrap();
+        let route = option.route().unwrap();
+
+        assert_eq!(option.number(), Number::RecordRoute);
+        assert_eq!(route.addresses().count(), 2);
+        assert_eq!(route.recorded().count(), 0);
+        assert_eq!(route.remaining().count(), 2);
+    }
+
+    #[test]
+    fn timestamp() {
+        // Addresses and timestamps, one entry recorded out of two, with an
+        // overflow of 1.
+        let raw = [0x44, 0x14, 0x0d, 0x11, 10, 0, 0, 1, 0x00, 0x00, 0x10, 0x00, 0, 0, 0, 0, 0, 0, 0, 0];
+        let option = Option::new(&raw[..]).unwrap();
+        let timestamp = option.timestamp().unwrap();
+
+        assert_eq!(timestamp.pointer(), 13);
+        assert_eq!(timestamp.overflow(), 1);
+        assert_eq!(timestamp.flag(), TimestampFlag::Addresses);
+        assert_eq!(timestamp.entries().count(), 2);
+        assert_eq!(timestamp.recorded().collect::<Vec<_>>(), vec![(Some("10.0.0.1".parse().unwrap()), 0x1000)]);
+
+        let packet = ip::v4::Builder::default().timestamp(TimestampFlag::Timestamps, 3).unwrap().build().unwrap();
+        let packet = ip::v4::Packet::new(packet).unwrap();
+        let option = packet.options().next().unwrap().unwrap();
+        let timestamp = option.timestamp().unwrap();
+
+        assert_eq!(timestamp.flag(), TimestampFlag::Timestamps);
+        assert_eq!(timestamp.entries().collect::<Vec<_>>(), vec![(None, 0); 3]);
+        assert_eq!(timestamp.recorded().count(), 0);
+
+        let packet = ip::v4::Builder::default().timestamp_prespecified(&["10.0.0.1".parse().unwrap()]).unwrap().build().unwrap();
+        let packet = ip::v4::Packet::new(packet).unwrap();
+        let option = packet.options().next().unwrap().unwrap();
+        let timestamp = option.timestamp().unwrap();
+
+        assert_eq!(timestamp.flag(), TimestampFlag::Prespecified);
+        assert_eq!(timestamp.entries().collect::<Vec<_>>(), vec![(Some("10.0.0.1".parse().unwrap()), 0)]);
+
+        // Entries must match the flag.
+        assert!(Option::new(&[0x44, 0x07, 0x05, 0x01, 0, 0, 0][..]).unwrap().timestamp().is_err());
+    }
+
+    #[test]
+    fn router_alert() {
+        let option = Option::new(&[0x94, 0x04, 0x00, 0x00][..]).unwrap();
+        assert_eq!(option.router_alert().unwrap(), 0);
+        assert!(Option::new(&[0x01][..]).unwrap().router_alert().is_err());
+    }
+
+    #[test]
+    fn security() {
+        let option = Option::new(&[0x82, 0x04, 0x5a, 0x50][..]).unwrap();
+        let security = option.security().unwrap();
+
+        assert_eq!(security.classification(), security::Classification::Secret);
+        assert_eq!(security.authority(), security::Authority::SIOP_ESI | security::Authority::NSA);
+        assert_eq!(security.authorities(), &[0x50]);
+    }
+
+    #[test]
+    fn cipso() {
+        let raw = [
+            0x86, 0x1a, 0x00, 0x00, 0x00, 0x03,
+            // restricted bitmap, level 2, categories 0, 9 and 15
+            0x01, 0x06, 0x00, 0x02, 0x80, 0x41,
+            // enumerated, level 1, categories 5 and 300
+            0x02, 0x08, 0x00, 0x01, 0x00, 0x05, 0x01, 0x2c,
+            // ranged, level 3, 10 down to 0
+            0x05, 0x06, 0x00, 0x03, 0x00, 0x0a,
+        ];
+
+        let option = Option::new(&raw[..]).unwrap();
+        let cipso = option.cipso().unwrap();
+        assert_eq!(cipso.doi(), 3);
+
+        let tags = cipso.tags().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tags.len(), 3);
+
+        assert_eq!(tags[0].kind(), cipso::Kind::RestrictedBitmap);
+        assert_eq!(tags[0].level().unwrap(), 2);
+        assert_eq!(tags[0].categories().unwrap(), vec![0, 9, 15]);
+
+        assert_eq!(tags[1].kind(), cipso::Kind::Enumerated);
+        assert_eq!(tags[1].level().unwrap(), 1);
+        assert_eq!(tags[1].categories().unwrap(), vec![5, 300]);
+
+        assert_eq!(tags[2].kind(), cipso::Kind::Ranged);
+        assert_eq!(tags[2].level().unwrap(), 3);
+        assert_eq!(tags[2].ranges().unwrap(), vec![(10, 0)]);
+        assert!(tags[2].categories().is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(Option::new(&[0x07][..]).is_err());
+        assert!(Option::new(&[0x07, 0x00][..]).is_err());
+        assert!(Option::new(&[0x07, 0x01][..]).is_err());
+
+        let raw = [0x46, 0x00, 0x00, 0x18, 0, 0, 0, 0, 0x40, 0x11, 0, 0, 10, 0, 0, 1, 10, 0, 0, 2, 0x07, 0x00, 0x00, 0x00];
+        let packet = ip::v4::Packet::unchecked(&raw[..]);
+        let mut options = packet.options();
+        assert!(options.next().unwrap().is_err());
+        assert!(options.next().is_none());
+    }
+}
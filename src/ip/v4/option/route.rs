@@ -0,0 +1,105 @@
+use std::fmt;
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+
+/// Record Route and Loose/Strict Source Route option parser, over the option
+/// payload.
+pub struct Route<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Route<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::option::Route")
+            .field("pointer", &self.pointer())
+            .field("addresses", &self.addresses().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Route<B> {
+    /// Parse a route option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Route<B>> {
+        let option = Route { buffer };
+
+        if option.buffer.as_ref().is_empty() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if (option.buffer.as_ref().len() - 1) % 4 != 0 || option.pointer() < 4 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Route<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Route<B> {
+    /// Octet of the option, counting from the type, where the next address
+    /// is to be processed.
+    pub fn pointer(&self) -> u8 {
+        self.buffer.as_ref()[0]
+    }
+
+    /// Whether there is no room left for an address.
+    pub fn is_full(&self) -> bool {
+        self.index() >= self.buffer.as_ref().len() - 1
+    }
+
+    /// All the addresses in the option.
+    pub fn addresses(&self) -> AddressIter {
+        AddressIter { buffer: &self.buffer.as_ref()[1..] }
+    }
+
+    /// Addresses already recorded, which for source routes are the hops
+    /// already visited.
+    pub fn recorded(&self) -> AddressIter {
+        let end = self.index().min(self.buffer.as_ref().len() - 1);
+
+        AddressIter {
+            buffer: &self.buffer.as_ref()[1..1 + end],
+        }
+    }
+
+    /// Addresses after the pointer, which for source routes are the hops
+    /// still to visit.
+    pub fn remaining(&self) -> AddressIter {
+        let start = self.index().min(self.buffer.as_ref().len() - 1);
+
+        AddressIter {
+            buffer: &self.buffer.as_ref()[1 + start..],
+        }
+    }
+
+    /// Index of the pointed address from the start of the addresses.
+    fn index(&self) -> usize {
+        (self.pointer() as usize - 4) / 4 * 4
+    }
+}
+
+/// Iterator over the addresses in a route option.
+pub struct AddressIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for AddressIter<'a> {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < 4 {
+            return None;
+        }
+
+        let address = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+        self.buffer = &self.buffer[4..];
+
+        Some(address)
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use crate::error::*;
+
+/// Basic Security option parser, as defined in RFC 1108, over the option
+/// payload.
+pub struct Security<B> {
+    buffer: B,
+}
+
+/// Classification level of a Basic Security option.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Classification {
+    ///
+    TopSecret,
+
+    ///
+    Secret,
+
+    ///
+    Confidential,
+
+    ///
+    Unclassified,
+
+    ///
+    Reserved(u8),
+}
+
+bitflags! {
+    /// Protection authorities of a Basic Security option.
+    pub struct Authority: u8 {
+        ///
+        const GENSER = 0b1000_0000;
+
+        ///
+        const SIOP_ESI = 0b0100_0000;
+
+        ///
+        const SCI = 0b0010_0000;
+
+        ///
+        const NSA = 0b0001_0000;
+
+        ///
+        const DOE = 0b0000_1000;
+    }
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Security<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::option::Security")
+            .field("classification", &self.classification())
+            .field("authority", &self.authority())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Security<B> {
+    /// Parse a Basic Security option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Security<B>> {
+        let option = Security { buffer };
+
+        if option.buffer.as_ref().is_empty() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Security<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Security<B> {
+    /// Classification level.
+    pub fn classification(&self) -> Classification {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Protection authorities from the first authority field octet.
+    pub fn authority(&self) -> Authority {
+        Authority::from_bits_truncate(self.buffer.as_ref().get(1).cloned().unwrap_or(0))
+    }
+
+    /// Raw protection authority field, where the low bit of each octet flags
+    /// whether another one follows.
+    pub fn authorities(&self) -> &[u8] {
+        &self.buffer.as_ref()[1..]
+    }
+}
+
+impl From<u8> for Classification {
+    fn from(value: u8) -> Self {
+        use self::Classification::*;
+
+        match value {
+            0b0011_1101 => TopSecret,
+            0b0101_1010 => Secret,
+            0b1001_0110 => Confidential,
+            0b1010_1011 => Unclassified,
+            n => Reserved(n),
+        }
+    }
+}
+
+impl Into<u8> for Classification {
+    fn into(self) -> u8 {
+        use self::Classification::*;
+
+        match self {
+            TopSecret => 0b0011_1101,
+            Secret => 0b0101_1010,
+            Confidential => 0b1001_0110,
+            Unclassified => 0b1010_1011,
+            Reserved(n) => n,
+        }
+    }
+}
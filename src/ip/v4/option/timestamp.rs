@@ -0,0 +1,141 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::ip::v4::option::TimestampFlag;
+
+/// Timestamp option parser, over the option payload.
+pub struct Timestamp<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Timestamp<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v4::option::Timestamp")
+            .field("pointer", &self.pointer())
+            .field("overflow", &self.overflow())
+            .field("flag", &self.flag())
+            .field("entries", &self.entries().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Timestamp<B> {
+    /// Parse a Timestamp option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Timestamp<B>> {
+        let option = Timestamp { buffer };
+
+        if option.buffer.as_ref().len() < 2 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if option.pointer() < 5 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        match option.flag() {
+            TimestampFlag::Timestamps | TimestampFlag::Addresses | TimestampFlag::Prespecified => (),
+
+            TimestampFlag::Unknown(_) => return Err(ErrorKind::InvalidPacket.into()),
+        }
+
+        if (option.buffer.as_ref().len() - 2) % option.size() != 0 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Timestamp<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Timestamp<B> {
+    /// Octet of the option, counting from the type, where the next entry is to
+    /// be recorded.
+    pub fn pointer(&self) -> u8 {
+        self.buffer.as_ref()[0]
+    }
+
+    /// Number of entities that couldn't register a timestamp for lack of space.
+    pub fn overflow(&self) -> u8 {
+        self.buffer.as_ref()[1] >> 4
+    }
+
+    /// Content of the entries.
+    pub fn flag(&self) -> TimestampFlag {
+        (self.buffer.as_ref()[1] & 0b1111).into()
+    }
+
+    /// Whether there is no room left for an entry.
+    pub fn is_full(&self) -> bool {
+        self.index() >= self.buffer.as_ref().len() - 2
+    }
+
+    /// All the entries in the option, including the ones still to be filled.
+    pub fn entries(&self) -> EntryIter {
+        EntryIter {
+            addresses: self.flag() != TimestampFlag::Timestamps,
+            buffer: &self.buffer.as_ref()[2..],
+        }
+    }
+
+    /// Entries already recorded.
+    pub fn recorded(&self) -> EntryIter {
+        let end = self.index().min(self.buffer.as_ref().len() - 2);
+
+        EntryIter {
+            addresses: self.flag() != TimestampFlag::Timestamps,
+            buffer: &self.buffer.as_ref()[2..2 + end],
+        }
+    }
+
+    /// Size of an entry.
+    fn size(&self) -> usize {
+        if self.flag() == TimestampFlag::Timestamps {
+            4
+        } else {
+            8
+        }
+    }
+
+    /// Index of the pointed entry from the start of the entries.
+    fn index(&self) -> usize {
+        (self.pointer() as usize - 5) / self.size() * self.size()
+    }
+}
+
+/// Iterator over the entries of a Timestamp option, as the optional address
+/// and the timestamp in milliseconds since midnight UT.
+pub struct EntryIter<'a> {
+    addresses: bool,
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for EntryIter<'a> {
+    type Item = (Option<Ipv4Addr>, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let address = if self.addresses {
+            if self.buffer.len() < 8 {
+                return None;
+            }
+
+            let address = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+            self.buffer = &self.buffer[4..];
+
+            Some(address)
+        } else {
+            None
+        };
+
+        let timestamp = (&self.buffer[..]).read_u32::<BigEndian>().ok()?;
+        self.buffer = &self.buffer[4..];
+
+        Some((address, timestamp))
+    }
+}
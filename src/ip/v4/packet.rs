@@ -5,6 +5,7 @@ use std::net::Ipv4Addr;
 
 use crate::error::*;
 use crate::ip::v4::checksum;
+use crate::ip::v4::fragment::Fragments;
 use crate::ip::v4::option;
 use crate::ip::v4::Flags;
 use crate::ip::Protocol;
@@ -57,6 +58,12 @@ impl<B: AsRef<[u8]>> Packet<B> {
     }
 
     /// Parse an IPv4 packet without checking the payload.
+    ///
+    /// # Note
+    ///
+    /// This is meant for packets quoted by ICMP error messages, where only
+    /// the header and the beginning of the payload are included, so the
+    /// payload is usually truncated.
     pub fn no_payload(buffer: B) -> Result<Packet<B>> {
         use crate::size::header::Min;
 
@@ -191,10 +198,10 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
     /// Flags of the packet.
     pub fn flags(&self) -> Flags {
-        Flags::from_bits((&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap() >> 13).unwrap()
+        Flags::from_bits_truncate((&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap() >> 13)
     }
 
-    /// Offset of the packet.
+    /// Offset of the packet, in 8 octet units.
     pub fn offset(&self) -> u16 {
         (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap() & 0x1fff
     }
@@ -235,6 +242,11 @@ impl<B: AsRef<[u8]>> Packet<B> {
             buffer: &self.buffer.as_ref()[20..(self.header() as usize * 4)],
         }
     }
+
+    /// Split the packet into fragments that fit in the given MTU.
+    pub fn fragments(&self, mtu: usize) -> Result<Fragments> {
+        Fragments::new(self, mtu)
+    }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
@@ -271,14 +283,20 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 
     /// Packet flags.
     pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
-        Cursor::new(&mut self.header_mut()[6..]).write_u16::<BigEndian>(value.bits())?;
+        let offset = self.offset();
+        Cursor::new(&mut self.header_mut()[6..]).write_u16::<BigEndian>(value.bits() << 13 | offset)?;
 
         Ok(self)
     }
 
-    /// Packet fragment offset.
+    /// Packet fragment offset, in 8 octet units.
     pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
-        Cursor::new(&mut self.header_mut()[6..]).write_u16::<BigEndian>(value)?;
+        if value > 0x1fff {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let flags = self.flags();
+        Cursor::new(&mut self.header_mut()[6..]).write_u16::<BigEndian>(flags.bits() << 13 | value)?;
 
         Ok(self)
     }
@@ -426,7 +444,10 @@ impl<'a> Iterator for OptionIter<'a> {
                 Some(Ok(option))
             }
 
-            Err(error) => Some(Err(error)),
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
         }
     }
 }
@@ -0,0 +1,476 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use std::time::{Duration, Instant};
+
+use crate::error::*;
+use crate::ip::v4::{flag, Packet};
+use crate::ip::Protocol;
+use crate::packet::Packet as P;
+
+/// Policy used to resolve data sent more than once by overlapping fragments.
+///
+/// The policies model the behavior of real stacks, so the reassembled
+/// datagram matches what the receiving host would see.
+#[derive(Eq, PartialEq, Copy, Clone, Default, Debug)]
+pub enum Policy {
+    /// The data received first is kept.
+    First,
+
+    /// The data received last is kept.
+    Last,
+
+    /// The data received first is kept, unless the new fragment starts before
+    /// the old one.
+    Bsd,
+
+    /// Like `Bsd`, but a new fragment starting at the same offset also wins if
+    /// it ends after the old one.
+    #[default]
+    Linux,
+
+    /// The data received first is kept, unless the new fragment starts before
+    /// the old one and covers it entirely.
+    Windows,
+}
+
+impl Policy {
+    /// Whether the data of the `new` fragment replaces the data of the `old`
+    /// fragment, where both are `(start, end)` ranges in octets.
+    fn replaces(&self, old: (usize, usize), new: (usize, usize)) -> bool {
+        match *self {
+            Policy::First => false,
+            Policy::Last => true,
+            Policy::Bsd => new.0 < old.0,
+            Policy::Linux => new.0 < old.0 || (new.0 == old.0 && new.1 > old.1),
+            Policy::Windows => new.0 < old.0 && new.1 >= old.1,
+        }
+    }
+}
+
+/// Key identifying the fragments of a datagram.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Key {
+    /// Source address.
+    pub source: Ipv4Addr,
+
+    /// Destination address.
+    pub destination: Ipv4Addr,
+
+    /// Inner protocol.
+    pub protocol: Protocol,
+
+    /// Packet ID.
+    pub id: u16,
+}
+
+impl Key {
+    /// Get the key for the given packet.
+    pub fn new<B: AsRef<[u8]>>(packet: &Packet<B>) -> Key {
+        Key {
+            source: packet.source(),
+            destination: packet.destination(),
+            protocol: packet.protocol(),
+            id: packet.id(),
+        }
+    }
+}
+
+/// IPv4 datagram reassembler.
+///
+/// # Note
+///
+/// Time is supplied by the caller, datagrams are expired lazily when new
+/// fragments are pushed or when `expire` is called.
+#[derive(Debug)]
+pub struct Reassembler {
+    policy: Policy,
+    timeout: Duration,
+    memory: usize,
+
+    used: usize,
+    datagrams: HashMap<Key, Datagram>,
+}
+
+#[derive(Debug)]
+struct Datagram {
+    start: Instant,
+    end: Option<usize>,
+    fragments: Vec<Fragment>,
+}
+
+#[derive(Debug)]
+struct Fragment {
+    offset: usize,
+    header: Option<Vec<u8>>,
+    payload: Vec<u8>,
+}
+
+impl Fragment {
+    fn range(&self) -> (usize, usize) {
+        (self.offset, self.offset + self.payload.len())
+    }
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Reassembler::new()
+    }
+}
+
+impl Reassembler {
+    /// Create a reassembler with the default policy, a 30 seconds timeout and
+    /// a 4 MiB memory limit.
+    pub fn new() -> Reassembler {
+        Reassembler {
+            policy: Default::default(),
+            timeout: Duration::from_secs(30),
+            memory: 4 * 1024 * 1024,
+
+            used: 0,
+            datagrams: HashMap::new(),
+        }
+    }
+
+    /// Overlap resolution policy.
+    pub fn policy(mut self, value: Policy) -> Self {
+        self.policy = value;
+        self
+    }
+
+    /// Time after the first fragment of a datagram when it's dropped.
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Maximum amount of payload octets held, the oldest datagrams are dropped
+    /// to make room for new fragments.
+    pub fn memory(mut self, value: usize) -> Self {
+        self.memory = value;
+        self
+    }
+
+    /// Amount of payload octets currently held.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Number of datagrams waiting for fragments.
+    pub fn pending(&self) -> usize {
+        self.datagrams.len()
+    }
+
+    /// Drop the datagrams that timed out, returning their keys.
+    pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+        let timeout = self.timeout;
+        let expired = self
+            .datagrams
+            .iter()
+            .filter(|(_, datagram)| now.saturating_duration_since(datagram.start) >= timeout)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for key in &expired {
+            self.remove(key);
+        }
+
+        expired
+    }
+
+    /// Push a packet, returning the complete datagram if it was the missing
+    /// fragment.
+    ///
+    /// Packets that are not fragments are returned as they are, malformed
+    /// fragments drop the whole datagram they belong to.
+    pub fn push<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>, now: Instant) -> Result<Option<Packet<Vec<u8>>>> {
+        let more = packet.flags().contains(flag::MORE_FRAGMENTS);
+        let offset = packet.offset() as usize * 8;
+
+        if !more && offset == 0 {
+            return Ok(Some(packet.to_owned()));
+        }
+
+        self.expire(now);
+
+        let key = Key::new(packet);
+        let (header, payload) = packet.split();
+
+        if (more && payload.len() % 8 != 0) || offset + payload.len() + header.len() > u16::MAX as usize {
+            self.remove(&key);
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if payload.len() > self.memory {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        while self.used + payload.len() > self.memory {
+            self.evict(&key);
+        }
+
+        let datagram = self.datagrams.entry(key).or_insert_with(|| Datagram {
+            start: now,
+            end: None,
+            fragments: Vec::new(),
+        });
+
+        let end = offset + payload.len();
+        let invalid = match datagram.end {
+            Some(last) => end > last || (!more && end != last),
+            None => false,
+        };
+
+        if invalid || (!more && datagram.fragments.iter().any(|f| f.range().1 > end)) {
+            self.remove(&key);
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if !more {
+            datagram.end = Some(end);
+        }
+
+        datagram.fragments.push(Fragment {
+            offset,
+            header: if offset == 0 { Some(header.to_vec()) } else { None },
+            payload: payload.to_vec(),
+        });
+        self.used += payload.len();
+
+        if !datagram.is_complete() {
+            return Ok(None);
+        }
+
+        let datagram = self.remove(&key).unwrap();
+        Ok(Some(datagram.assemble(self.policy)))
+    }
+
+    fn remove(&mut self, key: &Key) -> Option<Datagram> {
+        let datagram = self.datagrams.remove(key)?;
+        self.used -= datagram.fragments.iter().map(|f| f.payload.len()).sum::<usize>();
+
+        Some(datagram)
+    }
+
+    /// Drop the oldest datagram, preferring any but the given one.
+    fn evict(&mut self, key: &Key) {
+        let oldest = self
+            .datagrams
+            .iter()
+            .min_by_key(|(k, datagram)| (*k == key, datagram.start))
+            .map(|(k, _)| *k);
+
+        if let Some(oldest) = oldest {
+            self.remove(&oldest);
+        }
+    }
+}
+
+impl Datagram {
+    fn is_complete(&self) -> bool {
+        let end = match self.end {
+            Some(end) => end,
+            None => return false,
+        };
+
+        let mut ranges = self.fragments.iter().map(Fragment::range).collect::<Vec<_>>();
+        ranges.sort();
+
+        let mut covered = 0;
+        for (start, end) in ranges {
+            if start > covered {
+                return false;
+            }
+
+            covered = covered.max(end);
+        }
+
+        covered >= end
+    }
+
+    fn assemble(self, policy: Policy) -> Packet<Vec<u8>> {
+        let end = self.end.unwrap();
+        let mut payload = vec![0; end];
+        let mut owner: Vec<Option<usize>> = vec![None; end];
+
+        for (index, fragment) in self.fragments.iter().enumerate() {
+            let range = fragment.range();
+
+            for position in range.0..range.1 {
+                let replaces = match owner[position] {
+                    None => true,
+                    Some(old) => policy.replaces(self.fragments[old].range(), range),
+                };
+
+                if replaces {
+                    owner[position] = Some(index);
+                    payload[position] = fragment.payload[position - range.0];
+                }
+            }
+        }
+
+        // The header comes from the fragment owning the first octet, which is
+        // never empty since unfragmented packets are not kept.
+        let mut buffer = self.fragments[owner[0].unwrap()].header.clone().unwrap();
+        buffer.extend_from_slice(&payload);
+
+        let length = buffer.len() as u16;
+        Cursor::new(&mut buffer[2..]).write_u16::<BigEndian>(length).unwrap();
+
+        let mut packet = Packet::unchecked(buffer);
+        let flags = packet.flags() - flag::MORE_FRAGMENTS;
+        packet.set_offset(0).unwrap();
+        packet.set_flags(flags).unwrap();
+        packet.update_checksum().unwrap();
+
+        packet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{Policy, Reassembler};
+    use crate::ip;
+    use crate::ip::v4::fixture::{fragment, packet};
+    use crate::ip::v4::flag;
+    use crate::packet::Packet;
+
+    #[test]
+    fn unfragmented() {
+        let raw = packet(1, b"hello");
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+
+        let mut reassembler = Reassembler::new();
+        let result = reassembler.push(&packet, Instant::now()).unwrap().unwrap();
+        assert_eq!(result.as_ref(), &raw[..]);
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let payload = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let raw = packet(0x4242, &payload);
+        let packet = ip::v4::Packet::new(&raw[..]).unwrap();
+
+        let mut fragments = packet.fragments(200).unwrap().collect::<Vec<_>>();
+        fragments.reverse();
+
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+        let last = fragments.pop().unwrap();
+
+        for fragment in &fragments {
+            assert!(reassembler.push(fragment, now).unwrap().is_none());
+        }
+
+        assert_eq!(reassembler.pending(), 1);
+        assert_eq!(reassembler.used(), 1000 - last.payload().len());
+
+        let result = reassembler.push(&last, now).unwrap().unwrap();
+        assert!(result.is_valid());
+        assert_eq!(result.as_ref(), &raw[..]);
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.used(), 0);
+    }
+
+    fn overlap(policy: Policy) -> Vec<u8> {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().policy(policy);
+
+        // Original fragments A, B and C, followed by X starting before A and
+        // covering it, Z starting with B and ending after it, and Y starting
+        // before C and ending inside it.
+        let fragments = [
+            fragment(7, 1, true, &[b'A'; 16]),
+            fragment(7, 4, true, &[b'B'; 8]),
+            fragment(7, 7, true, &[b'C'; 16]),
+            fragment(7, 0, true, &[b'X'; 24]),
+            fragment(7, 3, true, &[b'-'; 8]),
+            fragment(7, 4, true, &[b'Z'; 16]),
+            fragment(7, 6, true, &[b'Y'; 16]),
+        ];
+
+        for fragment in &fragments {
+            assert!(reassembler.push(fragment, now).unwrap().is_none());
+        }
+
+        let result = reassembler.push(&fragment(7, 9, false, b"end"), now).unwrap().unwrap();
+        assert!(result.is_valid());
+        assert!(!result.flags().contains(flag::MORE_FRAGMENTS));
+        assert_eq!(result.offset(), 0);
+
+        result.payload().to_vec()
+    }
+
+    #[test]
+    fn policies() {
+        fn expected(parts: &[(u8, usize)]) -> Vec<u8> {
+            let mut result = Vec::new();
+
+            for &(byte, count) in parts {
+                result.resize(result.len() + count, byte);
+            }
+
+            result.extend_from_slice(b"end");
+            result
+        }
+
+        assert_eq!(overlap(Policy::First), expected(&[(b'X', 8), (b'A', 16), (b'-', 8), (b'B', 8), (b'Z', 8), (b'Y', 8), (b'C', 16)]));
+        assert_eq!(overlap(Policy::Last), expected(&[(b'X', 24), (b'-', 8), (b'Z', 16), (b'Y', 16), (b'C', 8)]));
+        assert_eq!(overlap(Policy::Bsd), expected(&[(b'X', 24), (b'-', 8), (b'B', 8), (b'Z', 8), (b'Y', 16), (b'C', 8)]));
+        assert_eq!(overlap(Policy::Linux), expected(&[(b'X', 24), (b'-', 8), (b'Z', 16), (b'Y', 16), (b'C', 8)]));
+        assert_eq!(overlap(Policy::Windows), expected(&[(b'X', 24), (b'-', 8), (b'B', 8), (b'Z', 8), (b'Y', 8), (b'C', 16)]));
+    }
+
+    #[test]
+    fn timeout() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().timeout(Duration::from_secs(10));
+
+        assert!(reassembler.push(&fragment(1, 0, true, &[0; 8]), now).unwrap().is_none());
+        assert!(reassembler.expire(now + Duration::from_secs(5)).is_empty());
+
+        let expired = reassembler.expire(now + Duration::from_secs(10));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, 1);
+        assert_eq!(reassembler.pending(), 0);
+
+        // The missing half arrives too late to complete anything.
+        assert!(reassembler.push(&fragment(1, 1, false, &[0; 8]), now + Duration::from_secs(11)).unwrap().is_none());
+    }
+
+    #[test]
+    fn memory() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().memory(32);
+
+        assert!(reassembler.push(&fragment(1, 0, true, &[0; 16]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(2, 0, true, &[0; 16]), now + Duration::from_secs(1)).unwrap().is_none());
+        assert_eq!(reassembler.used(), 32);
+
+        // The oldest datagram is dropped to make room.
+        assert!(reassembler.push(&fragment(3, 0, true, &[0; 16]), now + Duration::from_secs(2)).unwrap().is_none());
+        assert_eq!(reassembler.pending(), 2);
+        assert_eq!(reassembler.used(), 32);
+
+        assert!(reassembler.push(&fragment(4, 0, true, &[0; 40]), now).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        // Non final fragments must carry a multiple of 8 octets.
+        assert!(reassembler.push(&fragment(1, 0, true, &[0; 10]), now).is_err());
+
+        // Data past the end of the datagram.
+        assert!(reassembler.push(&fragment(2, 0, false, &[0; 0]), now).unwrap().is_some());
+        assert!(reassembler.push(&fragment(2, 2, false, &[0; 8]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(2, 3, true, &[0; 8]), now).is_err());
+        assert_eq!(reassembler.pending(), 0);
+    }
+}
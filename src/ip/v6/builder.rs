@@ -1,21 +1,42 @@
 use byteorder::{BigEndian, WriteBytesExt};
 use std::io::Cursor;
+use std::net::Ipv6Addr;
 
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::error::*;
+use crate::ip::v6::extension::{self, option, routing};
+use crate::ip::v6::Packet;
+use crate::ip::Protocol;
+use crate::packet::{AsPacket, AsPacketMut};
 
 /// IPv6 packet builder.
+#[derive(Debug)]
 pub struct Builder<B: Buffer = buffer::Dynamic> {
     buffer: B,
     finalizer: Finalization,
+
+    extensions: Vec<(Protocol, usize)>,
+    options: Option<usize>,
+    payload: bool,
 }
 
 impl<B: Buffer> Build<B> for Builder<B> {
-    fn with(buffer: B) -> Result<Self> {
+    fn with(mut buffer: B) -> Result<Self> {
+        use crate::size::header::Min;
+        buffer.next(Packet::<()>::min())?;
+
+        // Set version to 6, the TCP and UDP builders base their finalizer on
+        // extracting the parent IP packet.
+        buffer.data_mut()[0] = 6 << 4;
+
         Ok(Builder {
-            buffer: buffer,
+            buffer,
             finalizer: Default::default(),
+
+            extensions: Vec::new(),
+            options: None,
+            payload: false,
         })
     }
 
@@ -23,8 +44,12 @@ impl<B: Buffer> Build<B> for Builder<B> {
         &mut self.finalizer
     }
 
-    fn build(self) -> Result<B::Inner> {
-        Err(ErrorKind::InvalidPacket.into())
+    fn build(mut self) -> Result<B::Inner> {
+        self.prepare()?;
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
     }
 }
 
@@ -33,3 +58,467 @@ impl Default for Builder<buffer::Dynamic> {
         Builder::with(buffer::Dynamic::default()).unwrap()
     }
 }
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+macro_rules! protocol {
+	($(#[$attr:meta])* fn $module:ident($protocol:ident)) => (
+		protocol!($(#[$attr])* fn $module($protocol) => $module);
+	);
+
+	($(#[$attr:meta])* fn $name:ident($protocol:ident) => $module:ident) => (
+		$(#[$attr])*
+		pub fn $name(mut self) -> Result<crate::$module::Builder<B>> {
+			if self.payload {
+				return Err(ErrorKind::AlreadyDefined.into());
+			}
+
+			self = self.next_header(Protocol::$protocol)?;
+			self.prepare()?;
+
+			let mut builder = crate::$module::Builder::with(self.buffer)?;
+			builder.finalizer().extend(self.finalizer);
+
+			Ok(builder)
+		}
+	)
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Traffic class.
+    pub fn traffic_class(mut self, value: u8) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_traffic_class(value)?;
+        Ok(self)
+    }
+
+    /// Flow label.
+    pub fn flow_label(mut self, value: u32) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_flow_label(value)?;
+        Ok(self)
+    }
+
+    /// Hop limit.
+    pub fn hop_limit(mut self, value: u8) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_hop_limit(value)?;
+        Ok(self)
+    }
+
+    /// Source address.
+    pub fn source(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_source(value)?;
+        Ok(self)
+    }
+
+    /// Destination address.
+    pub fn destination(mut self, value: Ipv6Addr) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
+        Ok(self)
+    }
+
+    /// Protocol of the upper layer.
+    ///
+    /// # Note
+    ///
+    /// When extension headers are present the finalizer takes care of
+    /// chaining them, and the value ends up in the last extension header.
+    pub fn next_header(mut self, value: Protocol) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_next_header(value)?;
+        Ok(self)
+    }
+
+    /// Start a Hop-by-Hop Options header, options are added with `option()`
+    /// and `router_alert()`.
+    ///
+    /// # Note
+    ///
+    /// The Hop-by-Hop Options header has to immediately follow the IPv6
+    /// header, so it must be the first extension.
+    pub fn hop_by_hop(self) -> Result<Self> {
+        if !self.extensions.is_empty() {
+            return Err(ErrorKind::InvalidState.into());
+        }
+
+        self.options(Protocol::Hopopt)
+    }
+
+    /// Start a Destination Options header, options are added with `option()`.
+    pub fn destination_options(self) -> Result<Self> {
+        self.options(Protocol::Ipv6Opts)
+    }
+
+    /// Add an option to the current Hop-by-Hop or Destination Options header.
+    pub fn option(mut self, number: option::Number, value: &[u8]) -> Result<Self> {
+        if self.options.is_none() {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if number == option::Number::Pad1 || value.len() > 255 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let start = self.buffer.length();
+        self.buffer.more(2 + value.len())?;
+
+        let data = &mut self.buffer.data_mut()[start..];
+        data[0] = number.into();
+        data[1] = value.len() as u8;
+        data[2..].copy_from_slice(value);
+
+        Ok(self)
+    }
+
+    /// Add a Router Alert option to the current Hop-by-Hop Options header.
+    pub fn router_alert(mut self, value: u16) -> Result<Self> {
+        // The value has to be aligned to 2n+0.
+        self.align(2, 0)?;
+        self.option(option::Number::RouterAlert, &value.to_be_bytes())
+    }
+
+    /// Add a Routing header with the given addresses.
+    pub fn routing<'a, T: IntoIterator<Item = &'a Ipv6Addr>>(mut self, kind: routing::Kind, segments_left: u8, addresses: T) -> Result<Self> {
+        let addresses = addresses.into_iter().collect::<Vec<_>>();
+        let count = addresses.len();
+
+        if count > 127 || segments_left as usize > count {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let start = self.extension(Protocol::Ipv6Route, 8)?;
+
+        for address in addresses {
+            let offset = self.buffer.length();
+            self.buffer.more(16)?;
+            self.buffer.data_mut()[offset..].copy_from_slice(&address.octets());
+        }
+
+        let data = &mut self.buffer.data_mut()[start..];
+        data[1] = (count * 2) as u8;
+        data[2] = kind.into();
+        data[3] = segments_left;
+
+        // Segment routing headers carry the index of the last segment.
+        if kind == routing::Kind::Segment && count > 0 {
+            data[4] = (count - 1) as u8;
+        }
+
+        Ok(self)
+    }
+
+    /// Add a Fragment header.
+    pub fn fragment(mut self, offset: u16, more: bool, identification: u32) -> Result<Self> {
+        let start = self.extension(Protocol::Ipv6Frag, 8)?;
+
+        extension::Fragment::unchecked(&mut self.buffer.data_mut()[start..])
+            .set_offset(offset)?
+            .set_more_fragments(more)?
+            .set_identification(identification)?;
+
+        Ok(self)
+    }
+
+    /// Payload for the packet.
+    pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+        if self.payload {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.close()?;
+        self.payload = true;
+
+        for byte in value {
+            self.buffer.more(1)?;
+            *self.buffer.data_mut().last_mut().unwrap() = *byte;
+        }
+
+        Ok(self)
+    }
+
+    fn options(mut self, protocol: Protocol) -> Result<Self> {
+        let start = self.extension(protocol, 2)?;
+        self.options = Some(start);
+
+        Ok(self)
+    }
+
+    /// Append a new extension header of the given initial size, returning its
+    /// offset within the layer.
+    fn extension(&mut self, protocol: Protocol, size: usize) -> Result<usize> {
+        if self.payload {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.close()?;
+
+        let start = self.buffer.length();
+        self.buffer.more(size)?;
+        self.extensions.push((protocol, start));
+
+        Ok(start)
+    }
+
+    /// Pad the current options header so the next option value is aligned to
+    /// `alignment * n + offset`.
+    fn align(&mut self, alignment: usize, offset: usize) -> Result<()> {
+        let start = match self.options {
+            Some(start) => start,
+            None => return Err(ErrorKind::InvalidPacket.into()),
+        };
+
+        let current = (self.buffer.length() - start + 2) % alignment;
+        self.pad((alignment + offset - current) % alignment)
+    }
+
+    /// Close the current options header, padding it to a multiple of 8 octets.
+    fn close(&mut self) -> Result<()> {
+        if let Some(start) = self.options.take() {
+            let length = self.buffer.length() - start;
+            self.pad((8 - length % 8) % 8)?;
+
+            let length = self.buffer.length() - start;
+            self.buffer.data_mut()[start + 1] = (length / 8 - 1) as u8;
+        }
+
+        Ok(())
+    }
+
+    fn pad(&mut self, length: usize) -> Result<()> {
+        let start = self.buffer.length();
+
+        match length {
+            0 => (),
+
+            1 => {
+                self.buffer.more(1)?;
+                self.buffer.data_mut()[start] = option::Number::Pad1.into();
+            }
+
+            n => {
+                self.buffer.more(n)?;
+                self.buffer.data_mut()[start] = option::Number::PadN.into();
+                self.buffer.data_mut()[start + 1] = (n - 2) as u8;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn prepare(&mut self) -> Result<()> {
+        use crate::size::header::Min;
+
+        self.close()?;
+
+        // Chain the extension headers, the upper layer protocol is moved from
+        // the fixed header to the last extension header.
+        let data = self.buffer.data_mut();
+        let mut next = 6;
+
+        for &(protocol, start) in &self.extensions {
+            let upper = data[next];
+            data[next] = protocol.into();
+            data[start] = upper;
+
+            next = start;
+        }
+
+        // Cover the extension headers until the final length is known, so the
+        // upper layer finalizers can find the final destination.
+        let header = Packet::<()>::min();
+        let length = self.buffer.length() - header;
+        Packet::unchecked(self.buffer.data_mut()).set_payload_length(length as u16)?;
+
+        let offset = self.buffer.offset();
+
+        self.finalizer.add(move |out| {
+            // Calculate and write the length of everything after the fixed header.
+            let length = out.len() - (offset + header);
+
+            if length > usize::from(u16::MAX) {
+                return Err(ErrorKind::InvalidPacket.into());
+            }
+
+            Cursor::new(&mut out[offset + 4..]).write_u16::<BigEndian>(length as u16)?;
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
+    protocol!(/// Build a TCP packet.
+		fn tcp(Tcp));
+
+    protocol!(/// Build a UDP packet.
+		fn udp(Udp));
+
+    protocol!(/// Build a UDP-Lite packet.
+		fn udplite(UdpLite));
+
+    protocol!(/// Build an ICMPv6 packet.
+		fn icmp(Ipv6Icmp) => icmpv6);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::ip;
+    use crate::ip::v6::extension::{option, routing};
+    use crate::ip::v6::Extension;
+    use crate::packet::Packet;
+    use crate::udp;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn payload() {
+        let packet = ip::v6::Builder::default()
+            .traffic_class(0x0a)
+            .unwrap()
+            .flow_label(0x12345)
+            .unwrap()
+            .hop_limit(64)
+            .unwrap()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .next_header(ip::Protocol::Ipv6NoNxt)
+            .unwrap()
+            .payload(b"test")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(packet.version(), 6);
+        assert_eq!(packet.traffic_class(), 0x0a);
+        assert_eq!(packet.flow_label(), 0x12345);
+        assert_eq!(packet.payload_length(), 4);
+        assert_eq!(packet.next_header(), ip::Protocol::Ipv6NoNxt);
+        assert_eq!(packet.hop_limit(), 64);
+        assert_eq!(packet.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(packet.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(packet.payload(), b"test");
+    }
+
+    #[test]
+    fn generic() {
+        let packet = ip::Builder::default()
+            .v6()
+            .unwrap()
+            .hop_limit(255)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::Packet::new(packet).unwrap();
+        assert_eq!(packet.header().len(), 40);
+        assert!(packet.payload().is_empty());
+    }
+
+    #[test]
+    fn extensions() {
+        let via = "2001:db8::3".parse::<Ipv6Addr>().unwrap();
+        let packet = ip::v6::Builder::default()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .hop_by_hop()
+            .unwrap()
+            .router_alert(0)
+            .unwrap()
+            .destination_options()
+            .unwrap()
+            .option(option::Number::Unknown(0x1e), &[1, 2, 3])
+            .unwrap()
+            .routing(routing::Kind::Type2, 1, &[via])
+            .unwrap()
+            .fragment(0, false, 0xdeadbeef)
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(ip.next_header(), ip::Protocol::Hopopt);
+        assert_eq!(ip.payload_length(), 8 + 8 + 24 + 8 + 11);
+
+        let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+        let protocols = extensions.iter().map(|e| e.protocol()).collect::<Vec<_>>();
+        assert_eq!(protocols, vec![ip::Protocol::Hopopt, ip::Protocol::Ipv6Opts, ip::Protocol::Ipv6Route, ip::Protocol::Ipv6Frag]);
+
+        match extensions[0] {
+            Extension::HopByHop(ref header) => {
+                assert_eq!(header.length(), 0);
+                assert_eq!(header.next_header(), ip::Protocol::Ipv6Opts);
+
+                let options = header.options().collect::<Result<Vec<_>, _>>().unwrap();
+                assert_eq!(options[0].number(), option::Number::RouterAlert);
+                assert_eq!(options[0].payload(), &[0, 0]);
+                assert_eq!(options[1].number(), option::Number::PadN);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        match extensions[2] {
+            Extension::Routing(ref header) => {
+                assert_eq!(header.length(), 2);
+                assert_eq!(header.segments_left(), 1);
+                assert_eq!(header.addresses().collect::<Vec<_>>(), vec![via]);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        match extensions[3] {
+            Extension::Fragment(ref header) => {
+                assert_eq!(header.next_header(), ip::Protocol::Udp);
+                assert_eq!(header.identification(), 0xdeadbeef);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        let (protocol, payload) = ip.upper_layer().unwrap();
+        assert_eq!(protocol, ip::Protocol::Udp);
+
+        let udp = udp::Packet::new(payload).unwrap();
+        assert_eq!(udp.payload(), b"lol");
+        // The pseudo-header carries the final destination from the Routing header.
+        assert_eq!(ip.final_destination(), via);
+        assert_eq!(udp.checksum(), 0xa390);
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+    }
+
+    #[test]
+    fn extensions_order() {
+        assert!(ip::v6::Builder::default().option(option::Number::RouterAlert, &[0, 0]).is_err());
+        assert!(ip::v6::Builder::default().payload(b"lol").unwrap().fragment(0, false, 0).is_err());
+        assert!(ip::v6::Builder::default().fragment(0, false, 0).unwrap().hop_by_hop().is_err());
+        assert!(ip::v6::Builder::default().hop_by_hop().unwrap().hop_by_hop().is_err());
+    }
+
+    #[test]
+    fn routing_invalid() {
+        let via = "2001:db8::3".parse::<Ipv6Addr>().unwrap();
+        assert!(ip::v6::Builder::default().routing(routing::Kind::Type2, 2, &[via]).is_err());
+        assert!(ip::v6::Builder::default().routing(routing::Kind::SourceRoute, 0, &[via; 128]).is_err());
+    }
+}
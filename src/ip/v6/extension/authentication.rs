@@ -0,0 +1,134 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+use crate::ip::Protocol;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Authentication header parser.
+pub struct Authentication<B> {
+    buffer: B,
+}
+
+sized!(Authentication,
+	header {
+		min:  12,
+		max:  12,
+		size: 12,
+	}
+
+	payload {
+		min:  0,
+		max:  1012,
+		size: p => (p.length() as usize + 2) * 4 - 12,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Authentication<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Authentication")
+            .field("next_header", &self.next_header())
+            .field("length", &self.length())
+            .field("spi", &self.spi())
+            .field("sequence", &self.sequence())
+            .field("icv", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+    /// Create an authentication header without checking the buffer.
+    pub fn unchecked(buffer: B) -> Authentication<B> {
+        Authentication { buffer }
+    }
+
+    /// Parse an authentication header, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Authentication<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let header = Authentication::unchecked(buffer);
+
+        if header.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if header.length() < 1 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if header.buffer.as_ref().len() < header.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Authentication<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Authentication<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Authentication<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Authentication<&[u8]>> {
+        Authentication::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Authentication<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Authentication<&mut [u8]>> {
+        Authentication::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Authentication<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::Size;
+
+        self.buffer.as_ref()[..self.size()].split_at(12)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Authentication<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::Size;
+
+        let size = self.size();
+        self.buffer.as_mut()[..size].split_at_mut(12)
+    }
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+    /// Protocol of the next header.
+    pub fn next_header(&self) -> Protocol {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the header in 4 octet units, minus 2.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Security Parameters Index.
+    pub fn spi(&self) -> u32 {
+        (&self.buffer.as_ref()[4..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Sequence number.
+    pub fn sequence(&self) -> u32 {
+        (&self.buffer.as_ref()[8..]).read_u32::<BigEndian>().unwrap()
+    }
+}
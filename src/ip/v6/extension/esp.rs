@@ -0,0 +1,105 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Encapsulating Security Payload header parser.
+///
+/// # Note
+///
+/// Everything after the sequence number is encrypted, including the next
+/// header, so the ESP header always ends the extension header chain.
+pub struct Esp<B> {
+    buffer: B,
+}
+
+sized!(Esp,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Esp<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Esp")
+            .field("spi", &self.spi())
+            .field("sequence", &self.sequence())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Esp<B> {
+    /// Create an ESP header without checking the buffer.
+    pub fn unchecked(buffer: B) -> Esp<B> {
+        Esp { buffer }
+    }
+
+    /// Parse an ESP header, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Esp<B>> {
+        use crate::size::header::Min;
+
+        let header = Esp::unchecked(buffer);
+
+        if header.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Esp<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Esp<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Esp<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Esp<&[u8]>> {
+        Esp::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Esp<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Esp<&mut [u8]>> {
+        Esp::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Esp<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref().split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Esp<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut().split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Esp<B> {
+    /// Security Parameters Index.
+    pub fn spi(&self) -> u32 {
+        (&self.buffer.as_ref()[0..]).read_u32::<BigEndian>().unwrap()
+    }
+
+    /// Sequence number.
+    pub fn sequence(&self) -> u32 {
+        (&self.buffer.as_ref()[4..]).read_u32::<BigEndian>().unwrap()
+    }
+}
@@ -0,0 +1,153 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::ip::Protocol;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Fragment header parser.
+pub struct Fragment<B> {
+    buffer: B,
+}
+
+sized!(Fragment,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Fragment<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Fragment")
+            .field("next_header", &self.next_header())
+            .field("offset", &self.offset())
+            .field("more_fragments", &self.more_fragments())
+            .field("identification", &self.identification())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+    /// Create a fragment header without checking the buffer.
+    pub fn unchecked(buffer: B) -> Fragment<B> {
+        Fragment { buffer }
+    }
+
+    /// Parse a fragment header, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Fragment<B>> {
+        use crate::size::header::Min;
+
+        let header = Fragment::unchecked(buffer);
+
+        if header.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Fragment<B> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer.as_ref()[..8]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Fragment<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer.as_mut()[..8]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Fragment<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Fragment<&[u8]>> {
+        Fragment::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Fragment<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Fragment<&mut [u8]>> {
+        Fragment::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Fragment<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        self.buffer.as_ref()[..8].split_at(8)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Fragment<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        self.buffer.as_mut()[..8].split_at_mut(8)
+    }
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+    /// Protocol of the next header.
+    pub fn next_header(&self) -> Protocol {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Offset of the fragment in 8 octet units.
+    pub fn offset(&self) -> u16 {
+        (&self.buffer.as_ref()[2..]).read_u16::<BigEndian>().unwrap() >> 3
+    }
+
+    /// Whether more fragments follow.
+    pub fn more_fragments(&self) -> bool {
+        self.buffer.as_ref()[3] & 0b1 != 0
+    }
+
+    /// Identification shared by all the fragments of a packet.
+    pub fn identification(&self) -> u32 {
+        (&self.buffer.as_ref()[4..]).read_u32::<BigEndian>().unwrap()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Fragment<B> {
+    /// Protocol of the next header.
+    pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = value.into();
+
+        Ok(self)
+    }
+
+    /// Offset of the fragment in 8 octet units.
+    pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
+        if value > 0x1fff {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let flags = self.buffer.as_ref()[3] & 0b111;
+        Cursor::new(&mut self.buffer.as_mut()[2..]).write_u16::<BigEndian>(value << 3 | u16::from(flags))?;
+
+        Ok(self)
+    }
+
+    /// Whether more fragments follow.
+    pub fn set_more_fragments(&mut self, value: bool) -> Result<&mut Self> {
+        if value {
+            self.buffer.as_mut()[3] |= 0b1;
+        } else {
+            self.buffer.as_mut()[3] &= !0b1;
+        }
+
+        Ok(self)
+    }
+
+    /// Identification shared by all the fragments of a packet.
+    pub fn set_identification(&mut self, value: u32) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u32::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+}
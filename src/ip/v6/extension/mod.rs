@@ -0,0 +1,75 @@
+/// Hop-by-Hop and Destination Options option parser.
+pub mod option;
+pub use self::option::Option;
+
+mod options;
+pub use self::options::Options;
+
+/// Routing header parser.
+pub mod routing;
+pub use self::routing::Routing;
+
+mod fragment;
+pub use self::fragment::Fragment;
+
+mod authentication;
+pub use self::authentication::Authentication;
+
+mod esp;
+pub use self::esp::Esp;
+
+use crate::ip::Protocol;
+
+/// Typed IPv6 extension header.
+#[derive(Debug)]
+pub enum Extension<B: AsRef<[u8]>> {
+    /// Hop-by-Hop Options header.
+    HopByHop(Options<B>),
+
+    /// Routing header.
+    Routing(Routing<B>),
+
+    /// Fragment header.
+    Fragment(Fragment<B>),
+
+    /// Destination Options header.
+    DestinationOptions(Options<B>),
+
+    /// Authentication header.
+    Authentication(Authentication<B>),
+
+    /// Encapsulating Security Payload header.
+    Esp(Esp<B>),
+}
+
+impl<B: AsRef<[u8]>> Extension<B> {
+    /// Protocol value identifying the extension header.
+    pub fn protocol(&self) -> Protocol {
+        match *self {
+            Extension::HopByHop(_) => Protocol::Hopopt,
+            Extension::Routing(_) => Protocol::Ipv6Route,
+            Extension::Fragment(_) => Protocol::Ipv6Frag,
+            Extension::DestinationOptions(_) => Protocol::Ipv6Opts,
+            Extension::Authentication(_) => Protocol::Ah,
+            Extension::Esp(_) => Protocol::Esp,
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Extension<B> {
+    fn as_ref(&self) -> &[u8] {
+        match *self {
+            Extension::HopByHop(ref header) => header.as_ref(),
+            Extension::Routing(ref header) => header.as_ref(),
+            Extension::Fragment(ref header) => header.as_ref(),
+            Extension::DestinationOptions(ref header) => header.as_ref(),
+            Extension::Authentication(ref header) => header.as_ref(),
+            Extension::Esp(ref header) => header.as_ref(),
+        }
+    }
+}
+
+/// Check if the protocol is an IPv6 extension header this module understands.
+pub fn is_extension(protocol: Protocol) -> bool {
+    matches!(protocol, Protocol::Hopopt | Protocol::Ipv6Route | Protocol::Ipv6Frag | Protocol::Ipv6Opts | Protocol::Ah | Protocol::Esp)
+}
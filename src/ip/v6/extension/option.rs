@@ -0,0 +1,228 @@
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Hop-by-Hop and Destination Options option parser.
+pub struct Option<B> {
+    buffer: B,
+}
+
+sized!(Option,
+	header {
+		min: 1,
+		max: 2,
+		size: p => match p.number() {
+			Number::Pad1 => 1,
+			_ => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  255,
+		size: p => p.length() as usize,
+	});
+
+/// Action to take when the option is not recognized.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Action {
+    /// Skip over the option.
+    Skip,
+
+    /// Discard the packet.
+    Discard,
+
+    /// Discard the packet and send an ICMP Parameter Problem.
+    DiscardAndReport,
+
+    /// Discard the packet and send an ICMP Parameter Problem, unless the
+    /// destination is a multicast address.
+    DiscardAndReportUnicast,
+}
+
+/// Option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+    ///
+    Pad1,
+
+    ///
+    PadN,
+
+    ///
+    TunnelEncapsulationLimit,
+
+    ///
+    RouterAlert,
+
+    ///
+    Calipso,
+
+    ///
+    QuickStart,
+
+    ///
+    Jumbo,
+
+    ///
+    HomeAddress,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Option")
+            .field("action", &self.action())
+            .field("is_mutable", &self.is_mutable())
+            .field("number", &self.number())
+            .field("length", &self.length())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Parse an option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Option<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let option = Option { buffer };
+
+        if option.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if option.number() != Number::Pad1 && option.buffer.as_ref().len() < 2 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if option.buffer.as_ref().len() < option.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Option<&[u8]>> {
+        Option::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+        Option::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        match self.number() {
+            Number::Pad1 => self.buffer.as_ref()[..1].split_at(1),
+
+            _ => self.buffer.as_ref()[..self.length() as usize + 2].split_at(2),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        match self.number() {
+            Number::Pad1 => self.buffer.as_mut()[..1].split_at_mut(1),
+
+            _ => {
+                let length = self.length() as usize;
+                self.buffer.as_mut()[..length + 2].split_at_mut(2)
+            }
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Action to take if the option is not recognized.
+    pub fn action(&self) -> Action {
+        match self.buffer.as_ref()[0] >> 6 {
+            0 => Action::Skip,
+            1 => Action::Discard,
+            2 => Action::DiscardAndReport,
+            _ => Action::DiscardAndReportUnicast,
+        }
+    }
+
+    /// Whether the option data may change en route.
+    pub fn is_mutable(&self) -> bool {
+        self.buffer.as_ref()[0] & 0b0010_0000 != 0
+    }
+
+    /// Option number.
+    pub fn number(&self) -> Number {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the option data in octets.
+    pub fn length(&self) -> u8 {
+        match self.number() {
+            Number::Pad1 => 0,
+
+            _ => self.buffer.as_ref()[1],
+        }
+    }
+}
+
+impl From<u8> for Number {
+    fn from(value: u8) -> Self {
+        use self::Number::*;
+
+        match value {
+            0x00 => Pad1,
+            0x01 => PadN,
+            0x04 => TunnelEncapsulationLimit,
+            0x05 => RouterAlert,
+            0x07 => Calipso,
+            0x26 => QuickStart,
+            0xc2 => Jumbo,
+            0xc9 => HomeAddress,
+            n => Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Number {
+    fn into(self) -> u8 {
+        use self::Number::*;
+
+        match self {
+            Pad1 => 0x00,
+            PadN => 0x01,
+            TunnelEncapsulationLimit => 0x04,
+            RouterAlert => 0x05,
+            Calipso => 0x07,
+            QuickStart => 0x26,
+            Jumbo => 0xc2,
+            HomeAddress => 0xc9,
+            Unknown(n) => n,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use crate::error::*;
+use crate::ip::v6::extension::option;
+use crate::ip::Protocol;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Hop-by-Hop Options and Destination Options header parser.
+pub struct Options<B> {
+    buffer: B,
+}
+
+sized!(Options,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  6,
+		max:  2046,
+		size: p => (p.length() as usize + 1) * 8 - 2,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Options<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Options")
+            .field("next_header", &self.next_header())
+            .field("length", &self.length())
+            .field("options", &self.options().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Options<B> {
+    /// Create an options header without checking the buffer.
+    pub fn unchecked(buffer: B) -> Options<B> {
+        Options { buffer }
+    }
+
+    /// Parse an options header, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Options<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let header = Options::unchecked(buffer);
+
+        if header.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if header.buffer.as_ref().len() < header.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Options<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Options<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Options<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Options<&[u8]>> {
+        Options::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Options<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Options<&mut [u8]>> {
+        Options::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Options<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::Size;
+
+        self.buffer.as_ref()[..self.size()].split_at(2)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Options<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::Size;
+
+        let size = self.size();
+        self.buffer.as_mut()[..size].split_at_mut(2)
+    }
+}
+
+impl<B: AsRef<[u8]>> Options<B> {
+    /// Protocol of the next header.
+    pub fn next_header(&self) -> Protocol {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the header in 8 octet units, not including the first 8
+    /// octets.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Options in the header.
+    pub fn options(&self) -> OptionIter {
+        OptionIter { buffer: self.payload() }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Options<B> {
+    /// Protocol of the next header.
+    pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = value.into();
+
+        Ok(self)
+    }
+}
+
+/// Iterator over Hop-by-Hop and Destination Options options.
+pub struct OptionIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+    type Item = Result<option::Option<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use crate::size::Size;
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        match option::Option::new(self.buffer) {
+            Ok(option) => {
+                self.buffer = &self.buffer[option.size()..];
+                Some(Ok(option))
+            }
+
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
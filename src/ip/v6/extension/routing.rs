@@ -0,0 +1,226 @@
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::ip::Protocol;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+
+/// Routing header parser.
+pub struct Routing<B> {
+    buffer: B,
+}
+
+sized!(Routing,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  4,
+		max:  2044,
+		size: p => (p.length() as usize + 1) * 8 - 4,
+	});
+
+/// Routing header type.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Kind {
+    /// Deprecated source route.
+    SourceRoute,
+
+    ///
+    Nimrod,
+
+    /// Mobile IPv6 home address.
+    Type2,
+
+    /// RPL source route.
+    Rpl,
+
+    /// Segment routing.
+    Segment,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Routing<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ip::v6::extension::Routing")
+            .field("next_header", &self.next_header())
+            .field("length", &self.length())
+            .field("kind", &self.kind())
+            .field("segments_left", &self.segments_left())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+    /// Create a routing header without checking the buffer.
+    pub fn unchecked(buffer: B) -> Routing<B> {
+        Routing { buffer }
+    }
+
+    /// Parse a routing header, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Routing<B>> {
+        use crate::size::header::Min;
+        use crate::size::Size;
+
+        let header = Routing::unchecked(buffer);
+
+        if header.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        if header.buffer.as_ref().len() < header.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Routing<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Routing<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Routing<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Routing<&[u8]>> {
+        Routing::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Routing<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Routing<&mut [u8]>> {
+        Routing::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Routing<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::Size;
+
+        self.buffer.as_ref()[..self.size()].split_at(4)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Routing<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::Size;
+
+        let size = self.size();
+        self.buffer.as_mut()[..size].split_at_mut(4)
+    }
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+    /// Protocol of the next header.
+    pub fn next_header(&self) -> Protocol {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Length of the header in 8 octet units, not including the first 8
+    /// octets.
+    pub fn length(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Routing type.
+    pub fn kind(&self) -> Kind {
+        self.buffer.as_ref()[2].into()
+    }
+
+    /// Number of route segments remaining.
+    pub fn segments_left(&self) -> u8 {
+        self.buffer.as_ref()[3]
+    }
+
+    /// Addresses carried by source route, type 2 and segment routing headers.
+    pub fn addresses(&self) -> AddressIter {
+        AddressIter {
+            buffer: &self.payload()[4..],
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Routing<B> {
+    /// Protocol of the next header.
+    pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+        self.buffer.as_mut()[0] = value.into();
+
+        Ok(self)
+    }
+
+    /// Number of route segments remaining.
+    pub fn set_segments_left(&mut self, value: u8) -> Result<&mut Self> {
+        self.buffer.as_mut()[3] = value;
+
+        Ok(self)
+    }
+}
+
+/// Iterator over the addresses in a routing header.
+pub struct AddressIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for AddressIter<'a> {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < 16 {
+            return None;
+        }
+
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer[..16]);
+        self.buffer = &self.buffer[16..];
+
+        Some(Ipv6Addr::from(octets))
+    }
+}
+
+impl From<u8> for Kind {
+    fn from(value: u8) -> Self {
+        use self::Kind::*;
+
+        match value {
+            0 => SourceRoute,
+            1 => Nimrod,
+            2 => Type2,
+            3 => Rpl,
+            4 => Segment,
+            n => Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Kind {
+    fn into(self) -> u8 {
+        use self::Kind::*;
+
+        match self {
+            SourceRoute => 0,
+            Nimrod => 1,
+            Type2 => 2,
+            Rpl => 3,
+            Segment => 4,
+            Unknown(n) => n,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::builder::Builder;
+use crate::ip;
+
+/// Build a UDP datagram from 2001:db8::1 to 2001:db8::2 with a Hop-by-Hop
+/// and a Destination Options header.
+pub fn packet(payload: &[u8]) -> Vec<u8> {
+    ip::v6::Builder::default()
+        .source("2001:db8::1".parse().unwrap())
+        .unwrap()
+        .destination("2001:db8::2".parse().unwrap())
+        .unwrap()
+        .hop_by_hop()
+        .unwrap()
+        .router_alert(0)
+        .unwrap()
+        .destination_options()
+        .unwrap()
+        .next_header(ip::Protocol::Udp)
+        .unwrap()
+        .payload(payload)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+/// Build a fragment of datagram 42 at the given offset, in units of 8 octets.
+pub fn fragment(offset: u16, more: bool, payload: &[u8]) -> ip::v6::Packet<Vec<u8>> {
+    let raw = ip::v6::Builder::default()
+        .source("2001:db8::1".parse().unwrap())
+        .unwrap()
+        .destination("2001:db8::2".parse().unwrap())
+        .unwrap()
+        .fragment(offset, more, 42)
+        .unwrap()
+        .next_header(ip::Protocol::Udp)
+        .unwrap()
+        .payload(payload)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    ip::v6::Packet::new(raw).unwrap()
+}
@@ -0,0 +1,219 @@
+use crate::error::*;
+use crate::ip::v6::{extension, Extension, Packet};
+use crate::ip::Protocol;
+
+/// Iterator over the fragments of an IPv6 packet.
+///
+/// # Note
+///
+/// A packet that already fits in the MTU is yielded untouched as the only
+/// fragment, otherwise every fragment carries the unfragmentable part of the
+/// packet followed by a Fragment header.
+#[derive(Debug)]
+pub struct Fragments<'a> {
+    header: Vec<u8>,
+    payload: &'a [u8],
+    mtu: usize,
+
+    position: usize,
+    done: bool,
+}
+
+impl<'a> Fragments<'a> {
+    /// Prepare the fragments for a packet, failing if the packet already is a
+    /// fragment, or if the MTU cannot hold any data.
+    pub fn new<B: AsRef<[u8]>>(packet: &'a Packet<B>, mtu: usize, identification: u32) -> Result<Fragments<'a>> {
+        let buffer = packet.as_ref();
+        let (length, next) = unfragmentable(packet)?;
+
+        if buffer.len() <= mtu {
+            return Ok(Fragments {
+                header: Vec::new(),
+                payload: buffer,
+                mtu,
+
+                position: 0,
+                done: false,
+            });
+        }
+
+        let mut header = buffer[..length].to_vec();
+        let protocol = header[next].into();
+        header[next] = Protocol::Ipv6Frag.into();
+        header.extend_from_slice(&[0; 8]);
+
+        extension::Fragment::unchecked(&mut header[length..])
+            .set_next_header(protocol)?
+            .set_identification(identification)?;
+
+        if mtu < header.len() + 8 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        Ok(Fragments {
+            header,
+            payload: &buffer[length..],
+            mtu,
+
+            position: 0,
+            done: false,
+        })
+    }
+}
+
+impl<'a> Iterator for Fragments<'a> {
+    type Item = Packet<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // The packet fits, so it's the only fragment.
+        if self.header.is_empty() {
+            self.done = true;
+            return Some(Packet::unchecked(self.payload.to_vec()));
+        }
+
+        let remaining = self.payload.len() - self.position;
+        let available = self.mtu - self.header.len();
+
+        let (size, more) = if remaining <= available {
+            self.done = true;
+            (remaining, false)
+        } else {
+            (available / 8 * 8, true)
+        };
+
+        let mut buffer = Vec::with_capacity(self.header.len() + size);
+        buffer.extend_from_slice(&self.header);
+        buffer.extend_from_slice(&self.payload[self.position..self.position + size]);
+
+        let start = self.header.len() - 8;
+        extension::Fragment::unchecked(&mut buffer[start..])
+            .set_offset((self.position / 8) as u16)
+            .unwrap()
+            .set_more_fragments(more)
+            .unwrap();
+
+        self.position += size;
+
+        let length = (buffer.len() - 40) as u16;
+        let mut packet = Packet::unchecked(buffer);
+        packet.set_payload_length(length).unwrap();
+
+        Some(packet)
+    }
+}
+
+/// Find the unfragmentable part of a packet, returning its length and the
+/// position of the next header field to point at the Fragment header.
+///
+/// The unfragmentable part is made of the Hop-by-Hop Options header and the
+/// headers up to the Routing header, if any.
+pub(in crate::ip::v6) fn unfragmentable<B: AsRef<[u8]>>(packet: &Packet<B>) -> Result<(usize, usize)> {
+    let mut result = (40, 6);
+    let mut position = 40;
+
+    for extension in packet.extensions() {
+        let extension = extension?;
+        let start = position;
+        position += extension.as_ref().len();
+
+        match extension {
+            Extension::HopByHop(_) | Extension::Routing(_) => result = (position, start),
+
+            Extension::DestinationOptions(_) => (),
+
+            Extension::Fragment(_) => return Err(ErrorKind::InvalidPacket.into()),
+
+            Extension::Authentication(_) | Extension::Esp(_) => break,
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::ip;
+    use crate::ip::v6::fixture::packet;
+    use crate::ip::v6::Extension;
+    use crate::packet::Packet;
+
+    #[test]
+    fn fits() {
+        let raw = packet(&[0xaa; 100]);
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+
+        let fragments = packet.fragments(1280, 1).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].as_ref(), &raw[..]);
+    }
+
+    #[test]
+    fn split() {
+        let payload = (0..3000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let raw = packet(&payload);
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+
+        let fragments = packet.fragments(1280, 0xdead_beef).unwrap().collect::<Vec<_>>();
+        assert_eq!(fragments.len(), 3);
+
+        let mut data = Vec::new();
+
+        for (i, fragment) in fragments.iter().enumerate() {
+            let fragment = ip::v6::Packet::new(fragment.as_ref()).unwrap();
+            assert!(fragment.as_ref().len() <= 1280);
+            assert_eq!(fragment.payload_length() as usize, fragment.as_ref().len() - 40);
+
+            // Only the first fragment carries the destination options, so stop
+            // at the Fragment header.
+            let extensions = fragment.extensions().take(2).collect::<Result<Vec<_>, _>>().unwrap();
+            assert!(matches!(extensions[0], Extension::HopByHop(_)));
+
+            match extensions[1] {
+                Extension::Fragment(ref header) => {
+                    assert_eq!(header.identification(), 0xdead_beef);
+                    assert_eq!(header.next_header(), ip::Protocol::Ipv6Opts);
+                    assert_eq!(header.offset() as usize * 8, data.len());
+                    assert_eq!(header.more_fragments(), i != 2);
+                }
+
+                _ => panic!("expected a fragment header"),
+            }
+
+            data.extend_from_slice(&fragment.payload()[16..]);
+        }
+
+        // The destination options are fragmentable, so they are part of the data.
+        assert_eq!(&data[8..], &payload[..]);
+    }
+
+    #[test]
+    fn small_mtu() {
+        let raw = packet(&[0; 100]);
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+
+        // Fixed header, Hop-by-Hop and Fragment headers.
+        assert!(packet.fragments(63, 1).is_err());
+        assert_eq!(packet.fragments(64, 1).unwrap().count(), 14);
+    }
+
+    #[test]
+    fn fragmented() {
+        let raw = ip::v6::Builder::default()
+            .fragment(0, true, 1)
+            .unwrap()
+            .next_header(ip::Protocol::Udp)
+            .unwrap()
+            .payload(&[0; 100][..])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+        assert!(packet.fragments(64, 2).is_err());
+    }
+}
@@ -1,5 +1,20 @@
+/// IPv6 extension header parsers.
+pub mod extension;
+pub use self::extension::Extension;
+
 mod packet;
 pub use self::packet::Packet;
 
 mod builder;
 pub use self::builder::Builder;
+
+/// IPv6 fragmentation.
+pub mod fragment;
+pub use self::fragment::Fragments;
+
+/// IPv6 reassembly.
+pub mod reassembly;
+pub use self::reassembly::Reassembler;
+
+#[cfg(test)]
+pub(crate) mod fixture;
@@ -1,37 +1,46 @@
-use byteorder::{BigEndian, ReadBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use std::fmt;
-use std::net::Ipv4Addr;
+use std::io::Cursor;
+use std::net::Ipv6Addr;
 
 use crate::error::*;
-use crate::ip::v4::checksum;
-use crate::ip::v4::option;
-use crate::ip::v4::Flags;
+use crate::ip::v6::extension::{self, Extension};
+use crate::ip::v6::fragment::Fragments;
 use crate::ip::Protocol;
 use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
-use crate::size;
 
 /// IPv6 packet parser.
-#[derive(Clone)]
+#[derive(Copy, Clone)]
 pub struct Packet<B> {
     buffer: B,
 }
 
 sized!(Packet,
 	header {
-		min:  0,
-		max:  0,
-		size: 0,
+		min:  40,
+		max:  40,
+		size: 40,
 	}
 
 	payload {
 		min:  0,
-		max:  0,
-		size: 0,
+		max:  u16::MAX as usize,
+		size: p => p.payload_length() as usize,
 	});
 
 impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("ip::v6::Packet").finish()
+        f.debug_struct("ip::v6::Packet")
+            .field("version", &self.version())
+            .field("traffic_class", &self.traffic_class())
+            .field("flow_label", &self.flow_label())
+            .field("payload_length", &self.payload_length())
+            .field("next_header", &self.next_header())
+            .field("hop_limit", &self.hop_limit())
+            .field("source", &self.source())
+            .field("destination", &self.destination())
+            .field("payload", &self.payload())
+            .finish()
     }
 }
 
@@ -55,12 +64,20 @@ impl<B: AsRef<[u8]>> Packet<B> {
             return Err(ErrorKind::InvalidPacket.into());
         }
 
-        Err(ErrorKind::InvalidPacket.into())
+        Ok(packet)
     }
 
     /// Parse an IPv6 packet, checking the buffer contents are correct.
     pub fn new(buffer: B) -> Result<Packet<B>> {
-        Packet::no_payload(buffer)
+        use crate::size::Size;
+
+        let packet = Packet::no_payload(buffer)?;
+
+        if packet.buffer.as_ref().len() < packet.size() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(packet)
     }
 }
 
@@ -78,13 +95,18 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
     fn as_ref(&self) -> &[u8] {
-        &[]
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
     }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
     fn as_mut(&mut self) -> &mut [u8] {
-        &mut []
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
     }
 }
 
@@ -102,12 +124,427 @@ impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for
 
 impl<B: AsRef<[u8]>> P for Packet<B> {
     fn split(&self) -> (&[u8], &[u8]) {
-        (&[], &[])
+        use crate::size::payload::Size;
+
+        let header = 40;
+        let payload = self.size();
+
+        let buffer = self.buffer.as_ref();
+        let buffer = if buffer.len() < header + payload { buffer } else { &buffer[..header + payload] };
+
+        buffer.split_at(header)
     }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
     fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
-        (&mut [], &mut [])
+        use crate::size::payload::Size;
+
+        let header = 40;
+        let payload = self.size();
+
+        let buffer = self.buffer.as_mut();
+        let buffer = if buffer.len() < header + payload { buffer } else { &mut buffer[..header + payload] };
+
+        buffer.split_at_mut(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// IP protocol version, will always be 6.
+    pub fn version(&self) -> u8 {
+        self.buffer.as_ref()[0] >> 4
+    }
+
+    /// Traffic class, DSCP and ECN together.
+    pub fn traffic_class(&self) -> u8 {
+        (self.buffer.as_ref()[0] << 4) | (self.buffer.as_ref()[1] >> 4)
+    }
+
+    /// Flow label.
+    pub fn flow_label(&self) -> u32 {
+        (&self.buffer.as_ref()[0..]).read_u32::<BigEndian>().unwrap() & 0xf_ffff
+    }
+
+    /// Length of the payload in octets, including any extension headers.
+    pub fn payload_length(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Protocol of the header following the fixed header.
+    pub fn next_header(&self) -> Protocol {
+        self.buffer.as_ref()[6].into()
+    }
+
+    /// Hop limit for the packet.
+    pub fn hop_limit(&self) -> u8 {
+        self.buffer.as_ref()[7]
+    }
+
+    /// Source IP address.
+    pub fn source(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[8..24]);
+
+        Ipv6Addr::from(octets)
+    }
+
+    /// Destination IP address.
+    pub fn destination(&self) -> Ipv6Addr {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(&self.buffer.as_ref()[24..40]);
+
+        Ipv6Addr::from(octets)
+    }
+
+    /// Final destination IP address, the last address of the Routing header
+    /// while segments are left, as used by upper layer checksums.
+    pub fn final_destination(&self) -> Ipv6Addr {
+        for extension in self.extensions() {
+            let routing = match extension {
+                Ok(Extension::Routing(routing)) => routing,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+
+            if routing.segments_left() == 0 {
+                continue;
+            }
+
+            // Segment routing headers list the segments in reverse order.
+            let last = match routing.kind() {
+                extension::routing::Kind::SourceRoute | extension::routing::Kind::Type2 => routing.addresses().last(),
+                extension::routing::Kind::Segment => routing.addresses().next(),
+                _ => None,
+            };
+
+            if let Some(address) = last {
+                return address;
+            }
+        }
+
+        self.destination()
+    }
+
+    /// Extension headers following the fixed header.
+    pub fn extensions(&self) -> ExtensionIter {
+        ExtensionIter {
+            protocol: self.next_header(),
+            buffer: self.payload(),
+            done: false,
+        }
+    }
+
+    /// Protocol and payload of the upper layer, after skipping all the
+    /// extension headers.
+    ///
+    /// # Note
+    ///
+    /// The contents of an ESP header are encrypted, so if one is present the
+    /// returned protocol is ESP and the payload starts with the ESP header.
+    pub fn upper_layer(&self) -> Result<(Protocol, &[u8])> {
+        let mut extensions = self.extensions();
+
+        for extension in &mut extensions {
+            extension?;
+        }
+
+        Ok((extensions.protocol, extensions.buffer))
+    }
+
+    /// Split the packet into fragments that fit in the given MTU, using the
+    /// given identification.
+    pub fn fragments(&self, mtu: usize, identification: u32) -> Result<Fragments> {
+        Fragments::new(self, mtu, identification)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Traffic class.
+    pub fn set_traffic_class(&mut self, value: u8) -> Result<&mut Self> {
+        let buffer = self.buffer.as_mut();
+
+        buffer[0] = (buffer[0] & 0b1111_0000) | (value >> 4);
+        buffer[1] = (buffer[1] & 0b0000_1111) | (value << 4);
+
+        Ok(self)
+    }
+
+    /// Flow label.
+    pub fn set_flow_label(&mut self, value: u32) -> Result<&mut Self> {
+        if value > 0xf_ffff {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let old = (&self.buffer.as_ref()[0..]).read_u32::<BigEndian>()?;
+        Cursor::new(&mut self.buffer.as_mut()[0..]).write_u32::<BigEndian>((old & 0xfff0_0000) | value)?;
+
+        Ok(self)
+    }
+
+    /// Length of the payload.
+    pub fn set_payload_length(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.buffer.as_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Protocol of the next header.
+    pub fn set_next_header(&mut self, value: Protocol) -> Result<&mut Self> {
+        self.buffer.as_mut()[6] = value.into();
+
+        Ok(self)
+    }
+
+    /// Hop limit.
+    pub fn set_hop_limit(&mut self, value: u8) -> Result<&mut Self> {
+        self.buffer.as_mut()[7] = value;
+
+        Ok(self)
+    }
+
+    /// Source address.
+    pub fn set_source(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+        self.buffer.as_mut()[8..24].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+
+    /// Destination address.
+    pub fn set_destination(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+        self.buffer.as_mut()[24..40].copy_from_slice(&value.octets());
+
+        Ok(self)
+    }
+}
+
+/// Iterator over IPv6 extension headers.
+pub struct ExtensionIter<'a> {
+    protocol: Protocol,
+    buffer: &'a [u8],
+    done: bool,
+}
+
+impl<'a> Iterator for ExtensionIter<'a> {
+    type Item = Result<Extension<&'a [u8]>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match self.protocol {
+            Protocol::Hopopt => extension::Options::new(self.buffer).map(Extension::HopByHop),
+
+            Protocol::Ipv6Route => extension::Routing::new(self.buffer).map(Extension::Routing),
+
+            Protocol::Ipv6Frag => extension::Fragment::new(self.buffer).map(Extension::Fragment),
+
+            Protocol::Ipv6Opts => extension::Options::new(self.buffer).map(Extension::DestinationOptions),
+
+            Protocol::Ah => extension::Authentication::new(self.buffer).map(Extension::Authentication),
+
+            Protocol::Esp => extension::Esp::new(self.buffer).map(Extension::Esp),
+
+            _ => return None,
+        };
+
+        match result {
+            Ok(extension) => {
+                let next = match extension {
+                    Extension::HopByHop(ref header) | Extension::DestinationOptions(ref header) => header.next_header(),
+                    Extension::Routing(ref header) => header.next_header(),
+                    Extension::Fragment(ref header) => header.next_header(),
+                    Extension::Authentication(ref header) => header.next_header(),
+
+                    // Whatever follows is encrypted, so the chain ends here.
+                    Extension::Esp(_) => {
+                        self.done = true;
+                        return Some(Ok(extension));
+                    }
+                };
+
+                self.buffer = &self.buffer[extension.as_ref().len()..];
+                self.protocol = next;
+
+                Some(Ok(extension))
+            }
+
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ip;
+    use crate::packet::Packet;
+    use std::net::Ipv6Addr;
+
+    const RAW: [u8; 44] = [
+        0x60, 0xa1, 0x23, 0x45, 0x00, 0x04, 0x11, 0x40, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xde, 0xad, 0xbe, 0xef,
+    ];
+
+    #[test]
+    fn short_packet() {
+        assert!(ip::v6::Packet::no_payload(&[0x60; 20][..]).is_err());
+        assert!(ip::v6::Packet::no_payload(&[0x60; 39][..]).is_err());
+        assert!(ip::v6::Packet::no_payload(&[0x60; 40][..]).is_ok());
+        assert!(ip::v6::Packet::no_payload(&[0x40; 40][..]).is_err());
+
+        assert!(ip::v6::Packet::new(&RAW[..43]).is_err());
+        assert!(ip::v6::Packet::new(&RAW[..]).is_ok());
+    }
+
+    #[test]
+    fn values() {
+        let ip = ip::v6::Packet::new(&RAW[..]).unwrap();
+
+        assert_eq!(ip.version(), 6);
+        assert_eq!(ip.traffic_class(), 0x0a);
+        assert_eq!(ip.flow_label(), 0x12345);
+        assert_eq!(ip.payload_length(), 4);
+        assert_eq!(ip.next_header(), ip::Protocol::Udp);
+        assert_eq!(ip.hop_limit(), 64);
+        assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(ip.payload(), &[0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn generic() {
+        let ip = ip::Packet::new(&RAW[..]).unwrap();
+
+        if let ip::Packet::V6(ref packet) = ip {
+            assert_eq!(packet.hop_limit(), 64);
+        } else {
+            panic!("not an IPv6 packet");
+        }
+
+        assert_eq!(ip.header().len(), 40);
+        assert_eq!(ip.payload().len(), 4);
+    }
+
+    #[test]
+    fn mutable() {
+        let mut raw = RAW;
+        let mut ip = ip::v6::Packet::new(&mut raw[..]).unwrap();
+
+        ip.set_traffic_class(0xb8).unwrap();
+        ip.set_flow_label(0xabcde).unwrap();
+        ip.set_hop_limit(1).unwrap();
+        ip.set_destination("ff02::1".parse().unwrap()).unwrap();
+
+        assert_eq!(ip.version(), 6);
+        assert_eq!(ip.traffic_class(), 0xb8);
+        assert_eq!(ip.flow_label(), 0xabcde);
+        assert_eq!(ip.hop_limit(), 1);
+        assert_eq!(ip.destination(), "ff02::1".parse::<Ipv6Addr>().unwrap());
+        assert!(ip.set_flow_label(0x10_0000).is_err());
+    }
+
+    #[test]
+    fn extensions() {
+        use crate::ip::v6::extension::{option, routing};
+        use crate::ip::v6::Extension;
+
+        let mut raw = vec![0x60, 0x00, 0x00, 0x00, 0x00, 0x34, 0x00, 0x40];
+        raw.extend_from_slice(&"2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        raw.extend_from_slice(&"2001:db8::2".parse::<Ipv6Addr>().unwrap().octets());
+
+        // Hop-by-Hop with a Router Alert and padding.
+        raw.extend_from_slice(&[0x2b, 0x00, 0x05, 0x02, 0x00, 0x00, 0x01, 0x00]);
+        // Type 2 routing header.
+        raw.extend_from_slice(&[0x2c, 0x02, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        raw.extend_from_slice(&"2001:db8::3".parse::<Ipv6Addr>().unwrap().octets());
+        // Fragment.
+        raw.extend_from_slice(&[0x3c, 0x00, 0x00, 0x81, 0xde, 0xad, 0xbe, 0xef]);
+        // Destination Options with both kinds of padding.
+        raw.extend_from_slice(&[0x11, 0x00, 0x00, 0x00, 0x01, 0x02, 0x00, 0x00]);
+        // Upper layer.
+        raw.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+        let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(extensions.len(), 4);
+
+        match extensions[0] {
+            Extension::HopByHop(ref header) => {
+                let options = header.options().collect::<Result<Vec<_>, _>>().unwrap();
+                assert_eq!(options.len(), 2);
+                assert_eq!(options[0].number(), option::Number::RouterAlert);
+                assert_eq!(options[0].payload(), &[0x00, 0x00]);
+                assert_eq!(options[1].number(), option::Number::PadN);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        match extensions[1] {
+            Extension::Routing(ref header) => {
+                assert_eq!(header.kind(), routing::Kind::Type2);
+                assert_eq!(header.segments_left(), 1);
+                assert_eq!(header.addresses().collect::<Vec<_>>(), vec!["2001:db8::3".parse::<Ipv6Addr>().unwrap()]);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        match extensions[2] {
+            Extension::Fragment(ref header) => {
+                assert_eq!(header.offset(), 0x10);
+                assert!(header.more_fragments());
+                assert_eq!(header.identification(), 0xdeadbeef);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        match extensions[3] {
+            Extension::DestinationOptions(ref header) => {
+                let numbers = header.options().map(|o| o.unwrap().number()).collect::<Vec<_>>();
+                assert_eq!(numbers, vec![option::Number::Pad1, option::Number::Pad1, option::Number::PadN]);
+            }
+
+            ref other => panic!("unexpected {:?}", other),
+        }
+
+        let (protocol, payload) = ip.upper_layer().unwrap();
+        assert_eq!(protocol, ip::Protocol::Udp);
+        assert_eq!(payload, &[0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn extensions_esp() {
+        let mut raw = RAW;
+        raw[6] = 0x32;
+
+        let ip = ip::v6::Packet::no_payload(&raw[..]).unwrap();
+        assert!(ip.extensions().next().unwrap().is_err());
+
+        let mut raw = RAW.to_vec();
+        raw[5] = 12;
+        raw[6] = 0x32;
+        raw.extend_from_slice(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02]);
+
+        let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+        assert_eq!(ip.extensions().count(), 1);
+
+        let (protocol, payload) = ip.upper_layer().unwrap();
+        assert_eq!(protocol, ip::Protocol::Esp);
+        assert_eq!(payload.len(), 12);
+    }
+
+    #[test]
+    fn extensions_truncated() {
+        let mut raw = RAW;
+        raw[6] = 0x00;
+
+        let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+        assert!(ip.upper_layer().is_err());
     }
 }
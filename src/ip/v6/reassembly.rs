@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::net::Ipv6Addr;
+use std::time::{Duration, Instant};
+
+use crate::error::*;
+use crate::ip::v6::{Extension, Packet};
+use crate::ip::Protocol;
+
+/// Key identifying the fragments of a packet.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Key {
+    /// Source address.
+    pub source: Ipv6Addr,
+
+    /// Destination address.
+    pub destination: Ipv6Addr,
+
+    /// Identification from the Fragment header.
+    pub identification: u32,
+}
+
+/// IPv6 packet reassembler.
+///
+/// # Note
+///
+/// Overlapping fragments are handled as RFC 5722 mandates, the whole packet
+/// is discarded and any further fragment of it is rejected until it times
+/// out. Exact duplicates of a fragment already held are silently dropped
+/// instead.
+///
+/// Time is supplied by the caller, packets are expired lazily when new
+/// fragments are pushed or when `expire` is called.
+#[derive(Debug)]
+pub struct Reassembler {
+    timeout: Duration,
+    memory: usize,
+
+    used: usize,
+    datagrams: HashMap<Key, Datagram>,
+}
+
+#[derive(Debug)]
+struct Datagram {
+    start: Instant,
+    end: Option<usize>,
+    discarded: bool,
+    fragments: Vec<Fragment>,
+}
+
+#[derive(Debug)]
+struct Fragment {
+    offset: usize,
+    header: Option<(Vec<u8>, usize, Protocol)>,
+    payload: Vec<u8>,
+}
+
+impl Fragment {
+    fn range(&self) -> (usize, usize) {
+        (self.offset, self.offset + self.payload.len())
+    }
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Reassembler::new()
+    }
+}
+
+impl Reassembler {
+    /// Create a reassembler with a 60 seconds timeout and a 4 MiB memory
+    /// limit.
+    pub fn new() -> Reassembler {
+        Reassembler {
+            timeout: Duration::from_secs(60),
+            memory: 4 * 1024 * 1024,
+
+            used: 0,
+            datagrams: HashMap::new(),
+        }
+    }
+
+    /// Time after the first fragment of a packet when it's dropped.
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Maximum amount of payload octets held, the oldest packets are dropped
+    /// to make room for new fragments.
+    pub fn memory(mut self, value: usize) -> Self {
+        self.memory = value;
+        self
+    }
+
+    /// Amount of payload octets currently held.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Number of packets waiting for fragments.
+    pub fn pending(&self) -> usize {
+        self.datagrams.len()
+    }
+
+    /// Drop the packets that timed out, returning their keys.
+    pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+        let timeout = self.timeout;
+        let expired = self
+            .datagrams
+            .iter()
+            .filter(|(_, datagram)| now.saturating_duration_since(datagram.start) >= timeout)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for key in &expired {
+            self.remove(key);
+        }
+
+        expired
+    }
+
+    /// Push a packet, returning the complete packet if it was the missing
+    /// fragment.
+    ///
+    /// Packets without a Fragment header are returned as they are, atomic
+    /// fragments are returned without it.
+    pub fn push<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>, now: Instant) -> Result<Option<Packet<Vec<u8>>>> {
+        let buffer = packet.as_ref();
+        let mut position = 40;
+        let mut next = 6;
+        let mut found = None;
+
+        for extension in packet.extensions() {
+            let extension = extension?;
+            let start = position;
+            position += extension.as_ref().len();
+
+            if let Extension::Fragment(ref header) = extension {
+                found = Some((start, header.offset() as usize * 8, header.more_fragments(), header.identification(), header.next_header()));
+                break;
+            }
+
+            next = start;
+        }
+
+        let (start, offset, more, identification, protocol) = match found {
+            Some(fragment) => fragment,
+            None => return Ok(Some(packet.to_owned())),
+        };
+
+        let header = &buffer[..start];
+        let payload = &buffer[start + 8..];
+
+        if !more && offset == 0 {
+            return Ok(Some(assemble(header, next, protocol, payload)));
+        }
+
+        self.expire(now);
+
+        let key = Key {
+            source: packet.source(),
+            destination: packet.destination(),
+            identification,
+        };
+
+        if (more && payload.len() % 8 != 0) || offset + payload.len() + start - 40 > u16::MAX as usize {
+            self.discard(&key, now);
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if self.datagrams.get(&key).is_some_and(|d| d.discarded) {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if self.datagrams.get(&key).is_some_and(|d| d.is_duplicate(offset, more, payload)) {
+            return Ok(None);
+        }
+
+        if payload.len() > self.memory {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        while self.used + payload.len() > self.memory {
+            self.evict(&key);
+        }
+
+        let datagram = self.datagrams.entry(key).or_insert_with(|| Datagram {
+            start: now,
+            end: None,
+            discarded: false,
+            fragments: Vec::new(),
+        });
+
+        let range = (offset, offset + payload.len());
+        let invalid = match datagram.end {
+            Some(last) => range.1 > last || (!more && range.1 != last),
+            None => !more && datagram.fragments.iter().any(|f| f.range().1 > range.1),
+        };
+
+        let overlaps = datagram.fragments.iter().map(Fragment::range).any(|(start, end)| range.0 < end && start < range.1);
+
+        if invalid || overlaps {
+            self.discard(&key, now);
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        if !more {
+            datagram.end = Some(range.1);
+        }
+
+        datagram.fragments.push(Fragment {
+            offset,
+            header: if offset == 0 { Some((header.to_vec(), next, protocol)) } else { None },
+            payload: payload.to_vec(),
+        });
+        self.used += payload.len();
+
+        if !datagram.is_complete() {
+            return Ok(None);
+        }
+
+        let mut datagram = self.remove(&key).unwrap();
+        datagram.fragments.sort_by_key(|f| f.offset);
+
+        let (header, next, protocol) = datagram.fragments[0].header.take().unwrap();
+        let payload = datagram.fragments.iter().flat_map(|f| f.payload.iter().cloned()).collect::<Vec<_>>();
+
+        Ok(Some(assemble(&header, next, protocol, &payload)))
+    }
+
+    fn remove(&mut self, key: &Key) -> Option<Datagram> {
+        let datagram = self.datagrams.remove(key)?;
+        self.used -= datagram.fragments.iter().map(|f| f.payload.len()).sum::<usize>();
+
+        Some(datagram)
+    }
+
+    /// Drop the fragments of a packet, remembering it so the fragments still
+    /// to come are rejected as well.
+    fn discard(&mut self, key: &Key, now: Instant) {
+        let start = self.remove(key).map(|d| d.start).unwrap_or(now);
+
+        self.datagrams.insert(
+            *key,
+            Datagram {
+                start,
+                end: None,
+                discarded: true,
+                fragments: Vec::new(),
+            },
+        );
+    }
+
+    /// Drop the oldest packet, preferring any but the given one.
+    fn evict(&mut self, key: &Key) {
+        let oldest = self
+            .datagrams
+            .iter()
+            .filter(|(_, datagram)| !datagram.fragments.is_empty())
+            .min_by_key(|(k, datagram)| (*k == key, datagram.start))
+            .map(|(k, _)| *k);
+
+        if let Some(oldest) = oldest {
+            self.remove(&oldest);
+        }
+    }
+}
+
+impl Datagram {
+    fn is_duplicate(&self, offset: usize, more: bool, payload: &[u8]) -> bool {
+        let end = offset + payload.len();
+
+        more != (self.end == Some(end)) && self.fragments.iter().any(|f| f.offset == offset && f.payload == payload)
+    }
+
+    fn is_complete(&self) -> bool {
+        let end = match self.end {
+            Some(end) => end,
+            None => return false,
+        };
+
+        // Fragments never overlap, so they only have to add up.
+        self.fragments.iter().any(|f| f.offset == 0) && self.fragments.iter().map(|f| f.payload.len()).sum::<usize>() == end
+    }
+}
+
+/// Rebuild a packet from the unfragmentable part of its first fragment and
+/// the reassembled payload.
+fn assemble(header: &[u8], next: usize, protocol: Protocol, payload: &[u8]) -> Packet<Vec<u8>> {
+    let mut buffer = header.to_vec();
+    buffer[next] = protocol.into();
+    buffer.extend_from_slice(payload);
+
+    let length = (buffer.len() - 40) as u16;
+    let mut packet = Packet::unchecked(buffer);
+    packet.set_payload_length(length).unwrap();
+
+    packet
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::Reassembler;
+    use crate::ip;
+    use crate::ip::v6::fixture::{fragment, packet};
+    use crate::packet::Packet;
+
+    #[test]
+    fn roundtrip() {
+        let payload = (0..3000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let raw = packet(&payload);
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+
+        let mut fragments = packet.fragments(1280, 7).unwrap().collect::<Vec<_>>();
+        fragments.swap(0, 2);
+
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+        assert!(reassembler.push(&fragments[0], now).unwrap().is_none());
+        assert!(reassembler.push(&fragments[1], now).unwrap().is_none());
+        assert_eq!(reassembler.pending(), 1);
+
+        let result = reassembler.push(&fragments[2], now).unwrap().unwrap();
+        assert_eq!(result.as_ref(), &raw[..]);
+        assert_eq!(result.upper_layer().unwrap(), (ip::Protocol::Udp, &payload[..]));
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.used(), 0);
+    }
+
+    #[test]
+    fn unfragmented() {
+        let raw = packet(b"hello");
+        let packet = ip::v6::Packet::new(&raw[..]).unwrap();
+
+        let mut reassembler = Reassembler::new();
+        assert_eq!(reassembler.push(&packet, Instant::now()).unwrap().unwrap().as_ref(), &raw[..]);
+    }
+
+    #[test]
+    fn atomic() {
+        let mut reassembler = Reassembler::new();
+        let result = reassembler.push(&fragment(0, false, b"hello"), Instant::now()).unwrap().unwrap();
+
+        assert_eq!(result.next_header(), ip::Protocol::Udp);
+        assert_eq!(result.payload_length(), 5);
+        assert_eq!(result.payload(), b"hello");
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn overlap() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(reassembler.push(&fragment(0, true, &[0; 16]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(1, true, &[1; 16]), now).is_err());
+        assert_eq!(reassembler.used(), 0);
+
+        // The rest of the packet is rejected too, even if it doesn't overlap.
+        assert!(reassembler.push(&fragment(2, false, &[2; 8]), now).is_err());
+        assert_eq!(reassembler.pending(), 1);
+
+        // Until the packet times out.
+        let later = now + Duration::from_secs(60);
+        assert!(reassembler.expire(later).len() == 1);
+        assert!(reassembler.push(&fragment(0, true, &[0; 16]), later).unwrap().is_none());
+        assert!(reassembler.push(&fragment(2, false, &[2; 8]), later).unwrap().is_some());
+    }
+
+    #[test]
+    fn duplicate() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(reassembler.push(&fragment(0, true, &[0; 16]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(0, true, &[0; 16]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(3, false, &[3; 8]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(3, false, &[3; 8]), now).unwrap().is_none());
+        assert_eq!(reassembler.used(), 24);
+
+        // Same offset and length but different content is still an overlap.
+        assert!(reassembler.push(&fragment(0, true, &[1; 16]), now).is_err());
+        assert_eq!(reassembler.used(), 0);
+    }
+
+    #[test]
+    fn timeout() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().timeout(Duration::from_secs(10));
+
+        assert!(reassembler.push(&fragment(0, true, &[0; 8]), now).unwrap().is_none());
+        assert!(reassembler.expire(now + Duration::from_secs(9)).is_empty());
+        assert!(reassembler.push(&fragment(1, false, &[0; 8]), now + Duration::from_secs(10)).unwrap().is_none());
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn memory() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().memory(16);
+
+        assert!(reassembler.push(&fragment(0, true, &[0; 16]), now).unwrap().is_none());
+        assert!(reassembler.push(&fragment(0, true, &[0; 24]), now).is_err());
+        assert_eq!(reassembler.used(), 16);
+    }
+}
@@ -1,3 +1,6 @@
+// No minimum Rust version is declared, so keep to manual integer rounding.
+#![allow(clippy::manual_is_multiple_of, clippy::manual_div_ceil)]
+
 #[macro_use]
 extern crate error_chain;
 #[macro_use]
@@ -33,8 +36,14 @@ pub mod ip;
 /// ICMP packet parser and builder.
 pub mod icmp;
 
+/// ICMPv6 packet parser and builder.
+pub mod icmpv6;
+
 /// TCP packet parser and builder.
 pub mod tcp;
 
 /// UDP packet parser and builder.
 pub mod udp;
+
+/// UDP-Lite packet parser and builder.
+pub mod udplite;
@@ -0,0 +1,485 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::ip;
+use crate::packet::Packet as P;
+use crate::tcp::reassembly::Key;
+use crate::tcp::{flag, Packet};
+
+bitflags! {
+    /// Analysis flags of a segment.
+    pub struct Flags: u16 {
+        /// The segment carries data already sent.
+        const RETRANSMISSION = 0b0_0000_0001;
+
+        /// The segment is a retransmission asked by duplicate acknowledgments.
+        const FAST_RETRANSMISSION = 0b0_0000_0010;
+
+        /// The segment carries data already sent, but too soon to be a
+        /// retransmission.
+        const OUT_OF_ORDER = 0b0_0000_0100;
+
+        /// The segment acknowledges again what was already acknowledged.
+        const DUPLICATE_ACK = 0b0_0000_1000;
+
+        /// The segment advertises a zero window.
+        const ZERO_WINDOW = 0b0_0001_0000;
+
+        /// The segment fills the window of the receiver.
+        const WINDOW_FULL = 0b0_0010_0000;
+
+        /// The segment is a keep-alive.
+        const KEEP_ALIVE = 0b0_0100_0000;
+
+        /// The segment comes after data that was never seen.
+        const PREVIOUS_SEGMENT_LOST = 0b0_1000_0000;
+
+        /// The segment acknowledges data that was never seen.
+        const ACKED_UNSEEN = 0b1_0000_0000;
+    }
+}
+
+/// Analysis of a segment.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Analysis {
+    /// Analysis flags.
+    pub flags: Flags,
+
+    /// Number of duplicate acknowledgments so far, including this one.
+    pub duplicate: u32,
+
+    /// Window advertised, scaled.
+    pub window: u32,
+
+    /// Time since the data acknowledged by the segment was sent.
+    pub rtt: Option<Duration>,
+
+    /// Time since the timestamp echoed by the segment was sent.
+    pub timestamp_rtt: Option<Duration>,
+}
+
+/// TCP segment analyzer.
+///
+/// Packets of both directions are pushed in the order they were captured, and
+/// each gets analyzed against what was seen of its connection.
+///
+/// # Note
+///
+/// Windows are only scaled when the SYN of both ends were seen offering it.
+///
+/// Time is supplied by the caller, flows are expired lazily when new packets
+/// are pushed or when `expire` is called.
+#[derive(Debug)]
+pub struct Analyzer {
+    timeout: Duration,
+    reordering: Duration,
+
+    flows: HashMap<Key, Flow>,
+}
+
+#[derive(Default, Debug)]
+struct Flow {
+    last: Option<Instant>,
+
+    /// Whether the SYN was seen, and the window scale it offered.
+    syn: bool,
+    scale: Option<u8>,
+
+    /// Sequence number after the highest one sent, and when it was sent.
+    next: Option<(u32, Instant)>,
+
+    /// Last acknowledgment sent, and the amount of duplicates of it.
+    acknowledgment: Option<u32>,
+    duplicates: u32,
+
+    /// Last window advertised, scaled.
+    window: Option<u32>,
+
+    /// Segments waiting for an acknowledgment, as their end and when they
+    /// were sent, unless they were retransmitted.
+    unacknowledged: VecDeque<(u32, Option<Instant>)>,
+
+    /// Timestamps values sent, and when they were first sent.
+    timestamps: VecDeque<(u32, Instant)>,
+
+    rtt: Option<Duration>,
+}
+
+/// Maximum amount of timestamps values remembered for a flow.
+const TIMESTAMPS: usize = 64;
+
+/// Maximum amount of segments waiting for an acknowledgment remembered for a
+/// flow.
+const UNACKNOWLEDGED: usize = 1024;
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Analyzer::new()
+    }
+}
+
+impl Analyzer {
+    /// Create an analyzer with a 5 minutes idle timeout and a 3 milliseconds
+    /// reordering threshold.
+    pub fn new() -> Analyzer {
+        Analyzer {
+            timeout: Duration::from_secs(5 * 60),
+            reordering: Duration::from_millis(3),
+
+            flows: HashMap::new(),
+        }
+    }
+
+    /// Time without packets after which a flow is dropped.
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Minimum time after the highest segment within which an older segment
+    /// is out of order rather than a retransmission, the round trip time is
+    /// used when longer.
+    pub fn reordering(mut self, value: Duration) -> Self {
+        self.reordering = value;
+        self
+    }
+
+    /// Last round trip time measured for the flow of the given direction.
+    pub fn rtt(&self, key: &Key) -> Option<Duration> {
+        self.flows.get(key).and_then(|flow| flow.rtt)
+    }
+
+    /// Drop the flows that timed out, returning their keys.
+    pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+        let timeout = self.timeout;
+        let expired = self
+            .flows
+            .iter()
+            .filter(|(_, flow)| flow.last.is_some_and(|last| now.saturating_duration_since(last) >= timeout))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for key in &expired {
+            self.flows.remove(key);
+        }
+
+        expired
+    }
+
+    /// Push a packet, returning its analysis.
+    pub fn push<BI: AsRef<[u8]>, BT: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>, packet: &Packet<BT>, now: Instant) -> Analysis {
+        self.expire(now);
+
+        let key = Key::new(ip, packet);
+        let mut reverse = self.flows.remove(&key.reverse()).unwrap_or_default();
+        let mut flow = self.flows.remove(&key).unwrap_or_default();
+
+        let analysis = analyze(&mut flow, &mut reverse, packet, now, self.reordering);
+
+        flow.last = Some(now);
+        self.flows.insert(key, flow);
+
+        if reverse.last.is_some() {
+            self.flows.insert(key.reverse(), reverse);
+        }
+
+        analysis
+    }
+}
+
+fn analyze<B: AsRef<[u8]>>(flow: &mut Flow, reverse: &mut Flow, packet: &Packet<B>, now: Instant, reordering: Duration) -> Analysis {
+    let flags = packet.flags();
+    let sequence = packet.sequence();
+    let length = packet.payload().len() as u32;
+    let control = flags.intersects(flag::SYN | flag::FIN | flag::RST);
+    let ack = flags.contains(flag::ACK);
+
+    let mut result = Flags::empty();
+    let mut duplicate = 0;
+
+    if flags.contains(flag::SYN) {
+        flow.syn = true;
+        flow.scale = packet.window_scale();
+    }
+
+    // The window of the SYN itself is never scaled.
+    let window = if flags.contains(flag::SYN) {
+        u32::from(packet.window())
+    } else if flow.syn && reverse.syn && flow.scale.is_some() && reverse.scale.is_some() {
+        u32::from(packet.window()) << flow.scale.unwrap().min(14)
+    } else {
+        u32::from(packet.window())
+    };
+
+    if window == 0 && !control {
+        result |= Flags::ZERO_WINDOW;
+    }
+
+    if let Some((next, _)) = flow.next {
+        if after(sequence, next) && !flags.contains(flag::RST) {
+            result |= Flags::PREVIOUS_SEGMENT_LOST;
+        }
+
+        if length <= 1 && !control && sequence == next.wrapping_sub(1) {
+            result |= Flags::KEEP_ALIVE;
+        }
+    }
+
+    if let (Some(acknowledgment), Some(limit)) = (reverse.acknowledgment, reverse.window) {
+        if length > 0 && !control && sequence.wrapping_add(length) == acknowledgment.wrapping_add(limit) {
+            result |= Flags::WINDOW_FULL;
+        }
+    }
+
+    if ack && length == 0 && !control && flow.acknowledgment == Some(packet.acknowledgment()) && flow.window == Some(window) && flow.next.map(|(next, _)| next) == Some(sequence) {
+        flow.duplicates += 1;
+        duplicate = flow.duplicates;
+        result |= Flags::DUPLICATE_ACK;
+    } else if ack && flow.acknowledgment != Some(packet.acknowledgment()) {
+        flow.duplicates = 0;
+    }
+
+    if let Some((next, _)) = reverse.next {
+        if ack && after(packet.acknowledgment(), next) {
+            result |= Flags::ACKED_UNSEEN;
+        }
+    }
+
+    let consumed = length + flags.intersects(flag::SYN | flag::FIN) as u32;
+    let end = sequence.wrapping_add(consumed);
+
+    if let Some((next, time)) = flow.next {
+        if consumed > 0 && after(next, sequence) && !result.contains(Flags::KEEP_ALIVE) {
+            let threshold = flow.rtt.map_or(reordering, |rtt| rtt.max(reordering));
+
+            if reverse.duplicates >= 2 && reverse.acknowledgment == Some(sequence) {
+                result |= Flags::FAST_RETRANSMISSION;
+            } else if now.saturating_duration_since(time) < threshold && next != end {
+                result |= Flags::OUT_OF_ORDER;
+            } else {
+                result |= Flags::RETRANSMISSION;
+            }
+        }
+    }
+
+    let retransmitted = result.intersects(Flags::RETRANSMISSION | Flags::FAST_RETRANSMISSION | Flags::OUT_OF_ORDER);
+
+    if consumed > 0 {
+        let advanced = match flow.next {
+            Some((next, _)) => after(end, next),
+            None => true,
+        };
+
+        if advanced {
+            flow.next = Some((end, now));
+        }
+
+        // Karn's algorithm, retransmitted data doesn't give a round trip time.
+        if retransmitted {
+            for entry in flow.unacknowledged.iter_mut().filter(|(other, _)| !after(*other, end) && after(*other, sequence)) {
+                entry.1 = None;
+            }
+        } else {
+            if flow.unacknowledged.len() == UNACKNOWLEDGED {
+                flow.unacknowledged.pop_front();
+            }
+
+            flow.unacknowledged.push_back((end, Some(now)));
+        }
+    }
+
+    // Round trip time of the data acknowledged.
+    let mut rtt = None;
+
+    if ack && !result.contains(Flags::DUPLICATE_ACK) {
+        while let Some(&(other, sent)) = reverse.unacknowledged.front() {
+            if after(other, packet.acknowledgment()) {
+                break;
+            }
+
+            if other == packet.acknowledgment() {
+                rtt = sent.map(|sent| now.saturating_duration_since(sent));
+            }
+
+            reverse.unacknowledged.pop_front();
+        }
+    }
+
+    // Round trip time of the timestamp echoed.
+    let mut timestamp_rtt = None;
+
+    if let Some((value, echo)) = packet.timestamps() {
+        if !flow.timestamps.iter().any(|&(other, _)| other == value) {
+            if flow.timestamps.len() == TIMESTAMPS {
+                flow.timestamps.pop_front();
+            }
+
+            flow.timestamps.push_back((value, now));
+        }
+
+        if ack {
+            if let Some(index) = reverse.timestamps.iter().position(|&(other, _)| other == echo) {
+                timestamp_rtt = Some(now.saturating_duration_since(reverse.timestamps[index].1));
+                reverse.timestamps.drain(..=index);
+            }
+        }
+    }
+
+    if let Some(rtt) = rtt.or(timestamp_rtt) {
+        reverse.rtt = Some(rtt);
+    }
+
+    if ack {
+        flow.acknowledgment = Some(packet.acknowledgment());
+    }
+
+    flow.window = Some(window);
+
+    Analysis {
+        flags: result,
+        duplicate,
+        window,
+        rtt,
+        timestamp_rtt,
+    }
+}
+
+/// Whether the sequence number `a` comes after `b`, accounting for
+/// wraparound.
+fn after(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) > 0
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::error::*;
+    use crate::packet::Packet;
+    use crate::tcp::analysis::{Analysis, Analyzer, Flags};
+    use crate::tcp::fixture::{self, none};
+    use crate::tcp::{self, flag};
+
+    /// Push a segment from the client when `client` is set, or from the
+    /// server, with the given sequence, acknowledgment and window.
+    fn push<F>(analyzer: &mut Analyzer, client: bool, segment: (u32, u32, u16), flags: tcp::Flags, payload: &[u8], options: F, now: Instant) -> Analysis
+    where
+        F: FnOnce(tcp::Builder) -> Result<tcp::Builder>,
+    {
+        let packet = fixture::segment(client, segment, flags, payload, options);
+        let tcp = tcp::Packet::new(packet.payload()).unwrap();
+
+        analyzer.push(&packet, &tcp, now)
+    }
+
+    /// Open a connection with the client at sequence 100 and the server at
+    /// sequence 500.
+    fn handshake(analyzer: &mut Analyzer, now: Instant) {
+        push(analyzer, true, (100, 0, 1000), flag::SYN, b"", |b| b.window_scale(2), now);
+        push(analyzer, false, (500, 101, 2000), flag::SYN | flag::ACK, b"", |b| b.window_scale(4), now);
+        push(analyzer, true, (101, 501, 1000), flag::ACK, b"", none, now);
+    }
+
+    #[test]
+    fn window() {
+        let now = Instant::now();
+        let mut analyzer = Analyzer::new();
+
+        assert_eq!(push(&mut analyzer, true, (100, 0, 1000), flag::SYN, b"", |b| b.window_scale(2), now).window, 1000);
+        assert_eq!(push(&mut analyzer, false, (500, 101, 2000), flag::SYN | flag::ACK, b"", |b| b.window_scale(4), now).window, 2000);
+        assert_eq!(push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"", none, now).window, 4000);
+        assert_eq!(push(&mut analyzer, false, (501, 101, 10), flag::ACK, b"", none, now).window, 160);
+
+        let analysis = push(&mut analyzer, false, (501, 101, 0), flag::ACK, b"", none, now);
+        assert_eq!(analysis.flags, Flags::ZERO_WINDOW);
+
+        // Without the handshake the window is never scaled.
+        let mut analyzer = Analyzer::new();
+        assert_eq!(push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"", none, now).window, 1000);
+    }
+
+    #[test]
+    fn retransmission() {
+        let now = Instant::now();
+        let mut analyzer = Analyzer::new();
+        handshake(&mut analyzer, now);
+
+        let later = now + Duration::from_millis(100);
+        assert!(push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"hello", none, now).flags.is_empty());
+        assert_eq!(push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"hello", none, later).flags, Flags::RETRANSMISSION);
+
+        // Data sent after a gap, and an older segment right after.
+        assert_eq!(push(&mut analyzer, true, (110, 501, 1000), flag::ACK, b"abc", none, later).flags, Flags::PREVIOUS_SEGMENT_LOST);
+        assert_eq!(push(&mut analyzer, true, (106, 501, 1000), flag::ACK, b"abcd", none, later).flags, Flags::OUT_OF_ORDER);
+
+        // Keep-alive.
+        assert_eq!(push(&mut analyzer, true, (112, 501, 1000), flag::ACK, b"", none, later).flags, Flags::KEEP_ALIVE);
+
+        // Acknowledgment of data never sent.
+        assert_eq!(push(&mut analyzer, false, (501, 200, 2000), flag::ACK, b"", none, later).flags, Flags::ACKED_UNSEEN);
+    }
+
+    #[test]
+    fn fast_retransmission() {
+        let now = Instant::now();
+        let mut analyzer = Analyzer::new();
+        handshake(&mut analyzer, now);
+
+        push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"one", none, now);
+        push(&mut analyzer, true, (104, 501, 1000), flag::ACK, b"two", none, now);
+        push(&mut analyzer, true, (107, 501, 1000), flag::ACK, b"three", none, now);
+
+        assert!(push(&mut analyzer, false, (501, 104, 2000), flag::ACK, b"", none, now).flags.is_empty());
+
+        for count in 1..=3 {
+            let analysis = push(&mut analyzer, false, (501, 104, 2000), flag::ACK, b"", none, now);
+            assert_eq!(analysis.flags, Flags::DUPLICATE_ACK);
+            assert_eq!(analysis.duplicate, count);
+        }
+
+        let later = now + Duration::from_millis(100);
+        assert_eq!(push(&mut analyzer, true, (104, 501, 1000), flag::ACK, b"two", none, later).flags, Flags::FAST_RETRANSMISSION);
+        assert!(push(&mut analyzer, false, (501, 112, 2000), flag::ACK, b"", none, later).flags.is_empty());
+    }
+
+    #[test]
+    fn window_full() {
+        let now = Instant::now();
+        let mut analyzer = Analyzer::new();
+        push(&mut analyzer, true, (100, 0, 1000), flag::SYN, b"", none, now);
+        push(&mut analyzer, false, (500, 101, 8), flag::SYN | flag::ACK, b"", none, now);
+        push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"", none, now);
+        push(&mut analyzer, false, (501, 101, 8), flag::ACK, b"", none, now);
+
+        assert!(push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"abcd", none, now).flags.is_empty());
+        assert_eq!(push(&mut analyzer, true, (105, 501, 1000), flag::ACK, b"efgh", none, now).flags, Flags::WINDOW_FULL);
+    }
+
+    #[test]
+    fn rtt() {
+        let now = Instant::now();
+        let mut analyzer = Analyzer::new();
+        handshake(&mut analyzer, now);
+
+        let key = tcp::reassembly::Key {
+            source: "10.0.0.1".parse().unwrap(),
+            source_port: 1337,
+            destination: "10.0.0.2".parse().unwrap(),
+            destination_port: 80,
+        };
+
+        push(&mut analyzer, true, (101, 501, 1000), flag::ACK, b"hello", |b| b.timestamp(1, 0), now);
+        push(&mut analyzer, true, (106, 501, 1000), flag::ACK, b"world", |b| b.timestamp(2, 0), now + Duration::from_millis(5));
+
+        let analysis = push(&mut analyzer, false, (501, 111, 2000), flag::ACK, b"", |b| b.timestamp(7, 1), now + Duration::from_millis(25));
+        assert_eq!(analysis.rtt, Some(Duration::from_millis(20)));
+        assert_eq!(analysis.timestamp_rtt, Some(Duration::from_millis(25)));
+        assert_eq!(analyzer.rtt(&key), Some(Duration::from_millis(20)));
+
+        // Retransmitted data gives no round trip time.
+        let later = now + Duration::from_millis(100);
+        push(&mut analyzer, true, (111, 501, 1000), flag::ACK, b"again", none, later);
+        push(&mut analyzer, true, (111, 501, 1000), flag::ACK, b"again", none, later + Duration::from_millis(50));
+        assert_eq!(push(&mut analyzer, false, (501, 116, 2000), flag::ACK, b"", none, later + Duration::from_millis(60)).rtt, None);
+    }
+}
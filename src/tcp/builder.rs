@@ -1,5 +1,7 @@
 use byteorder::{BigEndian, WriteBytesExt};
 use std::io::Cursor;
+use std::net::IpAddr;
+use std::time::Duration;
 
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
@@ -7,6 +9,7 @@ use crate::error::*;
 use crate::ip;
 use crate::packet::{AsPacket, AsPacketMut};
 use crate::tcp::checksum;
+use crate::tcp::option::{self, mptcp};
 use crate::tcp::Flags;
 use crate::tcp::Packet;
 
@@ -49,6 +52,10 @@ impl<B: Buffer> Build<B> for Builder<B> {
     }
 
     fn build(mut self) -> Result<B::Inner> {
+        if !self.payload {
+            self.close()?;
+        }
+
         self.prepare();
 
         let mut buffer = self.buffer.into_inner();
@@ -118,12 +125,188 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
+    /// Add an option, the options are padded with No Operation octets once
+    /// they're complete.
+    pub fn option(mut self, number: option::Number, value: &[u8]) -> Result<Self> {
+        if value.len() > 38 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let start = self.extend(2 + value.len())?;
+
+        let data = &mut self.buffer.data_mut()[start..];
+        data[0] = number.into();
+        data[1] = (2 + value.len()) as u8;
+        data[2..].copy_from_slice(value);
+
+        Ok(self)
+    }
+
+    /// Add a No Operation option, used to align the following options.
+    pub fn no_operation(mut self) -> Result<Self> {
+        let start = self.extend(1)?;
+        self.buffer.data_mut()[start] = option::Number::NoOperation.into();
+
+        Ok(self)
+    }
+
+    /// Add an End of Option List option.
+    pub fn end(mut self) -> Result<Self> {
+        let start = self.extend(1)?;
+        self.buffer.data_mut()[start] = option::Number::End.into();
+
+        Ok(self)
+    }
+
+    /// Add a Maximum Segment Size option.
+    pub fn mss(self, value: u16) -> Result<Self> {
+        self.option(option::Number::MaximumSegmentSize, &value.to_be_bytes())
+    }
+
+    /// Add a Window Scale option with the given shift count.
+    pub fn window_scale(self, value: u8) -> Result<Self> {
+        if value > 14 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.option(option::Number::WindowScale, &[value])
+    }
+
+    /// Add a SACK-Permitted option.
+    pub fn sack_permitted(self) -> Result<Self> {
+        self.option(option::Number::SelectiveAcknowledgmentPermitted, &[])
+    }
+
+    /// Add a SACK option with the given left and right edges of the blocks.
+    pub fn sack<'a, T: IntoIterator<Item = &'a (u32, u32)>>(self, blocks: T) -> Result<Self> {
+        let value = blocks.into_iter().flat_map(|&(left, right)| [left.to_be_bytes(), right.to_be_bytes()].concat()).collect::<Vec<_>>();
+
+        if value.is_empty() {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.option(option::Number::SelectiveAcknowledgment, &value)
+    }
+
+    /// Add a Timestamps option.
+    pub fn timestamp(self, value: u32, echo: u32) -> Result<Self> {
+        self.option(option::Number::Timestamp, &[value.to_be_bytes(), echo.to_be_bytes()].concat())
+    }
+
+    /// Add a TCP MD5 Signature option with the given digest.
+    pub fn md5_signature(self, digest: &[u8]) -> Result<Self> {
+        if digest.len() != 16 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.option(option::Number::Md5Signature, digest)
+    }
+
+    /// Add a User Timeout option, in seconds if it fits and minutes otherwise.
+    pub fn user_timeout(self, value: Duration) -> Result<Self> {
+        let value = match value.as_secs() {
+            seconds if seconds <= 0x7fff => seconds as u16,
+            seconds if seconds / 60 <= 0x7fff => 0x8000 | (seconds / 60) as u16,
+            _ => return Err(ErrorKind::InvalidValue.into()),
+        };
+
+        self.option(option::Number::UserTimeout, &value.to_be_bytes())
+    }
+
+    /// Add a TCP Authentication Option.
+    pub fn authentication(self, key: u8, next: u8, mac: &[u8]) -> Result<Self> {
+        self.option(option::Number::Authentication, &[&[key, next][..], mac].concat())
+    }
+
+    /// Add a TCP Fast Open option, with an empty cookie to request one.
+    pub fn fast_open(self, cookie: &[u8]) -> Result<Self> {
+        if !cookie.is_empty() && (cookie.len() < 4 || cookie.len() > 16 || cookie.len() % 2 != 0) {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.option(option::Number::FastOpen, cookie)
+    }
+
+    /// Add an MP_CAPABLE option, with the sender key on SYN/ACK and both
+    /// keys on the third ACK.
+    pub fn mp_capable(self, version: u8, flags: mptcp::CapableFlags, sender: Option<u64>, receiver: Option<u64>) -> Result<Self> {
+        if version > 0b1111 || (sender.is_none() && receiver.is_some()) {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        let mut value = vec![multipath(mptcp::Subtype::Capable) | version, flags.bits()];
+        value.extend(sender.iter().chain(receiver.iter()).flat_map(|key| key.to_be_bytes()));
+
+        self.option(option::Number::Multipath, &value)
+    }
+
+    /// Add an MP_JOIN option for a SYN.
+    pub fn mp_join_syn(self, backup: bool, address: u8, token: u32, random: u32) -> Result<Self> {
+        let value = [&[multipath(mptcp::Subtype::Join) | backup as u8, address][..], &token.to_be_bytes(), &random.to_be_bytes()].concat();
+
+        self.option(option::Number::Multipath, &value)
+    }
+
+    /// Add an MP_JOIN option for a SYN/ACK, with the truncated HMAC.
+    pub fn mp_join_syn_ack(self, backup: bool, address: u8, hmac: u64, random: u32) -> Result<Self> {
+        let value = [&[multipath(mptcp::Subtype::Join) | backup as u8, address][..], &hmac.to_be_bytes(), &random.to_be_bytes()].concat();
+
+        self.option(option::Number::Multipath, &value)
+    }
+
+    /// Add an MP_JOIN option for the third ACK, with the full HMAC.
+    pub fn mp_join_ack(self, hmac: &[u8]) -> Result<Self> {
+        if hmac.len() != 20 {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.option(option::Number::Multipath, &[&[multipath(mptcp::Subtype::Join), 0][..], hmac].concat())
+    }
+
+    /// Add a DSS option, with 8 octets data acknowledgment and sequence
+    /// number.
+    pub fn data_sequence(self, acknowledgment: Option<u64>, mapping: Option<mptcp::Mapping>, fin: bool) -> Result<Self> {
+        let mut flags = if fin { 0b1_0000 } else { 0 };
+        let mut value = Vec::new();
+
+        if let Some(acknowledgment) = acknowledgment {
+            flags |= 0b11;
+            value.extend_from_slice(&acknowledgment.to_be_bytes());
+        }
+
+        if let Some(mapping) = mapping {
+            flags |= 0b1100;
+            value.extend_from_slice(&mapping.sequence.to_be_bytes());
+            value.extend_from_slice(&mapping.subflow.to_be_bytes());
+            value.extend_from_slice(&mapping.length.to_be_bytes());
+            value.extend(mapping.checksum.iter().flat_map(|checksum| checksum.to_be_bytes()));
+        }
+
+        self.option(option::Number::Multipath, &[&[multipath(mptcp::Subtype::DataSequence), flags][..], &value].concat())
+    }
+
+    /// Add an ADD_ADDR option, which is an echo when no HMAC is given.
+    pub fn add_address(self, id: u8, address: IpAddr, port: Option<u16>, hmac: Option<u64>) -> Result<Self> {
+        let mut value = vec![multipath(mptcp::Subtype::AddAddress) | hmac.is_none() as u8, id];
+
+        match address {
+            IpAddr::V4(address) => value.extend_from_slice(&address.octets()),
+            IpAddr::V6(address) => value.extend_from_slice(&address.octets()),
+        }
+
+        value.extend(port.iter().flat_map(|port| port.to_be_bytes()));
+        value.extend(hmac.iter().flat_map(|hmac| hmac.to_be_bytes()));
+
+        self.option(option::Number::Multipath, &value)
+    }
+
     /// Payload for the packet.
     pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
         if self.payload {
             return Err(ErrorKind::AlreadyDefined.into());
         }
 
+        self.close()?;
         self.payload = true;
 
         for byte in value {
@@ -134,6 +317,42 @@ impl<B: Buffer> Builder<B> {
         Ok(self)
     }
 
+    /// Grow the header for an option, returning where it starts.
+    fn extend(&mut self, size: usize) -> Result<usize> {
+        use crate::size::header::Max;
+
+        if self.payload {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let start = self.buffer.length();
+
+        if start + size > Packet::<()>::max() {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        self.buffer.more(size)?;
+        self.options = true;
+
+        Ok(start)
+    }
+
+    /// Pad the options and set the data offset, closing the header.
+    fn close(&mut self) -> Result<()> {
+        if self.options {
+            while self.buffer.length() % 4 != 0 {
+                self.buffer.more(1)?;
+                *self.buffer.data_mut().last_mut().unwrap() = option::Number::NoOperation.into();
+            }
+        }
+
+        let flags = self.buffer.data()[12] & 0b1111;
+        let offset = (self.buffer.length() / 4) as u8;
+        self.buffer.data_mut()[12] = offset << 4 | flags;
+
+        Ok(())
+    }
+
     fn prepare(&mut self) {
         let ip = self.ip;
         let length = self.buffer.length();
@@ -144,11 +363,6 @@ impl<B: Buffer> Builder<B> {
             let ip = &mut before[ip.0..];
             let tcp = &mut after[..length];
 
-            // Set the TCP data offset.
-            let flags = tcp[12] & 0b1111;
-            let offset = (length / 4) as u8;
-            tcp[12] = offset << 4 | flags;
-
             // Calculate the checksum by parsing back the IP packet and set it.
             let checksum = checksum(&ip::Packet::no_payload(&ip)?, tcp);
             Cursor::new(&mut tcp[16..]).write_u16::<BigEndian>(checksum)?;
@@ -158,13 +372,19 @@ impl<B: Buffer> Builder<B> {
     }
 }
 
+/// First octet of a Multipath TCP option with the given subtype.
+fn multipath(subtype: mptcp::Subtype) -> u8 {
+    let subtype: u8 = subtype.into();
+    subtype << 4
+}
+
 #[cfg(test)]
 mod test {
     use crate::builder::Builder;
     use crate::ip;
     use crate::packet::Packet;
     use crate::tcp;
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[test]
     fn simple() {
@@ -206,4 +426,203 @@ mod test {
         assert_eq!(tcp.flags(), tcp::flag::SYN);
         assert!(tcp.is_valid(&ip::Packet::from(&ip)));
     }
+
+    #[test]
+    fn v6() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(64)
+            .unwrap()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .tcp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .flags(tcp::flag::SYN)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(ip.payload_length(), 23);
+        assert_eq!(ip.next_header(), ip::Protocol::Tcp);
+        assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.source(), 1337);
+        assert_eq!(tcp.destination(), 9001);
+        assert_eq!(tcp.flags(), tcp::flag::SYN);
+        assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+    }
+
+    #[test]
+    fn options() {
+        let packet = ip::v4::Builder::default()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::SYN)
+            .unwrap()
+            .mss(1460)
+            .unwrap()
+            .sack_permitted()
+            .unwrap()
+            .timestamp(0x592b_2997, 0)
+            .unwrap()
+            .no_operation()
+            .unwrap()
+            .window_scale(7)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 60);
+
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.offset(), 10);
+        assert_eq!(tcp.flags(), tcp::flag::SYN);
+        assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+        assert_eq!(
+            &ip.payload()[20..],
+            &[0x02, 0x04, 0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03, 0x03, 0x07][..]
+        );
+
+        let numbers = tcp.options().map(|o| o.unwrap().number()).collect::<Vec<_>>();
+        assert_eq!(
+            numbers,
+            vec![
+                tcp::option::Number::MaximumSegmentSize,
+                tcp::option::Number::SelectiveAcknowledgmentPermitted,
+                tcp::option::Number::Timestamp,
+                tcp::option::Number::NoOperation,
+                tcp::option::Number::WindowScale,
+            ]
+        );
+    }
+
+    #[test]
+    fn options_padding() {
+        let packet = ip::v4::Builder::default()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::ACK)
+            .unwrap()
+            .window_scale(2)
+            .unwrap()
+            .payload(b"hello")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 20 + 24 + 5);
+
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.offset(), 6);
+        assert_eq!(tcp.flags(), tcp::flag::ACK);
+        assert_eq!(&ip.payload()[20..24], &[0x03, 0x03, 0x02, 0x01][..]);
+        assert_eq!(tcp.payload(), b"hello");
+        assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+    }
+
+    #[test]
+    fn options_limit() {
+        let builder = ip::v4::Builder::default().tcp().unwrap().timestamp(1, 2).unwrap().sack(&[(1, 2), (3, 4), (5, 6)]).unwrap().mss(1460).unwrap();
+        assert!(builder.no_operation().is_err());
+
+        let builder = ip::v4::Builder::default().tcp().unwrap().timestamp(1, 2).unwrap();
+        assert!(builder.sack(&[(1, 2), (3, 4), (5, 6), (7, 8)]).is_err());
+
+        assert!(ip::v4::Builder::default().tcp().unwrap().window_scale(15).is_err());
+        assert!(ip::v4::Builder::default().tcp().unwrap().payload(b"lol").unwrap().mss(1460).is_err());
+    }
+
+    #[test]
+    fn multipath() {
+        let packet = ip::v4::Builder::default()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::ACK)
+            .unwrap()
+            .data_sequence(
+                Some(0x1122334455667788),
+                Some(tcp::option::mptcp::Mapping {
+                    sequence: 42,
+                    subflow: 1,
+                    length: 3,
+                    checksum: None,
+                }),
+                true,
+            )
+            .unwrap()
+            .add_address(1, "10.0.0.1".parse().unwrap(), Some(8080), None)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+        assert_eq!(tcp.offset(), 5 + 9);
+        assert_eq!(tcp.payload(), b"lol");
+
+        let options = tcp.options().map(|o| o.unwrap()).collect::<Vec<_>>();
+        assert_eq!(options.len(), 2);
+
+        let multipath = options[0].multipath().unwrap();
+        let dss = multipath.data_sequence().unwrap();
+        assert!(dss.is_fin());
+        assert_eq!(dss.acknowledgment(), Some(0x1122334455667788));
+        assert_eq!(dss.mapping().unwrap().sequence, 42);
+        assert_eq!(dss.mapping().unwrap().checksum, None);
+
+        let multipath = options[1].multipath().unwrap();
+        let add = multipath.add_address().unwrap();
+        assert!(add.is_echo());
+        assert_eq!(add.address(), "10.0.0.1".parse::<std::net::IpAddr>().unwrap());
+        assert_eq!(add.port(), Some(8080));
+        assert_eq!(add.hmac(), None);
+
+        assert!(ip::v4::Builder::default().tcp().unwrap().add_address(1, "::1".parse().unwrap(), Some(1), Some(2)).is_ok());
+    }
+
+    #[test]
+    fn others() {
+        let packet = ip::v4::Builder::default()
+            .tcp()
+            .unwrap()
+            .flags(tcp::flag::SYN)
+            .unwrap()
+            .mss(1460)
+            .unwrap()
+            .fast_open(&[1, 2, 3, 4, 5, 6, 7, 8])
+            .unwrap()
+            .user_timeout(std::time::Duration::from_secs(600))
+            .unwrap()
+            .mp_capable(1, tcp::option::mptcp::CapableFlags::HMAC_SHA256, None, None)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        let options = tcp.options().map(|o| o.unwrap()).collect::<Vec<_>>();
+        assert_eq!(options[1].fast_open().unwrap(), &[1, 2, 3, 4, 5, 6, 7, 8][..]);
+        assert_eq!(options[2].user_timeout().unwrap(), std::time::Duration::from_secs(600));
+        assert_eq!(options[3].multipath().unwrap().capable().unwrap().version(), 1);
+
+        assert!(ip::v4::Builder::default().tcp().unwrap().fast_open(&[1, 2, 3]).is_err());
+        assert!(ip::v4::Builder::default().tcp().unwrap().md5_signature(&[0; 15]).is_err());
+        assert!(ip::v4::Builder::default().tcp().unwrap().mp_join_ack(&[0; 19]).is_err());
+        assert!(ip::v4::Builder::default().tcp().unwrap().user_timeout(std::time::Duration::from_secs(60 * 0x8000)).is_err());
+    }
 }
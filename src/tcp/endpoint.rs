@@ -0,0 +1,969 @@
+use std::cmp;
+use std::collections::VecDeque;
+use std::net::SocketAddrV4;
+use std::time::{Duration, Instant};
+
+use crate::builder::Builder as Build;
+use crate::error::*;
+use crate::ip;
+use crate::packet::Packet as P;
+use crate::tcp::{flag, Flags, Packet};
+
+/// State of an endpoint, as defined in RFC 793.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum State {
+    ///
+    Listen,
+
+    ///
+    SynSent,
+
+    ///
+    SynReceived,
+
+    ///
+    Established,
+
+    ///
+    FinWait1,
+
+    ///
+    FinWait2,
+
+    ///
+    CloseWait,
+
+    ///
+    Closing,
+
+    ///
+    LastAck,
+
+    ///
+    TimeWait,
+
+    ///
+    Closed,
+}
+
+/// Userspace TCP endpoint over IPv4.
+///
+/// The endpoint does no I/O, incoming packets are given to `push` and
+/// outgoing ones are taken from `poll` until it returns nothing, then `poll`
+/// has to be called again at the instant returned by `timeout` or when a
+/// packet arrives.
+///
+/// # Note
+///
+/// The send window is the one advertised by the peer, there's no congestion
+/// control and out of order segments are dropped.
+#[derive(Debug)]
+pub struct Endpoint {
+    local: SocketAddrV4,
+    remote: Option<SocketAddrV4>,
+    state: State,
+
+    /// Whether the handshake is still to complete after closing in
+    /// SynReceived.
+    handshake: bool,
+
+    mss: u16,
+    capacity: usize,
+    rto: Duration,
+    retries: u32,
+    linger: Duration,
+
+    /// Initial send sequence number.
+    initial: u32,
+
+    /// Oldest sequence number not acknowledged.
+    unacknowledged: u32,
+
+    /// Next sequence number to send.
+    next: u32,
+
+    /// Send window advertised by the peer, and its maximum segment size.
+    window: u32,
+    segment: u16,
+
+    /// Next sequence number expected.
+    expected: u32,
+
+    /// Data starting at the oldest octet not acknowledged.
+    outgoing: VecDeque<u8>,
+
+    /// Data received and not read yet.
+    incoming: VecDeque<u8>,
+
+    timer: Option<Instant>,
+    backoff: Duration,
+    attempts: u32,
+    probe: bool,
+    wait: Option<Instant>,
+
+    acknowledge: bool,
+    reset: Option<(SocketAddrV4, u32, Option<u32>)>,
+    was_reset: bool,
+}
+
+impl Endpoint {
+    fn new(local: SocketAddrV4, remote: Option<SocketAddrV4>, state: State, initial: u32) -> Endpoint {
+        Endpoint {
+            local,
+            remote,
+            state,
+
+            handshake: false,
+
+            mss: 1460,
+            capacity: 65535,
+            rto: Duration::from_secs(1),
+            retries: 5,
+            linger: Duration::from_secs(60),
+
+            initial,
+            unacknowledged: initial,
+            next: initial,
+
+            window: 0,
+            segment: 536,
+            expected: 0,
+
+            outgoing: VecDeque::new(),
+            incoming: VecDeque::new(),
+
+            timer: None,
+            backoff: Duration::from_secs(1),
+            attempts: 0,
+            probe: false,
+            wait: None,
+
+            acknowledge: false,
+            reset: None,
+            was_reset: false,
+        }
+    }
+
+    /// Create an endpoint connecting to the given remote, using the given
+    /// initial sequence number.
+    pub fn connect(local: SocketAddrV4, remote: SocketAddrV4, initial: u32) -> Endpoint {
+        Endpoint::new(local, Some(remote), State::SynSent, initial)
+    }
+
+    /// Create an endpoint waiting for a connection, using the given initial
+    /// sequence number.
+    pub fn listen(local: SocketAddrV4, initial: u32) -> Endpoint {
+        Endpoint::new(local, None, State::Listen, initial)
+    }
+
+    /// Maximum segment size to advertise, 1460 by default.
+    pub fn mss(mut self, value: u16) -> Self {
+        self.mss = value;
+        self
+    }
+
+    /// Size of the send and receive buffers, 65535 by default.
+    pub fn capacity(mut self, value: usize) -> Self {
+        self.capacity = value;
+        self
+    }
+
+    /// Initial retransmission timeout, doubled on every retransmission, 1
+    /// second by default.
+    pub fn rto(mut self, value: Duration) -> Self {
+        self.rto = value;
+        self.backoff = value;
+        self
+    }
+
+    /// Retransmissions after which the connection is dropped, 5 by default.
+    pub fn retries(mut self, value: u32) -> Self {
+        self.retries = value;
+        self
+    }
+
+    /// Time spent in TIME-WAIT, 60 seconds by default.
+    pub fn linger(mut self, value: Duration) -> Self {
+        self.linger = value;
+        self
+    }
+}
+
+impl Endpoint {
+    /// Local address.
+    pub fn local(&self) -> SocketAddrV4 {
+        self.local
+    }
+
+    /// Remote address, once known.
+    pub fn remote(&self) -> Option<SocketAddrV4> {
+        self.remote
+    }
+
+    /// Current state.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Whether the connection was reset by the peer or dropped after too many
+    /// retransmissions.
+    pub fn is_reset(&self) -> bool {
+        self.was_reset
+    }
+
+    /// Amount of data queued and not acknowledged yet.
+    pub fn pending(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    /// Instant at which `poll` has to be called if no packet arrives.
+    pub fn timeout(&self) -> Option<Instant> {
+        match (self.timer, self.wait) {
+            (Some(timer), Some(wait)) => Some(timer.min(wait)),
+            (timer, wait) => timer.or(wait),
+        }
+    }
+
+    /// Queue data to send, returning how much fitted in the send buffer.
+    pub fn send(&mut self, data: &[u8]) -> Result<usize> {
+        match self.state {
+            State::SynSent | State::SynReceived | State::Established | State::CloseWait => (),
+
+            _ => return Err(ErrorKind::InvalidState.into()),
+        }
+
+        let size = cmp::min(data.len(), self.capacity.saturating_sub(self.outgoing.len()));
+        self.outgoing.extend(&data[..size]);
+
+        Ok(size)
+    }
+
+    /// Take the data received so far.
+    pub fn receive(&mut self) -> Vec<u8> {
+        let data = self.incoming.drain(..).collect::<Vec<_>>();
+
+        // Reading may have reopened a closed window.
+        if !data.is_empty() && self.is_synchronized() {
+            self.acknowledge = true;
+        }
+
+        data
+    }
+
+    /// Close the sending side, a FIN is sent once the queued data is.
+    ///
+    /// # Note
+    ///
+    /// When closing in SynReceived the FIN follows the acknowledgment of the
+    /// SYN.
+    pub fn close(&mut self) -> Result<()> {
+        self.state = match self.state {
+            State::Listen | State::SynSent => State::Closed,
+            State::SynReceived => {
+                self.handshake = true;
+                State::FinWait1
+            }
+            State::Established => State::FinWait1,
+            State::CloseWait => State::LastAck,
+
+            _ => return Err(ErrorKind::InvalidState.into()),
+        };
+
+        Ok(())
+    }
+
+    /// Abort the connection, sending a RST.
+    pub fn abort(&mut self) {
+        if let Some(remote) = self.remote {
+            if self.is_synchronized() || self.is_syn_received() {
+                self.reset = Some((remote, self.next, None));
+            }
+        }
+
+        self.state = State::Closed;
+        self.timer = None;
+        self.wait = None;
+    }
+
+    /// Handle an incoming packet.
+    ///
+    /// Packets not meant for the endpoint, or with a wrong checksum, are
+    /// rejected as invalid.
+    pub fn push<BI: AsRef<[u8]>, BT: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>, packet: &Packet<BT>, now: Instant) -> Result<()> {
+        let (source, destination) = match *ip {
+            ip::Packet::V4(ref ip) => (SocketAddrV4::new(ip.source(), packet.source()), SocketAddrV4::new(ip.destination(), packet.destination())),
+
+            ip::Packet::V6(_) => return Err(ErrorKind::InvalidPacket.into()),
+        };
+
+        if destination != self.local || self.remote.is_some_and(|remote| remote != source) || !packet.is_valid(ip) {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let flags = packet.flags();
+
+        match self.state {
+            State::Closed => {
+                // Anything but a RST gets one back.
+                if !flags.contains(flag::RST) {
+                    self.refuse(source, packet);
+                }
+            }
+
+            State::Listen => {
+                if flags.contains(flag::RST) {
+                    return Ok(());
+                }
+
+                if flags.contains(flag::ACK) {
+                    self.refuse(source, packet);
+                } else if flags.contains(flag::SYN) {
+                    self.remote = Some(source);
+                    self.synchronize(packet);
+                    self.state = State::SynReceived;
+                }
+            }
+
+            State::SynSent => {
+                let ack = flags.contains(flag::ACK);
+
+                if ack && packet.acknowledgment() != self.initial.wrapping_add(1) {
+                    if !flags.contains(flag::RST) {
+                        self.refuse(source, packet);
+                    }
+
+                    return Ok(());
+                }
+
+                if flags.contains(flag::RST) {
+                    if ack {
+                        self.terminate(true);
+                    }
+
+                    return Ok(());
+                }
+
+                if flags.contains(flag::SYN) {
+                    self.synchronize(packet);
+                    self.acknowledge = true;
+
+                    if ack {
+                        self.unacknowledged = packet.acknowledgment();
+                        self.state = State::Established;
+                        self.progress(now);
+                    } else {
+                        // Simultaneous open, the SYN is sent again with the ACK.
+                        self.state = State::SynReceived;
+                        self.next = self.initial;
+                        self.timer = None;
+                    }
+                }
+            }
+
+            _ => self.synchronized(source, packet, now),
+        }
+
+        Ok(())
+    }
+
+    /// Take the next packet to send, if any.
+    pub fn poll(&mut self, now: Instant) -> Result<Option<Vec<u8>>> {
+        if self.wait.is_some_and(|wait| now >= wait) {
+            self.wait = None;
+            self.state = State::Closed;
+        }
+
+        if let Some((remote, sequence, acknowledgment)) = self.reset.take() {
+            let flags = if acknowledgment.is_some() { flag::RST | flag::ACK } else { flag::RST };
+            return self.build(remote, sequence, acknowledgment, flags, &[], false).map(Some);
+        }
+
+        let remote = match self.remote {
+            Some(remote) if self.state != State::Closed => remote,
+            _ => return Ok(None),
+        };
+
+        if self.timer.is_some_and(|timer| now >= timer) {
+            self.timer = None;
+
+            if self.attempts >= self.retries {
+                self.reset = Some((remote, self.next, None));
+                self.terminate(true);
+
+                return self.poll(now);
+            }
+
+            self.attempts += 1;
+            self.backoff *= 2;
+
+            // With the window closed, whatever was in flight is sent again as
+            // a probe.
+            if self.window == 0 && self.is_synchronized() {
+                self.probe = true;
+            }
+
+            self.next = self.unacknowledged;
+        }
+
+        if self.state == State::SynSent || self.is_syn_received() {
+            if self.next != self.initial {
+                return Ok(None);
+            }
+
+            let (flags, acknowledgment) = match self.state {
+                State::SynSent => (flag::SYN, None),
+                _ => (flag::SYN | flag::ACK, Some(self.expected)),
+            };
+
+            self.next = self.initial.wrapping_add(1);
+            self.arm(now);
+            self.acknowledge = false;
+
+            return self.build(remote, self.initial, acknowledgment, flags, &[], true).map(Some);
+        }
+
+        if matches!(self.state, State::Established | State::CloseWait | State::FinWait1 | State::Closing | State::LastAck) && !self.is_fin_sent() {
+            let sent = self.next.wrapping_sub(self.unacknowledged) as usize;
+            let window = cmp::max(self.window, self.probe as u32) as usize;
+            let size = cmp::min(cmp::min(self.outgoing.len() - sent, window.saturating_sub(sent)), self.segment as usize);
+
+            if size > 0 {
+                let payload = self.outgoing.range(sent..sent + size).cloned().collect::<Vec<_>>();
+                let sequence = self.next;
+
+                self.next = self.next.wrapping_add(size as u32);
+                self.probe = false;
+                self.acknowledge = false;
+                self.arm(now);
+
+                return self.build(remote, sequence, Some(self.expected), flag::ACK | flag::PSH, &payload, false).map(Some);
+            }
+
+            if sent == self.outgoing.len() && matches!(self.state, State::FinWait1 | State::Closing | State::LastAck) {
+                let sequence = self.next;
+
+                self.next = self.next.wrapping_add(1);
+                self.acknowledge = false;
+                self.arm(now);
+
+                return self.build(remote, sequence, Some(self.expected), flag::FIN | flag::ACK, &[], false).map(Some);
+            }
+
+            // Probe the window once it has been closed for a while.
+            if self.window == 0 && sent < self.outgoing.len() {
+                self.arm(now);
+            }
+        }
+
+        if self.acknowledge {
+            self.acknowledge = false;
+            return self.build(remote, self.next, Some(self.expected), flag::ACK, &[], false).map(Some);
+        }
+
+        Ok(None)
+    }
+}
+
+impl Endpoint {
+    fn is_synchronized(&self) -> bool {
+        !matches!(self.state, State::Listen | State::SynSent | State::SynReceived | State::Closed) && !self.handshake
+    }
+
+    /// Whether the SYN of the peer was received and ours is yet to be
+    /// acknowledged.
+    fn is_syn_received(&self) -> bool {
+        self.state == State::SynReceived || self.handshake
+    }
+
+    /// Complete the handshake, going on with the close if it was asked for.
+    fn establish(&mut self) {
+        self.state = if self.handshake { State::FinWait1 } else { State::Established };
+        self.handshake = false;
+    }
+
+    /// Whether the FIN was sent, which is the last sequence number.
+    fn is_fin_sent(&self) -> bool {
+        matches!(self.state, State::FinWait1 | State::FinWait2 | State::Closing | State::LastAck | State::TimeWait)
+            && !self.handshake
+            && self.next == self.unacknowledged.wrapping_add(self.outgoing.len() as u32).wrapping_add(1)
+    }
+
+    fn receive_window(&self) -> u32 {
+        cmp::min(self.capacity.saturating_sub(self.incoming.len()), u16::MAX as usize) as u32
+    }
+
+    /// Start the retransmission timer unless it's running.
+    fn arm(&mut self, now: Instant) {
+        if self.timer.is_none() {
+            self.timer = Some(now + self.backoff);
+        }
+    }
+
+    /// Reset the retransmission timer after an acknowledgment.
+    fn progress(&mut self, now: Instant) {
+        self.attempts = 0;
+        self.backoff = self.rto;
+        self.timer = if self.next != self.unacknowledged { Some(now + self.backoff) } else { None };
+    }
+
+    fn terminate(&mut self, reset: bool) {
+        self.state = State::Closed;
+        self.handshake = false;
+        self.was_reset = reset;
+        self.timer = None;
+        self.outgoing.clear();
+    }
+
+    /// Answer an unacceptable segment with a RST.
+    fn refuse<B: AsRef<[u8]>>(&mut self, remote: SocketAddrV4, packet: &Packet<B>) {
+        let flags = packet.flags();
+
+        self.reset = if flags.contains(flag::ACK) {
+            Some((remote, packet.acknowledgment(), None))
+        } else {
+            let length = packet.payload().len() as u32 + flags.contains(flag::SYN) as u32 + flags.contains(flag::FIN) as u32;
+            Some((remote, 0, Some(packet.sequence().wrapping_add(length))))
+        };
+    }
+
+    /// Record the SYN of the peer.
+    fn synchronize<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>) {
+        self.expected = packet.sequence().wrapping_add(1);
+        self.window = u32::from(packet.window());
+        self.segment = cmp::min(self.mss, packet.mss().unwrap_or(536));
+    }
+
+    fn synchronized<B: AsRef<[u8]>>(&mut self, source: SocketAddrV4, packet: &Packet<B>, now: Instant) {
+        let flags = packet.flags();
+        let sequence = packet.sequence();
+        let payload = packet.payload();
+        let window = self.receive_window();
+
+        // In a simultaneous open the SYN of the peer comes again along with the
+        // acknowledgment of ours, which is all that is left to process.
+        if self.is_syn_received() && flags.contains(flag::SYN | flag::ACK) && sequence.wrapping_add(1) == self.expected && packet.acknowledgment() == self.initial.wrapping_add(1) {
+            self.establish();
+            self.next = packet.acknowledgment();
+            self.unacknowledged = self.next;
+            self.window = u32::from(packet.window());
+            self.acknowledge = true;
+            self.progress(now);
+
+            return;
+        }
+
+        let offset = sequence.wrapping_sub(self.expected);
+        let last = offset.wrapping_add(payload.len() as u32).wrapping_sub(1);
+        let acceptable = if payload.is_empty() {
+            offset == 0 || offset < window
+        } else {
+            window > 0 && (offset < window || last < window)
+        };
+
+        if !acceptable {
+            if !flags.contains(flag::RST) {
+                self.acknowledge = true;
+            }
+
+            return;
+        }
+
+        if flags.contains(flag::RST) {
+            self.terminate(true);
+            return;
+        }
+
+        if flags.contains(flag::SYN) {
+            self.refuse(source, packet);
+            self.terminate(true);
+            return;
+        }
+
+        if !flags.contains(flag::ACK) {
+            return;
+        }
+
+        let acknowledgment = packet.acknowledgment();
+        let acknowledged = acknowledgment.wrapping_sub(self.unacknowledged);
+
+        if acknowledged > self.next.wrapping_sub(self.unacknowledged) {
+            // Acknowledgment of something never sent.
+            if self.is_syn_received() {
+                self.refuse(source, packet);
+            } else {
+                self.acknowledge = true;
+            }
+
+            return;
+        }
+
+        if self.is_syn_received() {
+            if acknowledged == 0 {
+                return;
+            }
+
+            self.establish();
+            self.unacknowledged = self.unacknowledged.wrapping_add(1);
+            self.progress(now);
+        }
+
+        let acknowledged = acknowledgment.wrapping_sub(self.unacknowledged);
+
+        if acknowledged > 0 {
+            let fin = self.is_fin_sent() && acknowledgment == self.next;
+            let data = cmp::min(acknowledged as usize, self.outgoing.len());
+
+            self.outgoing.drain(..data);
+            self.unacknowledged = acknowledgment;
+            self.progress(now);
+
+            if fin {
+                match self.state {
+                    State::FinWait1 => self.state = State::FinWait2,
+                    State::Closing => self.enter_time_wait(now),
+                    State::LastAck => self.terminate(false),
+                    _ => (),
+                }
+            }
+        }
+
+        // The peer answering a zero window probe is still there, the
+        // connection is kept open however long the window stays closed and
+        // the refused probe is sent again once it opens.
+        if acknowledged == 0 && packet.window() == 0 && !self.outgoing.is_empty() {
+            self.attempts = 0;
+            self.next = self.unacknowledged;
+        }
+
+        self.window = u32::from(packet.window());
+
+        if matches!(self.state, State::Established | State::FinWait1 | State::FinWait2) && !payload.is_empty() {
+            // Out of order data is dropped, the acknowledgment asks for it
+            // again.
+            let skip = self.expected.wrapping_sub(sequence) as usize;
+
+            if offset == 0 || skip < payload.len() {
+                let skip = if offset == 0 { 0 } else { skip };
+                let size = cmp::min(payload.len() - skip, window as usize);
+
+                self.incoming.extend(&payload[skip..skip + size]);
+                self.expected = self.expected.wrapping_add(size as u32);
+            }
+
+            self.acknowledge = true;
+        }
+
+        if flags.contains(flag::FIN) && sequence.wrapping_add(payload.len() as u32) == self.expected {
+            self.expected = self.expected.wrapping_add(1);
+            self.acknowledge = true;
+
+            match self.state {
+                State::Established => self.state = State::CloseWait,
+                State::FinWait1 => self.state = State::Closing,
+                State::FinWait2 | State::TimeWait => self.enter_time_wait(now),
+                _ => (),
+            }
+        }
+    }
+
+    fn enter_time_wait(&mut self, now: Instant) {
+        self.state = State::TimeWait;
+        self.timer = None;
+        self.wait = Some(now + self.linger);
+    }
+
+    fn build(&self, remote: SocketAddrV4, sequence: u32, acknowledgment: Option<u32>, flags: Flags, payload: &[u8], syn: bool) -> Result<Vec<u8>> {
+        let mut builder = ip::v4::Builder::default()
+            .ttl(64)?
+            .source(*self.local.ip())?
+            .destination(*remote.ip())?
+            .tcp()?
+            .source(self.local.port())?
+            .destination(remote.port())?
+            .sequence(sequence)?
+            .acknowledgment(acknowledgment.unwrap_or(0))?
+            .flags(flags)?
+            .window(self.receive_window() as u16)?;
+
+        if syn {
+            builder = builder.mss(self.mss)?;
+        }
+
+        builder.payload(payload)?.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddrV4;
+    use std::time::{Duration, Instant};
+
+    use crate::ip;
+    use crate::packet::Packet;
+    use crate::tcp::endpoint::{Endpoint, State};
+    use crate::tcp::fixture::{none, segment};
+    use crate::tcp::{self, flag};
+
+    fn client() -> Endpoint {
+        Endpoint::connect("10.0.0.1:1337".parse().unwrap(), "10.0.0.2:80".parse().unwrap(), 100)
+    }
+
+    fn server() -> Endpoint {
+        Endpoint::listen("10.0.0.2:80".parse().unwrap(), 500)
+    }
+
+    /// Move the packets from an endpoint to the other, returning how many.
+    fn deliver(from: &mut Endpoint, to: &mut Endpoint, now: Instant) -> usize {
+        let mut count = 0;
+
+        while let Some(buffer) = from.poll(now).unwrap() {
+            let ip = ip::Packet::new(&buffer[..]).unwrap();
+            let tcp = tcp::Packet::new(ip.payload()).unwrap();
+            to.push(&ip, &tcp, now).unwrap();
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Move the packets between the endpoints until they're quiet.
+    fn exchange(a: &mut Endpoint, b: &mut Endpoint, now: Instant) {
+        while deliver(a, b, now) + deliver(b, a, now) > 0 {}
+    }
+
+    #[test]
+    fn handshake() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client(), server());
+
+        let syn = client.poll(now).unwrap().unwrap();
+        let ip = ip::Packet::new(&syn[..]).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.flags(), flag::SYN);
+        assert_eq!(tcp.sequence(), 100);
+        assert_eq!(tcp.mss(), Some(1460));
+        assert!(client.poll(now).unwrap().is_none());
+
+        server.push(&ip, &tcp, now).unwrap();
+        assert_eq!(server.state(), State::SynReceived);
+        assert_eq!(server.remote(), Some("10.0.0.1:1337".parse::<SocketAddrV4>().unwrap()));
+
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::Established);
+        assert_eq!(server.state(), State::Established);
+        assert_eq!(client.timeout(), None);
+    }
+
+    #[test]
+    fn transfer() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client().mss(100), server().capacity(250));
+        exchange(&mut client, &mut server, now);
+
+        let data = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(client.send(&data).unwrap(), 1000);
+
+        // The receive window fills up until the data is read.
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.pending(), 750);
+
+        let mut received = Vec::new();
+
+        while received.len() < data.len() {
+            received.extend(server.receive());
+            exchange(&mut client, &mut server, now);
+        }
+
+        assert_eq!(received, data);
+        assert_eq!(client.pending(), 0);
+    }
+
+    #[test]
+    fn close() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client().linger(Duration::from_secs(1)), server());
+        exchange(&mut client, &mut server, now);
+
+        client.send(b"bye").unwrap();
+        client.close().unwrap();
+        assert!(client.send(b"more").is_err());
+
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::FinWait2);
+        assert_eq!(server.state(), State::CloseWait);
+        assert_eq!(server.receive(), b"bye");
+
+        server.close().unwrap();
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::TimeWait);
+        assert_eq!(server.state(), State::Closed);
+        assert!(!server.is_reset());
+
+        assert!(client.poll(now + Duration::from_secs(1)).unwrap().is_none());
+        assert_eq!(client.state(), State::Closed);
+    }
+
+    #[test]
+    fn close_syn_received() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client(), server());
+
+        let syn = client.poll(now).unwrap().unwrap();
+        let ip = ip::Packet::new(&syn[..]).unwrap();
+        server.push(&ip, &tcp::Packet::new(ip.payload()).unwrap(), now).unwrap();
+
+        // The FIN waits for the handshake and the queued data.
+        server.send(b"bye").unwrap();
+        server.close().unwrap();
+        assert_eq!(server.state(), State::FinWait1);
+
+        exchange(&mut client, &mut server, now);
+        assert_eq!(server.state(), State::FinWait2);
+        assert_eq!(client.state(), State::CloseWait);
+        assert_eq!(client.receive(), b"bye");
+
+        client.close().unwrap();
+        exchange(&mut client, &mut server, now);
+        assert_eq!(server.state(), State::TimeWait);
+        assert_eq!(client.state(), State::Closed);
+    }
+
+    #[test]
+    fn retransmission() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client().rto(Duration::from_millis(100)).retries(2), server());
+        exchange(&mut client, &mut server, now);
+
+        // The segment is lost.
+        client.send(b"hello").unwrap();
+        assert!(client.poll(now).unwrap().is_some());
+        assert_eq!(client.timeout(), Some(now + Duration::from_millis(100)));
+
+        let later = now + Duration::from_millis(100);
+        exchange(&mut client, &mut server, later);
+        assert_eq!(server.receive(), b"hello");
+        assert_eq!(client.timeout(), None);
+
+        // The peer is gone.
+        client.send(b"anyone?").unwrap();
+        assert!(client.poll(later).unwrap().is_some());
+        assert!(client.poll(later + Duration::from_millis(100)).unwrap().is_some());
+        assert!(client.poll(later + Duration::from_millis(300)).unwrap().is_some());
+
+        let rst = client.poll(later + Duration::from_millis(700)).unwrap().unwrap();
+        let ip = ip::Packet::new(&rst[..]).unwrap();
+        assert_eq!(tcp::Packet::new(ip.payload()).unwrap().flags(), flag::RST);
+        assert_eq!(client.state(), State::Closed);
+        assert!(client.is_reset());
+    }
+
+    #[test]
+    fn zero_window() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client().rto(Duration::from_millis(100)).retries(3), server().capacity(10));
+        exchange(&mut client, &mut server, now);
+
+        let data = (0..50).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(client.send(&data).unwrap(), 50);
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.pending(), 40);
+
+        // The reader is slow, the window stays closed past the retries.
+        let mut later = now;
+
+        for _ in 0..10 {
+            later = client.timeout().unwrap();
+            exchange(&mut client, &mut server, later);
+        }
+
+        assert_eq!(client.state(), State::Established);
+        assert!(!client.is_reset());
+        assert_eq!(client.pending(), 40);
+
+        let mut received = Vec::new();
+
+        while received.len() < data.len() {
+            received.extend(server.receive());
+            exchange(&mut client, &mut server, later);
+        }
+
+        assert_eq!(received, data);
+        assert_eq!(client.pending(), 0);
+    }
+
+    #[test]
+    fn reset() {
+        let now = Instant::now();
+
+        // Connection refused.
+        let (mut client, mut server) = (client(), server());
+        server.close().unwrap();
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::Closed);
+        assert!(client.is_reset());
+
+        // Aborted connection.
+        let (mut client, mut server) = (self::client(), self::server());
+        exchange(&mut client, &mut server, now);
+        server.abort();
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::Closed);
+        assert!(client.is_reset());
+    }
+
+    #[test]
+    fn out_of_window() {
+        let now = Instant::now();
+        let (mut client, mut server) = (client(), server().capacity(100));
+        exchange(&mut client, &mut server, now);
+
+        // A RST outside the window is dropped, a SYN is acknowledged.
+        for &flags in &[flag::RST, flag::SYN] {
+            let packet = segment(true, (1000, 501, 1000), flags, b"", none);
+            server.push(&packet, &tcp::Packet::new(packet.payload()).unwrap(), now).unwrap();
+            assert_eq!(server.state(), State::Established);
+        }
+
+        let ack = server.poll(now).unwrap().unwrap();
+        let ip = ip::Packet::new(&ack[..]).unwrap();
+        let tcp = tcp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(tcp.flags(), flag::ACK);
+        assert_eq!(tcp.acknowledgment(), 101);
+        assert!(server.poll(now).unwrap().is_none());
+
+        // A SYN inside the window resets the connection.
+        let packet = segment(true, (150, 501, 1000), flag::SYN | flag::ACK, b"", none);
+        server.push(&packet, &tcp::Packet::new(packet.payload()).unwrap(), now).unwrap();
+        assert_eq!(server.state(), State::Closed);
+        assert!(server.is_reset());
+
+        exchange(&mut client, &mut server, now);
+        assert_eq!(client.state(), State::Closed);
+        assert!(client.is_reset());
+    }
+
+    #[test]
+    fn simultaneous_open() {
+        let now = Instant::now();
+        let mut a = client();
+        let mut b = Endpoint::connect("10.0.0.2:80".parse().unwrap(), "10.0.0.1:1337".parse().unwrap(), 500);
+
+        // The SYNs cross.
+        let (syn_a, syn_b) = (a.poll(now).unwrap().unwrap(), b.poll(now).unwrap().unwrap());
+
+        for (endpoint, syn) in [(&mut a, syn_b), (&mut b, syn_a)] {
+            let ip = ip::Packet::new(&syn[..]).unwrap();
+            endpoint.push(&ip, &tcp::Packet::new(ip.payload()).unwrap(), now).unwrap();
+            assert_eq!(endpoint.state(), State::SynReceived);
+        }
+
+        exchange(&mut a, &mut b, now);
+        assert_eq!(a.state(), State::Established);
+        assert_eq!(b.state(), State::Established);
+
+        a.send(b"hello").unwrap();
+        exchange(&mut a, &mut b, now);
+        assert_eq!(b.receive(), b"hello");
+        assert_eq!(a.timeout(), None);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::builder::Builder;
+use crate::error::*;
+use crate::ip;
+use crate::tcp::{self, Flags};
+
+/// Build a segment from the client at 10.0.0.1:1337 when `client` is set, or
+/// from the server at 10.0.0.2:80, with the given sequence, acknowledgment and
+/// window.
+pub fn segment<F>(client: bool, segment: (u32, u32, u16), flags: Flags, payload: &[u8], options: F) -> ip::Packet<Vec<u8>>
+where
+    F: FnOnce(tcp::Builder) -> Result<tcp::Builder>,
+{
+    let (source, destination, ports) = if client { ("10.0.0.1", "10.0.0.2", (1337, 80)) } else { ("10.0.0.2", "10.0.0.1", (80, 1337)) };
+
+    let builder = ip::v4::Builder::default()
+        .source(source.parse().unwrap())
+        .unwrap()
+        .destination(destination.parse().unwrap())
+        .unwrap()
+        .tcp()
+        .unwrap()
+        .source(ports.0)
+        .unwrap()
+        .destination(ports.1)
+        .unwrap()
+        .sequence(segment.0)
+        .unwrap()
+        .acknowledgment(segment.1)
+        .unwrap()
+        .flags(flags)
+        .unwrap()
+        .window(segment.2)
+        .unwrap();
+
+    let buffer = options(builder).unwrap().payload(payload).unwrap().build().unwrap();
+    ip::Packet::from(ip::v4::Packet::new(buffer).unwrap())
+}
+
+/// Leave the options of a segment empty.
+pub fn none(builder: tcp::Builder) -> Result<tcp::Builder> {
+    Ok(builder)
+}
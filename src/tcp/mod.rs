@@ -12,6 +12,25 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+/// TCP stream reassembly.
+pub mod reassembly;
+pub use self::reassembly::Reassembler;
+
+/// Passive TCP connection tracking.
+pub mod tracker;
+pub use self::tracker::Tracker;
+
+/// TCP segment analysis.
+pub mod analysis;
+pub use self::analysis::Analyzer;
+
+/// Userspace TCP endpoint.
+pub mod endpoint;
+pub use self::endpoint::Endpoint;
+
+#[cfg(test)]
+pub(crate) mod fixture;
+
 use crate::ip;
 use crate::ip::Protocol;
 
@@ -19,34 +38,53 @@ use crate::ip::Protocol;
 ///
 /// # Note
 ///
-/// Since the checksum for UDP packets includes a pseudo-header based on the
+/// Since the checksum for TCP packets includes a pseudo-header based on the
 /// enclosing IP packet, one has to be given.
 pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
-    use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+    match *ip {
+        ip::Packet::V4(ref packet) => checksum_v4(packet, buffer),
+
+        ip::Packet::V6(ref packet) => checksum_v6(packet, buffer),
+    }
+}
+
+/// Calculate the checksum for a TCP packet enclosed in an IPv4 packet.
+pub fn checksum_v4<B: AsRef<[u8]>>(ip: &ip::v4::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    let mut prefix = [0u8; 12];
+    prefix[0..4].copy_from_slice(&ip.source().octets());
+    prefix[4..8].copy_from_slice(&ip.destination().octets());
+
+    prefix[9] = Protocol::Tcp.into();
+    Cursor::new(&mut prefix[10..]).write_u16::<BigEndian>(buffer.len() as u16).unwrap();
+
+    sum(&prefix, buffer)
+}
+
+/// Calculate the checksum for a TCP packet enclosed in an IPv6 packet.
+pub fn checksum_v6<B: AsRef<[u8]>>(ip: &ip::v6::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
     use std::io::Cursor;
 
     let mut prefix = [0u8; 40];
-    match *ip {
-        ip::Packet::V4(ref packet) => {
-            prefix[0..4].copy_from_slice(&packet.source().octets());
-            prefix[4..8].copy_from_slice(&packet.destination().octets());
+    prefix[0..16].copy_from_slice(&ip.source().octets());
+    prefix[16..32].copy_from_slice(&ip.final_destination().octets());
 
-            prefix[9] = Protocol::Tcp.into();
-            Cursor::new(&mut prefix[10..]).write_u16::<BigEndian>(buffer.len() as u16).unwrap();
-        }
+    Cursor::new(&mut prefix[32..]).write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+    prefix[39] = Protocol::Tcp.into();
 
-        ip::Packet::V6(ref _packet) => {
-            unimplemented!();
-        }
-    };
+    sum(&prefix, buffer)
+}
+
+fn sum(prefix: &[u8], buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, ReadBytesExt};
+    use std::io::Cursor;
 
     let mut result = 0xffffu32;
     let mut buffer = Cursor::new(buffer);
-    let mut prefix = match *ip {
-        ip::Packet::V4(_) => Cursor::new(&prefix[0..12]),
-
-        ip::Packet::V6(_) => Cursor::new(&prefix[0..40]),
-    };
+    let mut prefix = Cursor::new(prefix);
 
     while let Ok(value) = prefix.read_u16::<BigEndian>() {
         result += u32::from(value);
@@ -69,8 +107,9 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
         }
     }
 
-    if let Ok(value) = buffer.read_u8() {
-        let last = u32::from(value) << 8;
+    // A failed read may consume the odd byte, so take it from the slice.
+    if buffer.get_ref().len() % 2 == 1 {
+        let last = u32::from(buffer.get_ref()[buffer.get_ref().len() - 1]) << 8;
         result += last;
 
         if result > 0xffff {
@@ -80,7 +119,3 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 
     !result as u16
 }
-
-pub fn checksum_v4<B: AsRef<[u8]>>(ip: &ip::v4::Packet<B>, buffer: &[u8]) -> u16 {
-    0
-}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::error::*;
+
+/// TCP Authentication Option parser, over the option payload.
+pub struct Authentication<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Authentication<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::Authentication")
+            .field("key", &self.key())
+            .field("next_key", &self.next_key())
+            .field("mac", &self.mac())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+    /// Parse a TCP Authentication Option, checking the buffer contents are
+    /// correct.
+    pub fn new(buffer: B) -> Result<Authentication<B>> {
+        let option = Authentication { buffer };
+
+        if option.buffer.as_ref().len() < 2 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Authentication<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+    /// Identifier of the key used for the segment.
+    pub fn key(&self) -> u8 {
+        self.buffer.as_ref()[0]
+    }
+
+    /// Identifier of the key the sender is ready to receive with.
+    pub fn next_key(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Message authentication code.
+    pub fn mac(&self) -> &[u8] {
+        &self.buffer.as_ref()[2..]
+    }
+}
@@ -0,0 +1,450 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::*;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+use crate::size;
+
+mod authentication;
+pub use self::authentication::Authentication;
+
+/// Multipath TCP option parser.
+pub mod mptcp;
+pub use self::mptcp::Multipath;
+
+/// TCP option parser.
+pub struct Option<B> {
+    buffer: B,
+}
+
+sized!(Option,
+	header {
+		min: 1,
+		max: 2,
+		size: p => match p.length() {
+			1 => 1,
+			_ => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  32,
+		size: p => match p.length() {
+			1 => 0,
+			n => n as usize - 2,
+		},
+	});
+
+/// TCP option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+    ///
+    End,
+
+    ///
+    NoOperation,
+
+    ///
+    MaximumSegmentSize,
+
+    ///
+    WindowScale,
+
+    ///
+    SelectiveAcknowledgmentPermitted,
+
+    ///
+    SelectiveAcknowledgment,
+
+    ///
+    Timestamp,
+
+    ///
+    Md5Signature,
+
+    ///
+    UserTimeout,
+
+    ///
+    Authentication,
+
+    ///
+    Multipath,
+
+    ///
+    FastOpen,
+
+    ///
+    Unknown(u8),
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::Option")
+            .field("number", &self.number())
+            .field("length", &self.length())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Parse a TCP option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Option<B>> {
+        let option = Option { buffer: buffer };
+
+        if option.buffer.as_ref().len() < <Self as size::header::Min>::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        // Only End of Option List and No Operation are a single octet.
+        match option.number() {
+            Number::End | Number::NoOperation => (),
+
+            _ if option.buffer.as_ref().len() < 2 => return Err(ErrorKind::SmallBuffer.into()),
+
+            _ if option.length() < 2 => return Err(ErrorKind::InvalidPacket.into()),
+
+            _ => (),
+        }
+
+        if option.buffer.as_ref().len() < option.length() as usize {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Option<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Option<&[u8]>> {
+        Option::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Option<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Option<&mut [u8]>> {
+        Option::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        match self.length() {
+            1 => self.buffer.as_ref().split_at(1),
+
+            length => self.buffer.as_ref()[..length as usize].split_at(2),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Option<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        match self.length() {
+            1 => self.buffer.as_mut().split_at_mut(1),
+
+            length => self.buffer.as_mut()[..length as usize].split_at_mut(2),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+    /// Option number.
+    pub fn number(&self) -> Number {
+        self.buffer.as_ref()[0].into()
+    }
+
+    /// Option length.
+    pub fn length(&self) -> u8 {
+        match self.number() {
+            Number::End | Number::NoOperation => 1,
+
+            _ => self.buffer.as_ref()[1],
+        }
+    }
+
+    /// Maximum segment size.
+    pub fn mss(&self) -> Result<u16> {
+        if self.number() != Number::MaximumSegmentSize || self.payload().len() != 2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(self.payload().read_u16::<BigEndian>()?)
+    }
+
+    /// Window scale shift count.
+    pub fn window_scale(&self) -> Result<u8> {
+        if self.number() != Number::WindowScale || self.payload().len() != 1 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(self.payload()[0])
+    }
+
+    /// Whether the option is a well formed SACK-Permitted.
+    pub fn is_sack_permitted(&self) -> bool {
+        self.number() == Number::SelectiveAcknowledgmentPermitted && self.length() == 2
+    }
+
+    /// Blocks of a SACK option.
+    pub fn sack(&self) -> Result<BlockIter> {
+        if self.number() != Number::SelectiveAcknowledgment || self.payload().is_empty() || self.payload().len() % 8 != 0 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(BlockIter { buffer: self.payload() })
+    }
+
+    /// Timestamp value and echo reply.
+    pub fn timestamp(&self) -> Result<(u32, u32)> {
+        if self.number() != Number::Timestamp || self.payload().len() != 8 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let mut payload = self.payload();
+        Ok((payload.read_u32::<BigEndian>()?, payload.read_u32::<BigEndian>()?))
+    }
+
+    /// MD5 digest of a TCP MD5 Signature option.
+    pub fn md5_signature(&self) -> Result<&[u8]> {
+        if self.number() != Number::Md5Signature || self.payload().len() != 16 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(self.payload())
+    }
+
+    /// Timeout of a User Timeout option.
+    pub fn user_timeout(&self) -> Result<Duration> {
+        if self.number() != Number::UserTimeout || self.payload().len() != 2 {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        let value = self.payload().read_u16::<BigEndian>()?;
+        let timeout = u64::from(value & 0x7fff);
+
+        // The high bit sets the granularity to minutes.
+        if value & 0x8000 != 0 {
+            Ok(Duration::from_secs(timeout * 60))
+        } else {
+            Ok(Duration::from_secs(timeout))
+        }
+    }
+
+    /// Parse a TCP Authentication Option.
+    pub fn authentication(&self) -> Result<Authentication<&[u8]>> {
+        if self.number() != Number::Authentication {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Authentication::new(self.payload())
+    }
+
+    /// Parse a Multipath TCP option.
+    pub fn multipath(&self) -> Result<Multipath<&[u8]>> {
+        if self.number() != Number::Multipath {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Multipath::new(self.payload())
+    }
+
+    /// Cookie of a TCP Fast Open option, empty when requesting one.
+    pub fn fast_open(&self) -> Result<&[u8]> {
+        if self.number() != Number::FastOpen {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        match self.payload().len() {
+            0 => Ok(self.payload()),
+            n if (4..=16).contains(&n) && n % 2 == 0 => Ok(self.payload()),
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+}
+
+/// Iterator over the blocks of a SACK option, as left and right edge.
+pub struct BlockIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for BlockIter<'a> {
+    type Item = (u32, u32);
+
+    fn next(&mut self) -> std::option::Option<Self::Item> {
+        let left = (&self.buffer[..]).read_u32::<BigEndian>().ok()?;
+        let right = (&self.buffer[4..]).read_u32::<BigEndian>().ok()?;
+        self.buffer = &self.buffer[8..];
+
+        Some((left, right))
+    }
+}
+
+impl From<u8> for Number {
+    fn from(value: u8) -> Self {
+        use self::Number::*;
+
+        match value {
+            0 => End,
+            1 => NoOperation,
+            2 => MaximumSegmentSize,
+            3 => WindowScale,
+            4 => SelectiveAcknowledgmentPermitted,
+            5 => SelectiveAcknowledgment,
+            8 => Timestamp,
+            19 => Md5Signature,
+            28 => UserTimeout,
+            29 => Authentication,
+            30 => Multipath,
+            34 => FastOpen,
+            n => Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Number {
+    fn into(self) -> u8 {
+        use self::Number::*;
+
+        match self {
+            End => 0,
+            NoOperation => 1,
+            MaximumSegmentSize => 2,
+            WindowScale => 3,
+            SelectiveAcknowledgmentPermitted => 4,
+            SelectiveAcknowledgment => 5,
+            Timestamp => 8,
+            Md5Signature => 19,
+            UserTimeout => 28,
+            Authentication => 29,
+            Multipath => 30,
+            FastOpen => 34,
+            Unknown(n) => n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use crate::tcp::option::mptcp;
+    use crate::tcp::option::{Number, Option};
+
+    #[test]
+    fn multipath() {
+        // MP_CAPABLE on SYN, checksums and HMAC-SHA256.
+        let raw = [0x1eu8, 0x04, 0x01, 0x81];
+        let capable = Option::new(&raw[..]).unwrap();
+        let capable = capable.multipath().unwrap();
+        let capable = capable.capable().unwrap();
+        assert_eq!(capable.version(), 1);
+        assert_eq!(capable.flags(), mptcp::CapableFlags::CHECKSUM | mptcp::CapableFlags::HMAC_SHA256);
+        assert_eq!(capable.sender(), None);
+
+        // MP_CAPABLE on SYN/ACK.
+        let raw = [0x1eu8, 0x0c, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let capable = Option::new(&raw[..]).unwrap();
+        let capable = capable.multipath().unwrap();
+        let capable = capable.capable().unwrap();
+        assert_eq!(capable.sender(), Some(0x0102030405060708));
+        assert_eq!(capable.receiver(), None);
+
+        // MP_JOIN on SYN, backup.
+        let raw = [0x1eu8, 0x0c, 0x11, 0x03, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x2a];
+        let join = Option::new(&raw[..]).unwrap();
+        let join = join.multipath().unwrap();
+        assert!(join.capable().is_err());
+        let join = join.join().unwrap();
+        assert!(join.is_backup());
+        assert_eq!(join.address(), Some(3));
+        assert_eq!(join.token(), Some(0xdeadbeef));
+        assert_eq!(join.random(), Some(42));
+        assert_eq!(join.hmac(), None);
+
+        // DSS with 4 octets acknowledgment and mapping, and checksum.
+        let raw = [
+            0x1eu8, 0x14, 0x20, 0x05, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x64, 0xab, 0xcd,
+        ];
+        let dss = Option::new(&raw[..]).unwrap();
+        let dss = dss.multipath().unwrap();
+        let dss = dss.data_sequence().unwrap();
+        assert!(!dss.is_fin());
+        assert_eq!(dss.acknowledgment(), Some(0x10));
+        assert_eq!(
+            dss.mapping(),
+            Some(mptcp::Mapping {
+                sequence: 0x100,
+                subflow: 1,
+                length: 100,
+                checksum: Some(0xabcd),
+            })
+        );
+
+        // DSS with a length not matching the flags.
+        let raw = [0x1eu8, 0x08, 0x20, 0x05, 0x00, 0x00, 0x00, 0x10];
+        assert!(Option::new(&raw[..]).unwrap().multipath().unwrap().data_sequence().is_err());
+
+        // ADD_ADDR echo.
+        let raw = [0x1eu8, 0x08, 0x31, 0x02, 0xc0, 0xa8, 0x01, 0x01];
+        let add = Option::new(&raw[..]).unwrap();
+        let add = add.multipath().unwrap();
+        let add = add.add_address().unwrap();
+        assert!(add.is_echo());
+        assert_eq!(add.id(), 2);
+        assert_eq!(add.address(), Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(add.port(), None);
+        assert_eq!(add.hmac(), None);
+    }
+
+    #[test]
+    fn others() {
+        let raw = [0x22u8, 0x0a, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let option = Option::new(&raw[..]).unwrap();
+        assert_eq!(option.number(), Number::FastOpen);
+        assert_eq!(option.fast_open().unwrap(), &raw[2..]);
+
+        let raw = [0x22u8, 0x02];
+        assert!(Option::new(&raw[..]).unwrap().fast_open().unwrap().is_empty());
+
+        let raw = [0x22u8, 0x05, 0x01, 0x02, 0x03];
+        assert!(Option::new(&raw[..]).unwrap().fast_open().is_err());
+
+        let raw = [0x1cu8, 0x04, 0x80, 0x05];
+        assert_eq!(Option::new(&raw[..]).unwrap().user_timeout().unwrap(), Duration::from_secs(300));
+
+        let raw = [0x1cu8, 0x04, 0x00, 0x05];
+        assert_eq!(Option::new(&raw[..]).unwrap().user_timeout().unwrap(), Duration::from_secs(5));
+
+        let raw = [0x13u8, 0x12, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        assert_eq!(Option::new(&raw[..]).unwrap().md5_signature().unwrap(), &raw[2..]);
+
+        let raw = [0x1du8, 0x06, 0x01, 0x02, 0xaa, 0xbb];
+        let option = Option::new(&raw[..]).unwrap();
+        let ao = option.authentication().unwrap();
+        assert_eq!(ao.key(), 1);
+        assert_eq!(ao.next_key(), 2);
+        assert_eq!(ao.mac(), &[0xaa, 0xbb][..]);
+        assert!(option.multipath().is_err());
+    }
+}
@@ -0,0 +1,525 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::error::*;
+
+/// Multipath TCP option parser, as defined in RFC 8684, over the option
+/// payload.
+pub struct Multipath<B> {
+    buffer: B,
+}
+
+/// Multipath TCP option subtype.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Subtype {
+    ///
+    Capable,
+
+    ///
+    Join,
+
+    ///
+    DataSequence,
+
+    ///
+    AddAddress,
+
+    ///
+    RemoveAddress,
+
+    ///
+    Priority,
+
+    ///
+    Fail,
+
+    ///
+    FastClose,
+
+    ///
+    Reset,
+
+    ///
+    Unknown(u8),
+}
+
+bitflags! {
+    /// Flags of an MP_CAPABLE option.
+    pub struct CapableFlags: u8 {
+        /// Checksums are required.
+        const CHECKSUM = 0b1000_0000;
+
+        ///
+        const EXTENSIBILITY = 0b0100_0000;
+
+        /// No more subflows to the source address.
+        const NO_SUBFLOWS = 0b0010_0000;
+
+        ///
+        const HMAC_SHA256 = 0b0000_0001;
+    }
+}
+
+/// Data sequence mapping of a DSS option.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Mapping {
+    /// Data sequence number.
+    pub sequence: u64,
+
+    /// Subflow sequence number, relative to the initial one.
+    pub subflow: u32,
+
+    /// Data-level length.
+    pub length: u16,
+
+    /// DSS checksum, when negotiated.
+    pub checksum: Option<u16>,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Multipath<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::mptcp::Multipath")
+            .field("subtype", &self.subtype())
+            .field("payload", &self.buffer.as_ref())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Multipath<B> {
+    /// Parse a Multipath TCP option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Multipath<B>> {
+        let option = Multipath { buffer };
+
+        if option.buffer.as_ref().is_empty() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Multipath<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Multipath<B> {
+    /// Option subtype.
+    pub fn subtype(&self) -> Subtype {
+        (self.buffer.as_ref()[0] >> 4).into()
+    }
+
+    /// Parse an MP_CAPABLE option.
+    pub fn capable(&self) -> Result<Capable<&[u8]>> {
+        if self.subtype() != Subtype::Capable {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Capable::new(self.buffer.as_ref())
+    }
+
+    /// Parse an MP_JOIN option.
+    pub fn join(&self) -> Result<Join<&[u8]>> {
+        if self.subtype() != Subtype::Join {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Join::new(self.buffer.as_ref())
+    }
+
+    /// Parse a DSS option.
+    pub fn data_sequence(&self) -> Result<DataSequence<&[u8]>> {
+        if self.subtype() != Subtype::DataSequence {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        DataSequence::new(self.buffer.as_ref())
+    }
+
+    /// Parse an ADD_ADDR option.
+    pub fn add_address(&self) -> Result<AddAddress<&[u8]>> {
+        if self.subtype() != Subtype::AddAddress {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        AddAddress::new(self.buffer.as_ref())
+    }
+}
+
+/// MP_CAPABLE option parser.
+pub struct Capable<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Capable<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::mptcp::Capable")
+            .field("version", &self.version())
+            .field("flags", &self.flags())
+            .field("sender", &self.sender())
+            .field("receiver", &self.receiver())
+            .field("length", &self.length())
+            .field("checksum", &self.checksum())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Capable<B> {
+    /// Parse an MP_CAPABLE option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Capable<B>> {
+        let option = Capable { buffer };
+
+        match option.buffer.as_ref().len() {
+            2 | 10 | 18 | 20 | 22 => Ok(option),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Capable<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Capable<B> {
+    /// Protocol version.
+    pub fn version(&self) -> u8 {
+        self.buffer.as_ref()[0] & 0b1111
+    }
+
+    /// Option flags.
+    pub fn flags(&self) -> CapableFlags {
+        CapableFlags::from_bits_truncate(self.buffer.as_ref()[1])
+    }
+
+    /// Key of the sender of the option.
+    pub fn sender(&self) -> Option<u64> {
+        self.buffer.as_ref().get(2..10).map(|mut key| key.read_u64::<BigEndian>().unwrap())
+    }
+
+    /// Key of the receiver of the option.
+    pub fn receiver(&self) -> Option<u64> {
+        self.buffer.as_ref().get(10..18).map(|mut key| key.read_u64::<BigEndian>().unwrap())
+    }
+
+    /// Data-level length of the data carried with the option.
+    pub fn length(&self) -> Option<u16> {
+        self.buffer.as_ref().get(18..20).map(|mut length| length.read_u16::<BigEndian>().unwrap())
+    }
+
+    /// DSS checksum of the data carried with the option.
+    pub fn checksum(&self) -> Option<u16> {
+        self.buffer.as_ref().get(20..22).map(|mut checksum| checksum.read_u16::<BigEndian>().unwrap())
+    }
+}
+
+/// MP_JOIN option parser.
+pub struct Join<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Join<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::mptcp::Join")
+            .field("is_backup", &self.is_backup())
+            .field("address", &self.address())
+            .field("token", &self.token())
+            .field("random", &self.random())
+            .field("hmac", &self.hmac())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Join<B> {
+    /// Parse an MP_JOIN option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Join<B>> {
+        let option = Join { buffer };
+
+        match option.buffer.as_ref().len() {
+            10 | 14 | 22 => Ok(option),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Join<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Join<B> {
+    /// Whether the subflow is to be used as backup.
+    pub fn is_backup(&self) -> bool {
+        self.buffer.as_ref().len() != 22 && self.buffer.as_ref()[0] & 0b1 != 0
+    }
+
+    /// Address identifier, on SYN and SYN/ACK.
+    pub fn address(&self) -> Option<u8> {
+        match self.buffer.as_ref().len() {
+            22 => None,
+            _ => Some(self.buffer.as_ref()[1]),
+        }
+    }
+
+    /// Token of the receiver, on SYN.
+    pub fn token(&self) -> Option<u32> {
+        match self.buffer.as_ref().len() {
+            10 => Some((&self.buffer.as_ref()[2..]).read_u32::<BigEndian>().unwrap()),
+            _ => None,
+        }
+    }
+
+    /// Random number of the sender, on SYN and SYN/ACK.
+    pub fn random(&self) -> Option<u32> {
+        match self.buffer.as_ref().len() {
+            10 => Some((&self.buffer.as_ref()[6..]).read_u32::<BigEndian>().unwrap()),
+            14 => Some((&self.buffer.as_ref()[10..]).read_u32::<BigEndian>().unwrap()),
+            _ => None,
+        }
+    }
+
+    /// HMAC of the sender, truncated on SYN/ACK and full on ACK.
+    pub fn hmac(&self) -> Option<&[u8]> {
+        match self.buffer.as_ref().len() {
+            14 => Some(&self.buffer.as_ref()[2..10]),
+            22 => Some(&self.buffer.as_ref()[2..22]),
+            _ => None,
+        }
+    }
+}
+
+/// DSS option parser.
+pub struct DataSequence<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for DataSequence<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::mptcp::DataSequence")
+            .field("is_fin", &self.is_fin())
+            .field("acknowledgment", &self.acknowledgment())
+            .field("mapping", &self.mapping())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> DataSequence<B> {
+    /// Parse a DSS option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<DataSequence<B>> {
+        let option = DataSequence { buffer };
+
+        if option.buffer.as_ref().len() < 2 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        let length = 2 + option.acknowledgment_size() + option.mapping_size();
+
+        if option.buffer.as_ref().len() != length && (option.mapping_size() == 0 || option.buffer.as_ref().len() != length + 2) {
+            return Err(ErrorKind::InvalidPacket.into());
+        }
+
+        Ok(option)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for DataSequence<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> DataSequence<B> {
+    /// Whether the mapping covers the end of the data.
+    pub fn is_fin(&self) -> bool {
+        self.flags() & 0b1_0000 != 0
+    }
+
+    /// Data acknowledgment.
+    pub fn acknowledgment(&self) -> Option<u64> {
+        Self::read(&self.buffer.as_ref()[2..], self.acknowledgment_size())
+    }
+
+    /// Data sequence mapping.
+    pub fn mapping(&self) -> Option<Mapping> {
+        let buffer = &self.buffer.as_ref()[2 + self.acknowledgment_size()..];
+        let size = self.mapping_size().checked_sub(6)?;
+
+        Some(Mapping {
+            sequence: Self::read(buffer, size)?,
+            subflow: (&buffer[size..]).read_u32::<BigEndian>().unwrap(),
+            length: (&buffer[size + 4..]).read_u16::<BigEndian>().unwrap(),
+            checksum: (&buffer[size + 6..]).read_u16::<BigEndian>().ok(),
+        })
+    }
+
+    fn flags(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    fn acknowledgment_size(&self) -> usize {
+        match self.flags() & 0b11 {
+            0b01 => 4,
+            0b11 => 8,
+            _ => 0,
+        }
+    }
+
+    fn mapping_size(&self) -> usize {
+        match self.flags() & 0b1100 {
+            0b0100 => 4 + 6,
+            0b1100 => 8 + 6,
+            _ => 0,
+        }
+    }
+
+    fn read(mut buffer: &[u8], size: usize) -> Option<u64> {
+        match size {
+            4 => Some(u64::from(buffer.read_u32::<BigEndian>().unwrap())),
+            8 => Some(buffer.read_u64::<BigEndian>().unwrap()),
+            _ => None,
+        }
+    }
+}
+
+/// ADD_ADDR option parser.
+pub struct AddAddress<B> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for AddAddress<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("tcp::option::mptcp::AddAddress")
+            .field("is_echo", &self.is_echo())
+            .field("id", &self.id())
+            .field("address", &self.address())
+            .field("port", &self.port())
+            .field("hmac", &self.hmac())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> AddAddress<B> {
+    /// Parse an ADD_ADDR option, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<AddAddress<B>> {
+        let option = AddAddress { buffer };
+
+        if option.buffer.as_ref().len() < 2 {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        let length = if option.is_echo() {
+            option.buffer.as_ref().len()
+        } else {
+            option.buffer.as_ref().len().wrapping_sub(8)
+        };
+
+        match length {
+            6 | 8 | 18 | 20 => Ok(option),
+
+            _ => Err(ErrorKind::InvalidPacket.into()),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for AddAddress<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> AddAddress<B> {
+    /// Whether the option echoes a received one, and so carries no HMAC.
+    pub fn is_echo(&self) -> bool {
+        self.buffer.as_ref()[0] & 0b1 != 0
+    }
+
+    /// Address identifier.
+    pub fn id(&self) -> u8 {
+        self.buffer.as_ref()[1]
+    }
+
+    /// Advertised address.
+    pub fn address(&self) -> IpAddr {
+        let buffer = self.buffer.as_ref();
+
+        if self.size() < 18 {
+            IpAddr::V4(Ipv4Addr::new(buffer[2], buffer[3], buffer[4], buffer[5]))
+        } else {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&buffer[2..18]);
+
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+    }
+
+    /// Advertised port.
+    pub fn port(&self) -> Option<u16> {
+        match self.size() {
+            8 => Some((&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()),
+            20 => Some((&self.buffer.as_ref()[18..]).read_u16::<BigEndian>().unwrap()),
+            _ => None,
+        }
+    }
+
+    /// Truncated HMAC, unless the option is an echo.
+    pub fn hmac(&self) -> Option<u64> {
+        if self.is_echo() {
+            return None;
+        }
+
+        Some((&self.buffer.as_ref()[self.size()..]).read_u64::<BigEndian>().unwrap())
+    }
+
+    /// Size of the option without the HMAC.
+    fn size(&self) -> usize {
+        if self.is_echo() {
+            self.buffer.as_ref().len()
+        } else {
+            self.buffer.as_ref().len() - 8
+        }
+    }
+}
+
+impl From<u8> for Subtype {
+    fn from(value: u8) -> Self {
+        use self::Subtype::*;
+
+        match value {
+            0 => Capable,
+            1 => Join,
+            2 => DataSequence,
+            3 => AddAddress,
+            4 => RemoveAddress,
+            5 => Priority,
+            6 => Fail,
+            7 => FastClose,
+            8 => Reset,
+            n => Unknown(n),
+        }
+    }
+}
+
+impl Into<u8> for Subtype {
+    fn into(self) -> u8 {
+        use self::Subtype::*;
+
+        match self {
+            Capable => 0,
+            Join => 1,
+            DataSequence => 2,
+            AddAddress => 3,
+            RemoveAddress => 4,
+            Priority => 5,
+            Fail => 6,
+            FastClose => 7,
+            Reset => 8,
+            Unknown(n) => n,
+        }
+    }
+}
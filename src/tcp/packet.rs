@@ -180,6 +180,31 @@ impl<B: AsRef<[u8]>> Packet<B> {
             buffer: &self.buffer.as_ref()[20..(self.offset() as usize * 4)],
         }
     }
+
+    /// Maximum segment size from the options.
+    pub fn mss(&self) -> Option<u16> {
+        self.options().filter_map(|o| o.ok()?.mss().ok()).next()
+    }
+
+    /// Window scale shift count from the options.
+    pub fn window_scale(&self) -> Option<u8> {
+        self.options().filter_map(|o| o.ok()?.window_scale().ok()).next()
+    }
+
+    /// Whether SACK is permitted by the options.
+    pub fn is_sack_permitted(&self) -> bool {
+        self.options().any(|o| o.is_ok_and(|o| o.is_sack_permitted()))
+    }
+
+    /// SACK blocks from the options.
+    pub fn sack(&self) -> Vec<(u32, u32)> {
+        self.options().filter_map(|o| o.ok()).filter_map(|o| o.sack().ok().map(|b| b.collect::<Vec<_>>())).flatten().collect()
+    }
+
+    /// Timestamp value and echo reply from the options.
+    pub fn timestamps(&self) -> Option<(u32, u32)> {
+        self.options().filter_map(|o| o.ok()?.timestamp().ok()).next()
+    }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
@@ -215,7 +240,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
     pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
         let old = self.header()[12] & 0b1111_0000;
 
-        Cursor::new(&mut self.header_mut()[12..]).write_u16::<BigEndian>((u16::from(old)) << 12 | value.bits())?;
+        Cursor::new(&mut self.header_mut()[12..]).write_u16::<BigEndian>((u16::from(old)) << 8 | value.bits())?;
 
         Ok(self)
     }
@@ -350,7 +375,10 @@ impl<'a> Iterator for OptionIter<'a> {
                 Some(Ok(option))
             }
 
-            Err(error) => Some(Err(error)),
+            Err(error) => {
+                self.buffer = &[];
+                Some(Err(error))
+            }
         }
     }
 }
@@ -376,6 +404,52 @@ mod test {
 
         assert_eq!(tcp.flags(), tcp::flag::SYN);
         assert_eq!(tcp.destination(), 80);
+
+        assert_eq!(tcp.mss(), Some(1460));
+        assert_eq!(tcp.window_scale(), Some(7));
+        assert!(tcp.is_sack_permitted());
+        assert!(tcp.sack().is_empty());
+        assert_eq!(tcp.timestamps(), Some((0x592b2997, 0)));
+    }
+
+    #[test]
+    fn options() {
+        let raw = [
+            // Header.
+            0x00u8, 0x50, 0x9b, 0x8a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0xb0, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            // No Operation, No Operation, SACK with two blocks.
+            0x01, 0x01, 0x05, 0x12, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00,
+            // Truncated Maximum Segment Size, End of Option List.
+            0x02, 0x03, 0x05, 0x00,
+        ];
+
+        let tcp = tcp::Packet::new(&raw[..]).unwrap();
+
+        assert_eq!(tcp.sack(), vec![(0x1000, 0x2000), (0xffffff00, 0x100)]);
+        assert_eq!(tcp.mss(), None);
+        assert_eq!(tcp.window_scale(), None);
+        assert!(!tcp.is_sack_permitted());
+        assert_eq!(tcp.timestamps(), None);
+
+        let options = tcp.options().collect::<Vec<_>>();
+        assert_eq!(options.len(), 4);
+        assert!(options[3].as_ref().unwrap().mss().is_err());
+        assert!(options[2].as_ref().unwrap().timestamp().is_err());
+    }
+
+    #[test]
+    fn options_malformed() {
+        let raw = [
+            0x00u8, 0x50, 0x9b, 0x8a, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x60, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            // Maximum Segment Size with a zero length.
+            0x02, 0x00, 0x05, 0xb4,
+        ];
+
+        let tcp = tcp::Packet::new(&raw[..]).unwrap();
+
+        assert_eq!(tcp.options().count(), 1);
+        assert!(tcp.options().next().unwrap().is_err());
+        assert_eq!(tcp.mss(), None);
     }
 
     #[test]
@@ -0,0 +1,551 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use crate::ip;
+use crate::packet::Packet as P;
+use crate::tcp::{flag, Packet};
+
+/// Policy used to resolve data sent more than once by overlapping segments.
+///
+/// Data already delivered is never replaced, the policy only applies to
+/// segments buffered out of order.
+#[derive(Eq, PartialEq, Copy, Clone, Default, Debug)]
+pub enum Policy {
+    /// The data received first is kept.
+    #[default]
+    First,
+
+    /// The data received last is kept.
+    Last,
+}
+
+/// Key identifying one direction of a connection.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Key {
+    /// Source address.
+    pub source: IpAddr,
+
+    /// Source port.
+    pub source_port: u16,
+
+    /// Destination address.
+    pub destination: IpAddr,
+
+    /// Destination port.
+    pub destination_port: u16,
+}
+
+impl Key {
+    /// Get the key for the given packet.
+    pub fn new<BI: AsRef<[u8]>, BT: AsRef<[u8]>>(ip: &ip::Packet<BI>, tcp: &Packet<BT>) -> Key {
+        let (source, destination) = match *ip {
+            ip::Packet::V4(ref packet) => (IpAddr::V4(packet.source()), IpAddr::V4(packet.destination())),
+
+            ip::Packet::V6(ref packet) => (IpAddr::V6(packet.source()), IpAddr::V6(packet.destination())),
+        };
+
+        Key {
+            source,
+            source_port: tcp.source(),
+            destination,
+            destination_port: tcp.destination(),
+        }
+    }
+
+    /// Key of the other direction of the connection.
+    pub fn reverse(&self) -> Key {
+        Key {
+            source: self.destination,
+            source_port: self.destination_port,
+            destination: self.source,
+            destination_port: self.source_port,
+        }
+    }
+}
+
+/// Event in the stream of one direction of a connection.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Event {
+    /// Data in order.
+    Data(Vec<u8>),
+
+    /// Amount of octets that were never seen, the following data comes after
+    /// them.
+    Gap(u64),
+
+    /// The stream was closed by a FIN.
+    Fin,
+
+    /// The connection was reset.
+    Reset,
+
+    /// The stream was idle past the timeout and has been dropped.
+    Timeout,
+}
+
+/// TCP stream reassembler.
+///
+/// Packets of both directions of a connection are pushed in the order they
+/// were captured, and the ordered data of each direction comes out as events.
+///
+/// # Note
+///
+/// A stream picked up without seeing its SYN starts at the first segment seen,
+/// data from later segments that falls before it is silently dropped and is
+/// not reported as a gap.
+///
+/// Data missing from the capture is reported as a gap once the peer
+/// acknowledges it, when the buffered data exceeds the window, or when the
+/// stream times out.
+///
+/// Time is supplied by the caller, streams are expired lazily when new packets
+/// are pushed or when `expire` is called.
+#[derive(Debug)]
+pub struct Reassembler {
+    policy: Policy,
+    timeout: Duration,
+    window: usize,
+
+    streams: HashMap<Key, Stream>,
+}
+
+#[derive(Debug)]
+struct Stream {
+    last: Instant,
+    closed: bool,
+
+    /// Sequence number of the first octet of data.
+    origin: u32,
+
+    /// Offset of the next octet to deliver.
+    delivered: u64,
+
+    /// Offset after the last octet, once the FIN is seen.
+    fin: Option<u64>,
+
+    /// Non overlapping data received out of order, by offset.
+    segments: BTreeMap<u64, Vec<u8>>,
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Reassembler::new()
+    }
+}
+
+impl Reassembler {
+    /// Create a reassembler with the default policy, a 5 minutes idle timeout
+    /// and a 1 MiB window.
+    pub fn new() -> Reassembler {
+        Reassembler {
+            policy: Default::default(),
+            timeout: Duration::from_secs(5 * 60),
+            window: 1024 * 1024,
+
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Overlap resolution policy.
+    pub fn policy(mut self, value: Policy) -> Self {
+        self.policy = value;
+        self
+    }
+
+    /// Time without packets after which a stream is dropped.
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Maximum amount of octets buffered out of order for a stream, past it
+    /// the missing data is skipped as a gap.
+    pub fn window(mut self, value: usize) -> Self {
+        self.window = value;
+        self
+    }
+
+    /// Number of streams still open.
+    pub fn pending(&self) -> usize {
+        self.streams.values().filter(|stream| !stream.closed).count()
+    }
+
+    /// Drop the streams that timed out, flushing their buffered data.
+    pub fn expire(&mut self, now: Instant) -> Vec<(Key, Event)> {
+        let timeout = self.timeout;
+        let expired = self
+            .streams
+            .iter()
+            .filter(|(_, stream)| now.saturating_duration_since(stream.last) >= timeout)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        let mut events = Vec::new();
+
+        for key in expired {
+            let mut stream = self.streams.remove(&key).unwrap();
+
+            if !stream.closed {
+                let end = stream.segments.iter().next_back().map(|(offset, data)| offset + data.len() as u64);
+                let end = stream.fin.or(end).unwrap_or(stream.delivered);
+                stream.drain(end, &key, &mut events);
+
+                if !stream.closed {
+                    events.push((key, Event::Timeout));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Push a packet, returning the events it caused on either direction of
+    /// its connection.
+    pub fn push<BI: AsRef<[u8]>, BT: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>, packet: &Packet<BT>, now: Instant) -> Vec<(Key, Event)> {
+        let mut events = self.expire(now);
+        let key = Key::new(ip, packet);
+        let flags = packet.flags();
+
+        if flags.contains(flag::RST) {
+            for key in &[key, key.reverse()] {
+                if let Some(stream) = self.streams.get_mut(key) {
+                    stream.last = now;
+
+                    if !stream.closed {
+                        stream.close();
+                        events.push((*key, Event::Reset));
+                    }
+                }
+            }
+
+            return events;
+        }
+
+        // The peer acknowledging data never seen means it was lost from the
+        // capture.
+        if flags.contains(flag::ACK) {
+            let reverse = key.reverse();
+
+            if let Some(stream) = self.streams.get_mut(&reverse) {
+                if !stream.closed {
+                    let mut offset = stream.offset(packet.acknowledgment());
+
+                    if let Some(fin) = stream.fin {
+                        offset = offset.min(fin as i64);
+                    }
+
+                    if offset > stream.delivered as i64 {
+                        stream.drain(offset as u64, &reverse, &mut events);
+                    }
+                }
+            }
+        }
+
+        let syn = flags.contains(flag::SYN);
+        let origin = if syn { packet.sequence().wrapping_add(1) } else { packet.sequence() };
+
+        // A new SYN on a closed stream is a new connection reusing the ports.
+        let fresh = match self.streams.get(&key) {
+            Some(stream) => stream.closed && syn && stream.origin != origin,
+            None => true,
+        };
+
+        if fresh {
+            self.streams.insert(
+                key,
+                Stream {
+                    last: now,
+                    closed: false,
+                    origin,
+                    delivered: 0,
+                    fin: None,
+                    segments: BTreeMap::new(),
+                },
+            );
+        }
+
+        let policy = self.policy;
+        let window = self.window;
+        let stream = self.streams.get_mut(&key).unwrap();
+        stream.last = now;
+
+        if stream.closed {
+            return events;
+        }
+
+        let offset = stream.offset(origin);
+        let payload = packet.payload();
+        stream.insert(offset, payload, policy);
+
+        if flags.contains(flag::FIN) && stream.fin.is_none() {
+            let end = offset + payload.len() as i64;
+
+            if end >= stream.delivered as i64 {
+                let end = end as u64;
+                stream.fin = Some(end);
+                stream.segments.retain(|&start, _| start < end);
+
+                if let Some((&start, segment)) = stream.segments.iter_mut().next_back() {
+                    segment.truncate((end - start) as usize);
+                }
+            }
+        }
+
+        // Skip over the missing data when too much is buffered behind it.
+        while stream.buffered() > window {
+            let start = *stream.segments.keys().next().unwrap();
+            stream.drain(start, &key, &mut events);
+        }
+
+        let delivered = stream.delivered;
+        stream.drain(delivered, &key, &mut events);
+
+        events
+    }
+}
+
+impl Stream {
+    /// Offset of the given sequence number, relative to the data delivered so
+    /// it's unaffected by wraparound.
+    fn offset(&self, sequence: u32) -> i64 {
+        let expected = self.origin.wrapping_add(self.delivered as u32);
+        self.delivered as i64 + i64::from(sequence.wrapping_sub(expected) as i32)
+    }
+
+    fn buffered(&self) -> usize {
+        self.segments.values().map(Vec::len).sum()
+    }
+
+    fn close(&mut self) {
+        self.closed = true;
+        self.segments.clear();
+    }
+
+    fn insert(&mut self, offset: i64, data: &[u8], policy: Policy) {
+        // Drop what was already delivered or is past the FIN.
+        let start = offset.max(self.delivered as i64) as u64;
+        let mut end = (offset + data.len() as i64).max(start as i64) as u64;
+
+        if let Some(fin) = self.fin {
+            end = end.min(fin);
+        }
+
+        if start >= end {
+            return;
+        }
+
+        let data = &data[(start as i64 - offset) as usize..(end as i64 - offset) as usize];
+        let overlapping = self
+            .segments
+            .range(..end)
+            .filter(|(&offset, segment)| offset + segment.len() as u64 > start)
+            .map(|(&offset, _)| offset)
+            .collect::<Vec<_>>();
+
+        match policy {
+            Policy::First => {
+                let mut cursor = start;
+
+                for offset in overlapping {
+                    if offset > cursor {
+                        self.segments.insert(cursor, data[(cursor - start) as usize..(offset - start) as usize].to_vec());
+                    }
+
+                    cursor = cursor.max(offset + self.segments[&offset].len() as u64);
+                }
+
+                if cursor < end {
+                    self.segments.insert(cursor, data[(cursor - start) as usize..].to_vec());
+                }
+            }
+
+            Policy::Last => {
+                for offset in overlapping {
+                    let segment = self.segments.remove(&offset).unwrap();
+
+                    if offset < start {
+                        self.segments.insert(offset, segment[..(start - offset) as usize].to_vec());
+                    }
+
+                    if offset + segment.len() as u64 > end {
+                        self.segments.insert(end, segment[(end - offset) as usize..].to_vec());
+                    }
+                }
+
+                self.segments.insert(start, data.to_vec());
+            }
+        }
+    }
+
+    /// Deliver the data in order, reporting anything missing before `until`
+    /// as gaps.
+    fn drain(&mut self, until: u64, key: &Key, events: &mut Vec<(Key, Event)>) {
+        let mut data = Vec::new();
+
+        loop {
+            match self.segments.keys().next().cloned() {
+                Some(offset) if offset == self.delivered => {
+                    let segment = self.segments.remove(&offset).unwrap();
+                    self.delivered += segment.len() as u64;
+                    data.extend_from_slice(&segment);
+                }
+
+                next if self.delivered < until => {
+                    if !data.is_empty() {
+                        events.push((*key, Event::Data(std::mem::take(&mut data))));
+                    }
+
+                    let end = next.map_or(until, |offset| offset.min(until));
+                    events.push((*key, Event::Gap(end - self.delivered)));
+                    self.delivered = end;
+                }
+
+                _ => break,
+            }
+        }
+
+        if !data.is_empty() {
+            events.push((*key, Event::Data(data)));
+        }
+
+        if self.fin == Some(self.delivered) {
+            self.close();
+            events.push((*key, Event::Fin));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::packet::Packet;
+    use crate::tcp::fixture::{none, segment};
+    use crate::tcp::reassembly::{Event, Policy, Reassembler};
+    use crate::tcp::{self, flag, Flags};
+
+    /// Push a segment from the client when `client` is set, or from the
+    /// server, returning the events without their keys.
+    fn push(reassembler: &mut Reassembler, client: bool, sequence: u32, acknowledgment: u32, flags: Flags, payload: &[u8], now: Instant) -> Vec<Event> {
+        let packet = segment(client, (sequence, acknowledgment, 1000), flags, payload, none);
+        let tcp = tcp::Packet::new(packet.payload()).unwrap();
+
+        reassembler.push(&packet, &tcp, now).into_iter().map(|(_, event)| event).collect()
+    }
+
+    fn data(value: &[u8]) -> Event {
+        Event::Data(value.to_vec())
+    }
+
+    #[test]
+    fn in_order() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(push(&mut reassembler, true, 100, 0, flag::SYN, b"", now).is_empty());
+        assert!(push(&mut reassembler, false, 500, 101, flag::SYN | flag::ACK, b"", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 101, 501, flag::ACK | flag::PSH, b"hello", now), vec![data(b"hello")]);
+        assert_eq!(push(&mut reassembler, false, 501, 106, flag::ACK | flag::PSH, b"hi", now), vec![data(b"hi")]);
+        assert_eq!(push(&mut reassembler, true, 106, 503, flag::ACK | flag::FIN, b" world", now), vec![data(b" world"), Event::Fin]);
+        assert_eq!(reassembler.pending(), 1);
+
+        // Retransmissions after the FIN are ignored.
+        assert!(push(&mut reassembler, true, 106, 503, flag::ACK | flag::FIN, b" world", now).is_empty());
+        assert_eq!(push(&mut reassembler, false, 503, 113, flag::ACK | flag::FIN, b"", now), vec![Event::Fin]);
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn out_of_order() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(push(&mut reassembler, true, 100, 0, flag::SYN, b"", now).is_empty());
+        assert!(push(&mut reassembler, true, 107, 0, flag::ACK, b"three", now).is_empty());
+        assert!(push(&mut reassembler, true, 104, 0, flag::ACK, b"two", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 101, 0, flag::ACK, b"one", now), vec![data(b"onetwothree")]);
+
+        // Retransmission and partial retransmission.
+        assert!(push(&mut reassembler, true, 101, 0, flag::ACK, b"one", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 110, 0, flag::ACK, b"eefour", now), vec![data(b"four")]);
+    }
+
+    #[test]
+    fn overlap() {
+        for &(policy, expected) in &[(Policy::First, &b"xaaabbbyy"[..]), (Policy::Last, &b"xabbbbbyy"[..])] {
+            let now = Instant::now();
+            let mut reassembler = Reassembler::new().policy(policy);
+
+            assert!(push(&mut reassembler, true, 100, 0, flag::SYN, b"", now).is_empty());
+            assert!(push(&mut reassembler, true, 102, 0, flag::ACK, b"aaa", now).is_empty());
+            assert!(push(&mut reassembler, true, 103, 0, flag::ACK, b"bbbbb", now).is_empty());
+            assert!(push(&mut reassembler, true, 108, 0, flag::ACK, b"yy", now).is_empty());
+            assert_eq!(push(&mut reassembler, true, 101, 0, flag::ACK, b"x", now), vec![data(expected)]);
+        }
+    }
+
+    #[test]
+    fn wraparound() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(push(&mut reassembler, true, u32::MAX - 2, 0, flag::SYN, b"", now).is_empty());
+        assert!(push(&mut reassembler, true, 2, 0, flag::ACK, b"world", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, u32::MAX - 1, 0, flag::ACK, b"hell", now), vec![data(b"hellworld")]);
+    }
+
+    #[test]
+    fn gaps() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new().window(8).timeout(Duration::from_secs(10));
+
+        assert!(push(&mut reassembler, true, 100, 0, flag::SYN, b"", now).is_empty());
+        assert!(push(&mut reassembler, false, 500, 101, flag::SYN | flag::ACK, b"", now).is_empty());
+
+        // The server acknowledges data missing from the capture.
+        assert!(push(&mut reassembler, true, 104, 501, flag::ACK, b"def", now).is_empty());
+        assert_eq!(push(&mut reassembler, false, 501, 107, flag::ACK, b"", now), vec![Event::Gap(3), data(b"def")]);
+
+        // Too much data is buffered behind a hole.
+        assert!(push(&mut reassembler, true, 110, 501, flag::ACK, b"jklm", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 114, 501, flag::ACK, b"nopqr", now), vec![Event::Gap(3), data(b"jklmnopqr")]);
+
+        // The stream times out with data still buffered.
+        assert!(push(&mut reassembler, true, 121, 501, flag::ACK, b"uv", now).is_empty());
+        let events = reassembler.expire(now + Duration::from_secs(10));
+        let client = events.iter().filter(|(key, _)| key.source_port == 1337).map(|(_, event)| event.clone()).collect::<Vec<_>>();
+        assert_eq!(client, vec![Event::Gap(2), data(b"uv"), Event::Timeout]);
+        assert_eq!(events.len(), 4);
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn pickup() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        // The stream starts at the first segment seen, anything before it is
+        // dropped.
+        assert_eq!(push(&mut reassembler, true, 205, 0, flag::ACK, b"world", now), vec![data(b"world")]);
+        assert!(push(&mut reassembler, true, 200, 0, flag::ACK, b"hello", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 208, 0, flag::ACK, b"ld!", now), vec![data(b"!")]);
+    }
+
+    #[test]
+    fn reset() {
+        let now = Instant::now();
+        let mut reassembler = Reassembler::new();
+
+        assert!(push(&mut reassembler, true, 100, 0, flag::SYN, b"", now).is_empty());
+        assert!(push(&mut reassembler, false, 500, 101, flag::SYN | flag::ACK, b"", now).is_empty());
+        assert!(push(&mut reassembler, true, 105, 501, flag::ACK, b"lost", now).is_empty());
+        assert_eq!(push(&mut reassembler, false, 501, 0, flag::RST, b"", now), vec![Event::Reset, Event::Reset]);
+        assert_eq!(reassembler.pending(), 0);
+        assert!(push(&mut reassembler, true, 101, 501, flag::ACK, b"data", now).is_empty());
+
+        // A new connection reusing the ports.
+        assert!(push(&mut reassembler, true, 9000, 0, flag::SYN, b"", now).is_empty());
+        assert_eq!(push(&mut reassembler, true, 9001, 0, flag::ACK, b"again", now), vec![data(b"again")]);
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::ip;
+use crate::packet::Packet as P;
+use crate::tcp::reassembly::Key;
+use crate::tcp::{flag, Packet};
+
+/// State of a connection, as seen by an observer between the two ends.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum State {
+    /// The initiator sent a SYN.
+    SynSent,
+
+    /// The responder answered with a SYN.
+    SynReceived,
+
+    /// The handshake completed.
+    Established,
+
+    /// One end sent a FIN.
+    FinWait,
+
+    /// The FIN was acknowledged, the other end can still send data.
+    CloseWait,
+
+    /// Both ends sent a FIN.
+    LastAck,
+
+    /// Both FINs were acknowledged.
+    TimeWait,
+
+    /// The connection was reset.
+    Closed,
+}
+
+/// How a packet relates to the tracked connections.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Verdict {
+    /// The packet starts a connection, or belongs to one the other end didn't
+    /// answer yet.
+    New,
+
+    /// The packet belongs to a connection seen in both directions.
+    Established,
+
+    /// The packet doesn't fit the state of its connection.
+    Invalid,
+}
+
+/// Tracked connection.
+#[derive(Debug)]
+pub struct Connection {
+    key: Key,
+    state: State,
+    start: Instant,
+    last: Instant,
+
+    /// End that sent the first FIN, 0 being the initiator.
+    closer: Option<usize>,
+    ends: [End; 2],
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+struct End {
+    /// Initial sequence number, when the SYN was seen.
+    initial: Option<u32>,
+
+    /// Sequence number after the last one sent.
+    next: u32,
+
+    /// Largest window advertised, scaled.
+    window: u32,
+
+    /// Window scale offered in the SYN.
+    scale: Option<u8>,
+
+    /// Sequence number acknowledging the FIN.
+    fin: Option<u32>,
+
+    /// Whether the other end acknowledged the FIN.
+    closed: bool,
+}
+
+impl Connection {
+    /// Key of the direction from the initiator.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Current state.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Time of the first packet.
+    pub fn start(&self) -> Instant {
+        self.start
+    }
+
+    /// Time of the last packet.
+    pub fn last(&self) -> Instant {
+        self.last
+    }
+
+    /// Shift to apply to the windows advertised by the given end, only
+    /// enabled when both ends offered it.
+    fn scale(&self, end: usize) -> u8 {
+        match (self.ends[0].scale, self.ends[1].scale) {
+            (Some(_), Some(_)) => self.ends[end].scale.unwrap().min(14),
+            _ => 0,
+        }
+    }
+
+    /// Whether the reset sent by the given end is within the window of the
+    /// other one.
+    fn accepts_reset<B: AsRef<[u8]>>(&self, end: usize, packet: &Packet<B>) -> bool {
+        let (sender, receiver) = (&self.ends[end], &self.ends[1 - end]);
+
+        if self.state == State::SynSent {
+            return end == 1 && packet.flags().contains(flag::ACK) && Some(packet.acknowledgment()) == syn_acknowledgment(&self.ends[0]);
+        }
+
+        receiver.window == 0 || packet.sequence().wrapping_sub(sender.next) <= receiver.window
+    }
+
+    /// Record the sequence numbers and window of a packet sent by the given
+    /// end.
+    fn update<B: AsRef<[u8]>>(&mut self, end: usize, packet: &Packet<B>) {
+        let flags = packet.flags();
+        let next = packet
+            .sequence()
+            .wrapping_add(packet.payload().len() as u32)
+            .wrapping_add(flags.contains(flag::SYN) as u32)
+            .wrapping_add(flags.contains(flag::FIN) as u32);
+
+        if flags.contains(flag::SYN) {
+            self.ends[end].initial = Some(packet.sequence());
+            self.ends[end].scale = packet.window_scale();
+            self.ends[end].next = next;
+            self.ends[end].window = u32::from(packet.window());
+
+            return;
+        }
+
+        if next.wrapping_sub(self.ends[end].next) as i32 > 0 {
+            self.ends[end].next = next;
+        }
+
+        let window = u32::from(packet.window()) << self.scale(end);
+        self.ends[end].window = self.ends[end].window.max(window);
+
+        if flags.contains(flag::FIN) {
+            self.ends[end].fin = Some(next);
+        }
+    }
+}
+
+/// Acknowledgment number for the SYN of the given end.
+fn syn_acknowledgment(end: &End) -> Option<u32> {
+    end.initial.map(|initial| initial.wrapping_add(1))
+}
+
+/// Passive TCP connection tracker.
+///
+/// Packets of both directions are pushed in the order they were captured, each
+/// connection is advanced through its states and every packet gets a verdict.
+///
+/// # Note
+///
+/// Time is supplied by the caller, connections are expired lazily when new
+/// packets are pushed or when `expire` is called.
+#[derive(Debug)]
+pub struct Tracker {
+    pickup: bool,
+    timeout: Duration,
+    transient: Duration,
+
+    connections: HashMap<Key, Connection>,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Tracker::new()
+    }
+}
+
+impl Tracker {
+    /// Create a tracker picking up connections already established, with a 1
+    /// hour timeout for established connections and a 2 minutes one for the
+    /// others.
+    pub fn new() -> Tracker {
+        Tracker {
+            pickup: true,
+            timeout: Duration::from_secs(60 * 60),
+            transient: Duration::from_secs(2 * 60),
+
+            connections: HashMap::new(),
+        }
+    }
+
+    /// Whether to track connections whose handshake wasn't seen, otherwise
+    /// their packets are invalid.
+    pub fn pickup(mut self, value: bool) -> Self {
+        self.pickup = value;
+        self
+    }
+
+    /// Time without packets after which an established connection is dropped.
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Time without packets after which a connection opening or closing is
+    /// dropped.
+    pub fn transient(mut self, value: Duration) -> Self {
+        self.transient = value;
+        self
+    }
+
+    /// Number of connections tracked.
+    pub fn tracked(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Get the connection of the given direction.
+    pub fn get(&self, key: &Key) -> Option<&Connection> {
+        self.connections.get(key).or_else(|| self.connections.get(&key.reverse()))
+    }
+
+    /// Get the state of the connection of the given direction.
+    pub fn state(&self, key: &Key) -> Option<State> {
+        self.get(key).map(Connection::state)
+    }
+
+    /// Drop the connections that timed out, returning their keys.
+    pub fn expire(&mut self, now: Instant) -> Vec<Key> {
+        let (timeout, transient) = (self.timeout, self.transient);
+        let expired = self
+            .connections
+            .iter()
+            .filter(|(_, connection)| {
+                let idle = now.saturating_duration_since(connection.last);
+
+                match connection.state {
+                    State::Established => idle >= timeout,
+                    _ => idle >= transient,
+                }
+            })
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for key in &expired {
+            self.connections.remove(key);
+        }
+
+        expired
+    }
+
+    /// Push a packet, advancing its connection and returning its verdict.
+    pub fn push<BI: AsRef<[u8]>, BT: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>, packet: &Packet<BT>, now: Instant) -> Verdict {
+        self.expire(now);
+
+        let key = Key::new(ip, packet);
+        let flags = packet.flags();
+        let syn = flags.contains(flag::SYN);
+        let ack = flags.contains(flag::ACK);
+
+        let (key, end) = if self.connections.contains_key(&key) {
+            (key, 0)
+        } else if self.connections.contains_key(&key.reverse()) {
+            (key.reverse(), 1)
+        } else {
+            return self.open(key, packet, now);
+        };
+
+        let connection = self.connections.get_mut(&key).unwrap();
+
+        if flags.contains(flag::RST) {
+            if !connection.accepts_reset(end, packet) {
+                return Verdict::Invalid;
+            }
+
+            connection.state = State::Closed;
+            connection.last = now;
+
+            return Verdict::Established;
+        }
+
+        // A new SYN from the initiator reuses the ports of a closed connection.
+        if matches!(connection.state, State::TimeWait | State::Closed) && syn && !ack && end == 0 && Some(packet.sequence()) != connection.ends[0].initial {
+            self.connections.remove(&key);
+            return self.open(key, packet, now);
+        }
+
+        let state = match (connection.state, end) {
+            (State::SynSent, 0) if syn && !ack => State::SynSent,
+
+            (State::SynSent, 1) if syn && (!ack || Some(packet.acknowledgment()) == syn_acknowledgment(&connection.ends[0])) => State::SynReceived,
+
+            (State::SynReceived, 0) if syn && !ack => State::SynReceived,
+
+            (State::SynReceived, 1) if syn => State::SynReceived,
+
+            (State::SynReceived, _) if ack && Some(packet.acknowledgment()) == syn_acknowledgment(&connection.ends[1 - end]) => State::Established,
+
+            (State::SynReceived, 1) if ack => State::SynReceived,
+
+            (State::SynSent, _) | (State::SynReceived, _) | (State::Closed, _) => return Verdict::Invalid,
+
+            (state, _) if syn => {
+                // Retransmissions of the handshake are fine, anything else
+                // isn't.
+                if ack != (end == 1) || Some(packet.sequence()) != connection.ends[end].initial {
+                    return Verdict::Invalid;
+                }
+
+                state
+            }
+
+            (state, _) => state,
+        };
+
+        let verdict = if state == State::SynSent { Verdict::New } else { Verdict::Established };
+
+        connection.state = state;
+        connection.last = now;
+        connection.update(end, packet);
+
+        // Acknowledgment of the FIN of the other end.
+        let other = 1 - end;
+        if ack && connection.ends[other].fin == Some(packet.acknowledgment()) {
+            connection.ends[other].closed = true;
+            connection.state = match (connection.state, connection.closer) {
+                (State::FinWait, Some(closer)) if closer == other => State::CloseWait,
+                (State::LastAck, _) if connection.ends[end].closed => State::TimeWait,
+                (state, _) => state,
+            };
+        }
+
+        if flags.contains(flag::FIN) {
+            connection.state = match (connection.state, connection.closer) {
+                (State::Established, _) => {
+                    connection.closer = Some(end);
+                    State::FinWait
+                }
+
+                (State::FinWait, Some(closer)) | (State::CloseWait, Some(closer)) if closer != end => State::LastAck,
+
+                (state, _) => state,
+            };
+        }
+
+        verdict
+    }
+
+    /// Start tracking a connection from its first packet.
+    fn open<B: AsRef<[u8]>>(&mut self, key: Key, packet: &Packet<B>, now: Instant) -> Verdict {
+        let flags = packet.flags();
+
+        let state = if flags.contains(flag::SYN) && !flags.contains(flag::ACK) {
+            State::SynSent
+        } else if self.pickup && flags.contains(flag::ACK) && !flags.intersects(flag::SYN | flag::RST) {
+            State::Established
+        } else {
+            return Verdict::Invalid;
+        };
+
+        let mut connection = Connection {
+            key,
+            state,
+            start: now,
+            last: now,
+
+            closer: None,
+            ends: Default::default(),
+        };
+
+        connection.update(0, packet);
+        self.connections.insert(key, connection);
+
+        Verdict::New
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::packet::Packet;
+    use crate::tcp::fixture::{none, segment};
+    use crate::tcp::tracker::{State, Tracker, Verdict};
+    use crate::tcp::{self, flag, Flags};
+
+    /// Push a segment from the client when `client` is set, or from the
+    /// server, returning its verdict and the state of the connection.
+    fn push(tracker: &mut Tracker, client: bool, sequence: u32, acknowledgment: u32, flags: Flags, payload: &[u8], now: Instant) -> (Verdict, Option<State>) {
+        let packet = segment(client, (sequence, acknowledgment, 1000), flags, payload, none);
+        let tcp = tcp::Packet::new(packet.payload()).unwrap();
+        let verdict = tracker.push(&packet, &tcp, now);
+
+        (verdict, tracker.state(&tcp::reassembly::Key::new(&packet, &tcp)))
+    }
+
+    #[test]
+    fn lifecycle() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new();
+
+        assert_eq!(push(&mut tracker, true, 100, 0, flag::SYN, b"", now), (Verdict::New, Some(State::SynSent)));
+        assert_eq!(push(&mut tracker, true, 100, 0, flag::SYN, b"", now), (Verdict::New, Some(State::SynSent)));
+        assert_eq!(push(&mut tracker, false, 500, 101, flag::SYN | flag::ACK, b"", now), (Verdict::Established, Some(State::SynReceived)));
+        assert_eq!(push(&mut tracker, true, 101, 501, flag::ACK, b"", now), (Verdict::Established, Some(State::Established)));
+        assert_eq!(
+            push(&mut tracker, true, 101, 501, flag::ACK | flag::PSH, b"hello", now),
+            (Verdict::Established, Some(State::Established))
+        );
+
+        // Active close by the client.
+        assert_eq!(push(&mut tracker, true, 106, 501, flag::ACK | flag::FIN, b"", now), (Verdict::Established, Some(State::FinWait)));
+        assert_eq!(push(&mut tracker, false, 501, 107, flag::ACK, b"", now), (Verdict::Established, Some(State::CloseWait)));
+        assert_eq!(push(&mut tracker, false, 501, 107, flag::ACK | flag::FIN, b"bye", now), (Verdict::Established, Some(State::LastAck)));
+        assert_eq!(push(&mut tracker, true, 107, 504, flag::ACK, b"", now), (Verdict::Established, Some(State::LastAck)));
+        assert_eq!(push(&mut tracker, true, 107, 505, flag::ACK, b"", now), (Verdict::Established, Some(State::TimeWait)));
+
+        // Reusing the ports.
+        assert_eq!(push(&mut tracker, true, 9000, 0, flag::SYN, b"", now), (Verdict::New, Some(State::SynSent)));
+        assert_eq!(tracker.tracked(), 1);
+    }
+
+    #[test]
+    fn simultaneous_close() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new();
+
+        push(&mut tracker, true, 100, 0, flag::SYN, b"", now);
+        push(&mut tracker, false, 500, 101, flag::SYN | flag::ACK, b"", now);
+        push(&mut tracker, true, 101, 501, flag::ACK, b"", now);
+
+        assert_eq!(push(&mut tracker, false, 501, 101, flag::ACK | flag::FIN, b"", now).1, Some(State::FinWait));
+        assert_eq!(push(&mut tracker, true, 101, 501, flag::ACK | flag::FIN, b"", now).1, Some(State::LastAck));
+
+        // The FIN of the server isn't acknowledged yet.
+        assert_eq!(push(&mut tracker, false, 502, 102, flag::ACK, b"", now).1, Some(State::LastAck));
+        assert_eq!(push(&mut tracker, true, 102, 502, flag::ACK, b"", now).1, Some(State::TimeWait));
+    }
+
+    #[test]
+    fn syn() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new();
+
+        push(&mut tracker, true, 100, 0, flag::SYN, b"", now);
+        push(&mut tracker, false, 500, 101, flag::SYN | flag::ACK, b"", now);
+        push(&mut tracker, true, 101, 501, flag::ACK, b"", now);
+
+        // Retransmissions of the handshake.
+        assert_eq!(push(&mut tracker, true, 100, 0, flag::SYN, b"", now), (Verdict::Established, Some(State::Established)));
+        assert_eq!(push(&mut tracker, false, 500, 101, flag::SYN | flag::ACK, b"", now), (Verdict::Established, Some(State::Established)));
+
+        // A SYN with another initial sequence number leaves the ends alone.
+        assert_eq!(push(&mut tracker, true, 7000, 0, flag::SYN, b"", now), (Verdict::Invalid, Some(State::Established)));
+        assert_eq!(push(&mut tracker, false, 9000, 101, flag::SYN | flag::ACK, b"", now), (Verdict::Invalid, Some(State::Established)));
+        assert_eq!(push(&mut tracker, true, 101, 0, flag::RST, b"", now), (Verdict::Established, Some(State::Closed)));
+    }
+
+    #[test]
+    fn invalid() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new().pickup(false);
+
+        // No handshake seen.
+        assert_eq!(push(&mut tracker, true, 101, 501, flag::ACK, b"data", now), (Verdict::Invalid, None));
+
+        // SYN/ACK not acknowledging the SYN.
+        push(&mut tracker, true, 100, 0, flag::SYN, b"", now);
+        assert_eq!(push(&mut tracker, false, 500, 1234, flag::SYN | flag::ACK, b"", now), (Verdict::Invalid, Some(State::SynSent)));
+
+        // Data before the handshake completes.
+        assert_eq!(push(&mut tracker, true, 101, 0, flag::ACK, b"data", now), (Verdict::Invalid, Some(State::SynSent)));
+
+        // Reset not acknowledging the SYN.
+        assert_eq!(push(&mut tracker, false, 0, 1234, flag::RST | flag::ACK, b"", now), (Verdict::Invalid, Some(State::SynSent)));
+        assert_eq!(push(&mut tracker, false, 0, 101, flag::RST | flag::ACK, b"", now), (Verdict::Established, Some(State::Closed)));
+        assert_eq!(push(&mut tracker, true, 101, 0, flag::ACK, b"data", now), (Verdict::Invalid, Some(State::Closed)));
+    }
+
+    #[test]
+    fn reset() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new();
+
+        // Picked up in the middle.
+        assert_eq!(push(&mut tracker, true, 1000, 5000, flag::ACK, b"data", now), (Verdict::New, Some(State::Established)));
+        assert_eq!(push(&mut tracker, false, 5000, 1004, flag::ACK, b"", now), (Verdict::Established, Some(State::Established)));
+
+        // Out of the window.
+        assert_eq!(push(&mut tracker, true, 90000, 0, flag::RST, b"", now), (Verdict::Invalid, Some(State::Established)));
+        assert_eq!(push(&mut tracker, true, 1004, 0, flag::RST, b"", now), (Verdict::Established, Some(State::Closed)));
+    }
+
+    #[test]
+    fn expire() {
+        let now = Instant::now();
+        let mut tracker = Tracker::new().timeout(Duration::from_secs(60)).transient(Duration::from_secs(10));
+
+        push(&mut tracker, true, 100, 0, flag::SYN, b"", now);
+        assert_eq!(tracker.expire(now + Duration::from_secs(10)).len(), 1);
+
+        push(&mut tracker, true, 100, 0, flag::SYN, b"", now);
+        push(&mut tracker, false, 500, 101, flag::SYN | flag::ACK, b"", now);
+        push(&mut tracker, true, 101, 501, flag::ACK, b"", now);
+        assert!(tracker.expire(now + Duration::from_secs(30)).is_empty());
+        assert_eq!(tracker.expire(now + Duration::from_secs(60)).len(), 1);
+        assert_eq!(tracker.tracked(), 0);
+    }
+}
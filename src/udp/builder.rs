@@ -127,7 +127,7 @@ mod test {
     use crate::ip;
     use crate::packet::Packet;
     use crate::udp;
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[test]
     fn simple() {
@@ -164,4 +164,38 @@ mod test {
         assert_eq!(udp.destination(), 9001);
         assert!(udp.is_valid(&ip::Packet::from(&ip)));
     }
+
+    #[test]
+    fn v6() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(64)
+            .unwrap()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .udp()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(ip.payload_length(), 11);
+        assert_eq!(ip.next_header(), ip::Protocol::Udp);
+        assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+
+        let udp = udp::Packet::new(ip.payload()).unwrap();
+        assert_eq!(udp.source(), 1337);
+        assert_eq!(udp.destination(), 9001);
+        assert_eq!(udp.length(), 11);
+        assert_eq!(udp.payload(), b"lol");
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+    }
 }
@@ -14,31 +14,50 @@ use crate::ip::Protocol;
 /// Since the checksum for UDP packets includes a pseudo-header based on the
 /// enclosing IP packet, one has to be given.
 pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
-    use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+    match *ip {
+        ip::Packet::V4(ref packet) => checksum_v4(packet, buffer),
+
+        ip::Packet::V6(ref packet) => checksum_v6(packet, buffer),
+    }
+}
+
+/// Calculate the checksum for a UDP packet enclosed in an IPv4 packet.
+pub fn checksum_v4<B: AsRef<[u8]>>(ip: &ip::v4::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    let mut prefix = [0u8; 12];
+    prefix[0..4].copy_from_slice(&ip.source().octets());
+    prefix[4..8].copy_from_slice(&ip.destination().octets());
+
+    prefix[9] = Protocol::Udp.into();
+    Cursor::new(&mut prefix[10..]).write_u16::<BigEndian>(buffer.len() as u16).unwrap();
+
+    sum(&prefix, buffer)
+}
+
+/// Calculate the checksum for a UDP packet enclosed in an IPv6 packet.
+pub fn checksum_v6<B: AsRef<[u8]>>(ip: &ip::v6::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
     use std::io::Cursor;
 
     let mut prefix = [0u8; 40];
-    match *ip {
-        ip::Packet::V4(ref packet) => {
-            prefix[0..4].copy_from_slice(&packet.source().octets());
-            prefix[4..8].copy_from_slice(&packet.destination().octets());
+    prefix[0..16].copy_from_slice(&ip.source().octets());
+    prefix[16..32].copy_from_slice(&ip.final_destination().octets());
 
-            prefix[9] = Protocol::Udp.into();
-            Cursor::new(&mut prefix[10..]).write_u16::<BigEndian>(buffer.len() as u16).unwrap();
-        }
+    Cursor::new(&mut prefix[32..]).write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+    prefix[39] = Protocol::Udp.into();
 
-        ip::Packet::V6(ref _packet) => {
-            unimplemented!();
-        }
-    };
+    sum(&prefix, buffer)
+}
+
+pub(crate) fn sum(prefix: &[u8], buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, ReadBytesExt};
+    use std::io::Cursor;
 
     let mut result = 0xffffu32;
     let mut buffer = Cursor::new(buffer);
-    let mut prefix = match *ip {
-        ip::Packet::V4(_) => Cursor::new(&prefix[0..12]),
-
-        ip::Packet::V6(_) => Cursor::new(&prefix[0..40]),
-    };
+    let mut prefix = Cursor::new(prefix);
 
     while let Ok(value) = prefix.read_u16::<BigEndian>() {
         result += u32::from(value);
@@ -61,8 +80,9 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
         }
     }
 
-    if let Ok(value) = buffer.read_u8() {
-        let last = u32::from(value) << 8;
+    // A failed read may consume the odd byte, so take it from the slice.
+    if buffer.get_ref().len() % 2 == 1 {
+        let last = u32::from(buffer.get_ref()[buffer.get_ref().len() - 1]) << 8;
         result += last;
 
         if result > 0xffff {
@@ -70,5 +90,9 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
         }
     }
 
-    !result as u16
+    // A zero checksum means no checksum, so it's sent as all ones instead.
+    match !result as u16 {
+        0 => 0xffff,
+        value => value,
+    }
 }
@@ -265,6 +265,22 @@ mod test {
         assert_eq!(udp.destination(), 53);
     }
 
+    #[test]
+    fn values_v6() {
+        let raw = [
+            0x60u8, 0x00, 0x00, 0x00, 0x00, 0x14, 0x11, 0xff, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xff, 0x02, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfb, 0x14, 0xe9, 0x14, 0xe9, 0x00, 0x14, 0xd7, 0x73, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+        let udp = udp::Packet::new(ip.payload()).unwrap();
+
+        assert_eq!(udp.checksum(), 0xd773);
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+
+        assert_eq!(udp.destination(), 5353);
+    }
+
     #[test]
     fn mutable() {
         let mut raw = [
@@ -0,0 +1,214 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::Cursor;
+
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::error::*;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::udplite::checksum;
+use crate::udplite::Packet;
+
+/// UDP-Lite packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+    buffer: B,
+    finalizer: Finalization,
+
+    ip: (usize, usize),
+    payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+    fn with(mut buffer: B) -> Result<Self> {
+        let ip = (buffer.offset(), buffer.length());
+
+        use crate::size::header::Min;
+        buffer.next(Packet::<()>::min())?;
+
+        Ok(Builder {
+            buffer: buffer,
+            finalizer: Default::default(),
+
+            ip: ip,
+            payload: false,
+        })
+    }
+
+    fn finalizer(&mut self) -> &mut Finalization {
+        &mut self.finalizer
+    }
+
+    fn build(mut self) -> Result<B::Inner> {
+        self.prepare();
+
+        let mut buffer = self.buffer.into_inner();
+        self.finalizer.finalize(buffer.as_mut())?;
+        Ok(buffer)
+    }
+}
+
+impl Default for Builder<buffer::Dynamic> {
+    fn default() -> Self {
+        Builder::with(buffer::Dynamic::default()).unwrap()
+    }
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.buffer.data())
+    }
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.buffer.data_mut())
+    }
+}
+
+impl<B: Buffer> Builder<B> {
+    /// Source port.
+    pub fn source(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_source(value)?;
+        Ok(self)
+    }
+
+    /// Destination port.
+    pub fn destination(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
+        Ok(self)
+    }
+
+    /// Number of bytes covered by the checksum, zero means the whole packet.
+    pub fn coverage(mut self, value: u16) -> Result<Self> {
+        Packet::unchecked(self.buffer.data_mut()).set_coverage(value)?;
+        Ok(self)
+    }
+
+    /// Payload for the packet.
+    pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+        if self.payload {
+            return Err(ErrorKind::AlreadyDefined.into());
+        }
+
+        self.payload = true;
+
+        for byte in value {
+            self.buffer.more(1)?;
+            *self.buffer.data_mut().last_mut().unwrap() = *byte;
+        }
+
+        Ok(self)
+    }
+
+    fn prepare(&mut self) {
+        let ip = self.ip;
+        let length = self.buffer.length();
+
+        self.finalizer.add(move |out| {
+            let (before, after) = out.split_at_mut(ip.0 + ip.1);
+            let ip = &mut before[ip.0..];
+            let udplite = &mut after[..length];
+
+            if (&udplite[4..]).read_u16::<BigEndian>()? as usize > length {
+                return Err(ErrorKind::InvalidValue.into());
+            }
+
+            let checksum: Result<u16> = if let Ok(packet) = ip::v4::Packet::no_payload(&ip) {
+                Ok(checksum(&ip::Packet::from(packet), udplite))
+            } else if let Ok(packet) = ip::v6::Packet::no_payload(&ip) {
+                Ok(checksum(&ip::Packet::from(packet), udplite))
+            } else {
+                Err(ErrorKind::InvalidPacket.into())
+            };
+
+            Cursor::new(&mut udplite[6..]).write_u16::<BigEndian>(checksum?)?;
+
+            Ok(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::Builder;
+    use crate::ip;
+    use crate::packet::Packet;
+    use crate::udplite;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn simple() {
+        let packet = ip::v4::Builder::default()
+            .ttl(64)
+            .unwrap()
+            .source("192.168.1.1".parse().unwrap())
+            .unwrap()
+            .destination("192.168.1.2".parse().unwrap())
+            .unwrap()
+            .udplite()
+            .unwrap()
+            .source(5004)
+            .unwrap()
+            .destination(5006)
+            .unwrap()
+            .coverage(12)
+            .unwrap()
+            .payload(&[0x80, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xa0])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v4::Packet::new(packet).unwrap();
+        assert_eq!(ip.length(), 36);
+        assert_eq!(ip.protocol(), ip::Protocol::UdpLite);
+        assert!(ip.is_valid());
+
+        let udp = udplite::Packet::new(ip.payload()).unwrap();
+        assert_eq!(udp.source(), 5004);
+        assert_eq!(udp.destination(), 5006);
+        assert_eq!(udp.coverage(), 12);
+        assert_eq!(udp.checksum(), 0xd4eb);
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+    }
+
+    #[test]
+    fn v6() {
+        let packet = ip::v6::Builder::default()
+            .hop_limit(64)
+            .unwrap()
+            .source("2001:db8::1".parse().unwrap())
+            .unwrap()
+            .destination("2001:db8::2".parse().unwrap())
+            .unwrap()
+            .udplite()
+            .unwrap()
+            .source(1337)
+            .unwrap()
+            .destination(9001)
+            .unwrap()
+            .payload(b"lol")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let ip = ip::v6::Packet::new(packet).unwrap();
+        assert_eq!(ip.payload_length(), 11);
+        assert_eq!(ip.next_header(), ip::Protocol::UdpLite);
+        assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+
+        let udp = udplite::Packet::new(ip.payload()).unwrap();
+        assert_eq!(udp.coverage(), 0);
+        assert_eq!(udp.payload(), b"lol");
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+    }
+
+    #[test]
+    fn coverage() {
+        assert!(udplite::Builder::default().coverage(7).is_err());
+
+        assert!(ip::v4::Builder::default().udplite().unwrap().coverage(9).unwrap().payload(b"a").unwrap().build().is_ok());
+
+        assert!(ip::v4::Builder::default().udplite().unwrap().coverage(9).unwrap().build().is_err());
+    }
+}
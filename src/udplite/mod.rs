@@ -0,0 +1,70 @@
+mod packet;
+pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
+
+use crate::ip;
+use crate::ip::Protocol;
+use crate::udp::sum;
+
+/// Calculate the checksum for a UDP-Lite packet.
+///
+/// # Note
+///
+/// Since the checksum for UDP-Lite packets includes a pseudo-header based on
+/// the enclosing IP packet, one has to be given.
+///
+/// Only the bytes covered by the checksum coverage field are summed, a
+/// coverage of zero covers the whole packet.
+pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
+    match *ip {
+        ip::Packet::V4(ref packet) => checksum_v4(packet, buffer),
+
+        ip::Packet::V6(ref packet) => checksum_v6(packet, buffer),
+    }
+}
+
+/// Calculate the checksum for a UDP-Lite packet enclosed in an IPv4 packet.
+pub fn checksum_v4<B: AsRef<[u8]>>(ip: &ip::v4::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    let mut prefix = [0u8; 12];
+    prefix[0..4].copy_from_slice(&ip.source().octets());
+    prefix[4..8].copy_from_slice(&ip.destination().octets());
+
+    prefix[9] = Protocol::UdpLite.into();
+    Cursor::new(&mut prefix[10..]).write_u16::<BigEndian>(buffer.len() as u16).unwrap();
+
+    sum(&prefix, covered(buffer))
+}
+
+/// Calculate the checksum for a UDP-Lite packet enclosed in an IPv6 packet.
+pub fn checksum_v6<B: AsRef<[u8]>>(ip: &ip::v6::Packet<B>, buffer: &[u8]) -> u16 {
+    use byteorder::{BigEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    let mut prefix = [0u8; 40];
+    prefix[0..16].copy_from_slice(&ip.source().octets());
+    prefix[16..32].copy_from_slice(&ip.final_destination().octets());
+
+    Cursor::new(&mut prefix[32..]).write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+    prefix[39] = Protocol::UdpLite.into();
+
+    sum(&prefix, covered(buffer))
+}
+
+/// The part of the buffer covered by the checksum.
+fn covered(buffer: &[u8]) -> &[u8] {
+    use byteorder::{BigEndian, ReadBytesExt};
+
+    if buffer.len() < 6 {
+        return buffer;
+    }
+
+    match (&buffer[4..]).read_u16::<BigEndian>().unwrap() as usize {
+        0 => buffer,
+        coverage => &buffer[..coverage.min(buffer.len())],
+    }
+}
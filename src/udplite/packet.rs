@@ -0,0 +1,333 @@
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::error::*;
+use crate::ip;
+use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
+use crate::udplite::checksum;
+
+/// UDP-Lite packet parser.
+pub struct Packet<B> {
+    buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  u16::max_value() as usize - 8,
+		size: p => p.buffer.as_ref().len() - 8,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("udplite::Packet")
+            .field("source", &self.source())
+            .field("destination", &self.destination())
+            .field("coverage", &self.coverage())
+            .field("checksum", &self.checksum())
+            .field("payload", &self.payload())
+            .finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Create a UDP-Lite packet without checking.
+    pub fn unchecked(buffer: B) -> Packet<B> {
+        Packet { buffer }
+    }
+
+    /// Parse a UDP-Lite packet without checking the payload.
+    pub fn no_payload(buffer: B) -> Result<Packet<B>> {
+        use crate::size::header::Min;
+
+        let packet = Packet::unchecked(buffer);
+
+        if packet.buffer.as_ref().len() < Self::min() {
+            return Err(ErrorKind::SmallBuffer.into());
+        }
+
+        Ok(packet)
+    }
+
+    /// Parse a UDP-Lite packet, checking the buffer contents are correct.
+    pub fn new(buffer: B) -> Result<Packet<B>> {
+        let packet = Packet::no_payload(buffer)?;
+
+        match packet.coverage() {
+            1..=7 => return Err(ErrorKind::InvalidPacket.into()),
+
+            coverage if packet.buffer.as_ref().len() < coverage as usize => return Err(ErrorKind::SmallBuffer.into()),
+
+            _ => (),
+        }
+
+        Ok(packet)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Convert the packet to its owned version.
+    ///
+    /// # Notes
+    ///
+    /// It would be nice if `ToOwned` could be implemented, but `Packet` already
+    /// implements `Clone` and the impl would conflict.
+    pub fn to_owned(&self) -> Packet<Vec<u8>> {
+        Packet {
+            buffer: self.buffer.as_ref().to_vec(),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+    fn as_ref(&self) -> &[u8] {
+        use crate::size::Size;
+
+        &self.buffer.as_ref()[..self.size()]
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        use crate::size::Size;
+
+        let size = self.size();
+        &mut self.buffer.as_mut()[..size]
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+    fn as_packet(&self) -> Result<Packet<&[u8]>> {
+        Packet::new(self.as_ref())
+    }
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+    fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+        Packet::new(self.as_mut())
+    }
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+    fn split(&self) -> (&[u8], &[u8]) {
+        use crate::size::payload::Size;
+
+        let header = 8;
+        let payload = self.size();
+
+        let buffer = self.buffer.as_ref();
+        let buffer = if buffer.len() < header + payload { buffer } else { &buffer[..header + payload] };
+
+        buffer.split_at(header)
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+    fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+        use crate::size::payload::Size;
+
+        let header = 8;
+        let payload = self.size();
+
+        let buffer = self.buffer.as_mut();
+        let buffer = if buffer.len() < header + payload { buffer } else { &mut buffer[..header + payload] };
+
+        buffer.split_at_mut(header)
+    }
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+    /// Source port.
+    pub fn source(&self) -> u16 {
+        (&self.buffer.as_ref()[0..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Destination port.
+    pub fn destination(&self) -> u16 {
+        (&self.buffer.as_ref()[2..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Number of bytes covered by the checksum, zero means the whole packet.
+    pub fn coverage(&self) -> u16 {
+        (&self.buffer.as_ref()[4..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Checksum of the packet.
+    pub fn checksum(&self) -> u16 {
+        (&self.buffer.as_ref()[6..]).read_u16::<BigEndian>().unwrap()
+    }
+
+    /// Verify the packet is valid by calculating the checksum.
+    pub fn is_valid<I: AsRef<[u8]>>(&self, ip: &ip::Packet<I>) -> bool {
+        checksum(ip, self.buffer.as_ref()) == self.checksum()
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+    /// Source port.
+    pub fn set_source(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.header_mut()[0..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Destination port.
+    pub fn set_destination(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.header_mut()[2..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Number of bytes covered by the checksum, zero means the whole packet.
+    pub fn set_coverage(&mut self, value: u16) -> Result<&mut Self> {
+        if (1..8).contains(&value) {
+            return Err(ErrorKind::InvalidValue.into());
+        }
+
+        Cursor::new(&mut self.header_mut()[4..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Create a checksumed setter.
+    pub fn checked<'a, 'b, BI: AsRef<[u8]> + 'b>(&'a mut self, ip: &'b ip::Packet<BI>) -> Checked<'a, 'b, B, BI> {
+        Checked { packet: self, ip: ip }
+    }
+
+    /// Set the checksum value.
+    pub fn set_checksum(&mut self, value: u16) -> Result<&mut Self> {
+        Cursor::new(&mut self.header_mut()[6..]).write_u16::<BigEndian>(value)?;
+
+        Ok(self)
+    }
+
+    /// Recalculate and set the checksum value.
+    pub fn update_checksum<BI: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>) -> Result<&mut Self> {
+        let checksum = checksum(ip, self.buffer.as_ref());
+        self.set_checksum(checksum)
+    }
+}
+
+/// Checked wrapper for UDP-Lite packets.
+///
+/// # Note
+///
+/// The checksum recalculation happens on `Drop`, so don't leak it.
+pub struct Checked<'a, 'b, BP, BI>
+where
+    BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
+    BI: AsRef<[u8]> + 'b,
+{
+    packet: &'a mut Packet<BP>,
+    ip: &'b ip::Packet<BI>,
+}
+
+impl<'a, 'b, BP, BI> Checked<'a, 'b, BP, BI>
+where
+    BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
+    BI: AsRef<[u8]> + 'b,
+{
+    /// Source port.
+    pub fn set_source(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_source(value)?;
+        Ok(self)
+    }
+
+    /// Destination port.
+    pub fn set_destination(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_destination(value)?;
+        Ok(self)
+    }
+
+    /// Number of bytes covered by the checksum, zero means the whole packet.
+    pub fn set_coverage(&mut self, value: u16) -> Result<&mut Self> {
+        self.packet.set_coverage(value)?;
+        Ok(self)
+    }
+}
+
+impl<'a, 'b, BP, BI> Drop for Checked<'a, 'b, BP, BI>
+where
+    BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
+    BI: AsRef<[u8]> + 'b,
+{
+    fn drop(&mut self) {
+        self.packet.update_checksum(self.ip).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ip;
+    use crate::packet::{Packet, PacketMut};
+    use crate::udplite;
+
+    #[test]
+    fn values() {
+        let raw = [
+            0x45u8, 0x00, 0x00, 0x24, 0x12, 0x34, 0x40, 0x00, 0x40, 0x88, 0xa4, 0xca, 0xc0, 0xa8, 0x01, 0x01, 0xc0, 0xa8, 0x01, 0x02, 0x13, 0x8c, 0x13, 0x8e, 0x00, 0x0c, 0xd4, 0xeb, 0x80, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0xa0,
+        ];
+
+        let ip = ip::v4::Packet::new(&raw[..]).unwrap();
+        let udp = udplite::Packet::new(ip.payload()).unwrap();
+
+        assert!(ip.is_valid());
+        assert_eq!(ip.protocol(), ip::Protocol::UdpLite);
+        assert!(udp.is_valid(&ip::Packet::from(&ip)));
+
+        assert_eq!(udp.source(), 5004);
+        assert_eq!(udp.destination(), 5006);
+        assert_eq!(udp.coverage(), 12);
+        assert_eq!(udp.checksum(), 0xd4eb);
+        assert_eq!(udp.payload(), &[0x80, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xa0]);
+    }
+
+    #[test]
+    fn coverage() {
+        assert!(udplite::Packet::new(&[0x13, 0x8c, 0x13, 0x8e, 0x00, 0x07, 0x00, 0x00][..]).is_err());
+        assert!(udplite::Packet::new(&[0x13, 0x8c, 0x13, 0x8e, 0x00, 0x09, 0x00, 0x00][..]).is_err());
+        assert!(udplite::Packet::new(&[0x13, 0x8c, 0x13, 0x8e, 0x00, 0x08, 0x00, 0x00][..]).is_ok());
+        assert!(udplite::Packet::new(&[0x13, 0x8c, 0x13, 0x8e, 0x00, 0x00, 0x00, 0x00][..]).is_ok());
+    }
+
+    #[test]
+    fn mutable() {
+        let mut raw = [
+            0x45u8, 0x00, 0x00, 0x24, 0x12, 0x34, 0x40, 0x00, 0x40, 0x88, 0xa4, 0xca, 0xc0, 0xa8, 0x01, 0x01, 0xc0, 0xa8, 0x01, 0x02, 0x13, 0x8c, 0x13, 0x8e, 0x00, 0x0c, 0xd4, 0xeb, 0x80, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x00, 0xa0,
+        ];
+
+        let mut ip = ip::v4::Packet::new(&mut raw[..]).unwrap();
+        let (ip, udp) = ip.split_mut();
+        let ip = ip::Packet::from(ip::v4::Packet::unchecked(ip));
+        let mut udp = udplite::Packet::new(udp).unwrap();
+
+        // Bytes past the coverage are not checksummed.
+        udp.payload_mut()[7] = 0xff;
+        assert!(udp.is_valid(&ip));
+
+        udp.payload_mut()[3] = 0xff;
+        assert!(!udp.is_valid(&ip));
+
+        udp.update_checksum(&ip).unwrap();
+        assert!(udp.is_valid(&ip));
+
+        assert!(udp.set_coverage(4).is_err());
+        udp.set_coverage(0).unwrap();
+        assert!(!udp.is_valid(&ip));
+
+        udp.checked(&ip).set_coverage(16).unwrap();
+        assert!(udp.is_valid(&ip));
+
+        udp.payload_mut()[7] = 0xa0;
+        assert!(!udp.is_valid(&ip));
+    }
+}